@@ -0,0 +1,236 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Ident, LitInt, Member, Path};
+
+use crate::crate_path;
+
+/// The options of a field, parsed from its `#[heed(...)]` attributes.
+struct FieldAttrs {
+    little_endian: bool,
+    length_prefix: Option<Ident>,
+}
+
+impl FieldAttrs {
+    fn parse(field: &syn::Field) -> syn::Result<FieldAttrs> {
+        let mut little_endian = false;
+        let mut length_prefix = None;
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("heed")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("little_endian") {
+                    little_endian = true;
+                } else if meta.path.is_ident("big_endian") {
+                    little_endian = false;
+                } else if meta.path.is_ident("length_prefix") {
+                    let ident: Ident = meta.value()?.parse()?;
+                    let variant = match ident.to_string().as_str() {
+                        "u8" => "U8",
+                        "u16" => "U16",
+                        "u32" => "U32",
+                        "u64" => "U64",
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                ident,
+                                "the length prefix must be one of u8, u16, u32 or u64",
+                            ))
+                        }
+                    };
+                    length_prefix = Some(Ident::new(variant, ident.span()));
+                } else {
+                    return Err(meta.error("unsupported heed field attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(FieldAttrs { little_endian, length_prefix })
+    }
+}
+
+/// Generates the `FieldOptions` of each field, the last one inherits the `last` option.
+fn fields_options(fields: &Fields, krate: &Path) -> syn::Result<Vec<TokenStream>> {
+    let len = fields.len();
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let FieldAttrs { little_endian, length_prefix } = FieldAttrs::parse(field)?;
+            let length_prefix = match length_prefix {
+                Some(prefix) => {
+                    quote!(::std::option::Option::Some(#krate::types::LengthPrefix::#prefix))
+                }
+                None => quote!(::std::option::Option::None),
+            };
+            let last = if i + 1 == len { quote!(options.last) } else { quote!(false) };
+            Ok(quote! {
+                #krate::types::FieldOptions {
+                    little_endian: #little_endian,
+                    length_prefix: #length_prefix,
+                    last: #last,
+                }
+            })
+        })
+        .collect()
+}
+
+fn members(fields: &Fields) -> Vec<Member> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        })
+        .collect()
+}
+
+/// Parses the `#[heed(tag = ...)]` variant attribute.
+fn variant_tag(variant: &syn::Variant, default: usize) -> syn::Result<u8> {
+    let mut tag = None;
+    for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("heed")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("tag") {
+                let lit: LitInt = meta.value()?.parse()?;
+                tag = Some(lit.base10_parse::<u8>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported heed variant attribute"))
+            }
+        })?;
+    }
+
+    match tag {
+        Some(tag) => Ok(tag),
+        None => u8::try_from(default).map_err(|_| {
+            syn::Error::new_spanned(variant, "an enum can only have up to 256 variants")
+        }),
+    }
+}
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "HeedCodec cannot be derived for generic types",
+        ));
+    }
+
+    let krate = crate_path(input)?;
+    let field_codec = quote!(#krate::types::FieldCodec);
+
+    let (encode, decode) = match &input.data {
+        Data::Struct(data) => {
+            let options = fields_options(&data.fields, &krate)?;
+            let members = members(&data.fields);
+            let encode = quote! {
+                #(#field_codec::encode_field(&self.#members, #options, output)?;)*
+                ::std::result::Result::Ok(())
+            };
+            let decode = quote! {
+                ::std::result::Result::Ok(Self {
+                    #(#members: #field_codec::decode_field(input, #options)?,)*
+                })
+            };
+            (encode, decode)
+        }
+        Data::Enum(data) => {
+            let mut encode_arms = Vec::new();
+            let mut decode_arms = Vec::new();
+            let mut tags = Vec::new();
+
+            for (i, variant) in data.variants.iter().enumerate() {
+                let tag = variant_tag(variant, i)?;
+                if tags.contains(&tag) {
+                    return Err(syn::Error::new_spanned(variant, "duplicate enum tag"));
+                }
+                tags.push(tag);
+
+                let ident = &variant.ident;
+                let options = fields_options(&variant.fields, &krate)?;
+                let members = members(&variant.fields);
+                let bindings: Vec<_> =
+                    (0..members.len()).map(|i| format_ident!("__field_{}", i)).collect();
+
+                encode_arms.push(quote! {
+                    Self::#ident { #(#members: #bindings),* } => {
+                        #field_codec::encode_field(&#tag, options, output)?;
+                        #(#field_codec::encode_field(#bindings, #options, output)?;)*
+                    }
+                });
+                decode_arms.push(quote! {
+                    #tag => ::std::result::Result::Ok(Self::#ident {
+                        #(#members: #field_codec::decode_field(input, #options)?,)*
+                    }),
+                });
+            }
+
+            let encode = quote! {
+                match self {
+                    #(#encode_arms)*
+                }
+                ::std::result::Result::Ok(())
+            };
+            let decode = quote! {
+                let tag: u8 = #field_codec::decode_field(input, options)?;
+                match tag {
+                    #(#decode_arms)*
+                    tag => ::std::result::Result::Err(#krate::types::FieldError::UnknownTag(tag).into()),
+                }
+            };
+            (encode, decode)
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(input, "HeedCodec cannot be derived for unions"))
+        }
+    };
+
+    let ident = &input.ident;
+    Ok(quote! {
+        impl #field_codec for #ident {
+            #[allow(unused_variables)]
+            fn encode_field(
+                &self,
+                options: #krate::types::FieldOptions,
+                output: &mut ::std::vec::Vec<u8>,
+            ) -> ::std::result::Result<(), #krate::BoxedError> {
+                #encode
+            }
+
+            #[allow(unused_variables)]
+            fn decode_field(
+                input: &mut &[u8],
+                options: #krate::types::FieldOptions,
+            ) -> ::std::result::Result<Self, #krate::BoxedError> {
+                #decode
+            }
+        }
+
+        impl<'a> #krate::BytesEncode<'a> for #ident {
+            type EItem = Self;
+
+            fn bytes_encode(
+                item: &'a Self,
+            ) -> ::std::result::Result<::std::borrow::Cow<'a, [u8]>, #krate::BoxedError> {
+                let mut output = ::std::vec::Vec::new();
+                #field_codec::encode_field(item, #krate::types::FieldOptions::ROOT, &mut output)?;
+                ::std::result::Result::Ok(::std::borrow::Cow::Owned(output))
+            }
+        }
+
+        impl<'a> #krate::BytesEncodeInto<'a> for #ident {}
+
+        impl<'a> #krate::BytesDecode<'a> for #ident {
+            type DItem = Self;
+
+            fn bytes_decode(bytes: &'a [u8]) -> ::std::result::Result<Self, #krate::BoxedError> {
+                let mut input = bytes;
+                let item = #field_codec::decode_field(&mut input, #krate::types::FieldOptions::ROOT)?;
+                if input.is_empty() {
+                    ::std::result::Result::Ok(item)
+                } else {
+                    ::std::result::Result::Err(#krate::types::FieldError::TrailingBytes.into())
+                }
+            }
+        }
+    })
+}
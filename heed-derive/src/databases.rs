@@ -0,0 +1,140 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, Path, Type};
+
+use crate::crate_path;
+
+/// The options of a `Database` field, parsed from its `#[heed(...)]` attributes.
+struct DatabaseField {
+    ident: Ident,
+    name: Option<String>,
+    flags: Vec<Ident>,
+    key_comparator: Option<Type>,
+    dup_sort_comparator: Option<Type>,
+}
+
+impl DatabaseField {
+    fn parse(field: &syn::Field) -> syn::Result<DatabaseField> {
+        let ident = field.ident.clone().unwrap();
+        let mut name = Some(ident.to_string());
+        let mut flags = Vec::new();
+        let mut key_comparator = None;
+        let mut dup_sort_comparator = None;
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("heed")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    name = Some(lit.value());
+                } else if meta.path.is_ident("unnamed") {
+                    name = None;
+                } else if meta.path.is_ident("flags") {
+                    meta.parse_nested_meta(|flag| {
+                        flags.push(flag.path.require_ident()?.clone());
+                        Ok(())
+                    })?;
+                } else if meta.path.is_ident("key_comparator") {
+                    key_comparator = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("dup_sort_comparator") {
+                    dup_sort_comparator = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unsupported heed database attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(DatabaseField { ident, name, flags, key_comparator, dup_sort_comparator })
+    }
+
+    /// Generates the expression that builds the `DatabaseOpenOptions` of this field.
+    fn options(&self, krate: &Path) -> TokenStream {
+        let key_comparator = match &self.key_comparator {
+            Some(ty) => quote!(::<#ty>),
+            None => quote!(),
+        };
+        let dup_sort_comparator = match &self.dup_sort_comparator {
+            Some(ty) => quote!(::<#ty>),
+            None => quote!(),
+        };
+        let name = self.name.as_ref().map(|name| quote!(options.name(#name);));
+        let flags = (!self.flags.is_empty()).then(|| {
+            let flags = &self.flags;
+            quote!(options.flags(#(#krate::DatabaseFlags::#flags)|*);)
+        });
+
+        quote! {{
+            let mut options = env
+                .database_options()
+                .types()
+                .key_comparator #key_comparator ()
+                .dup_sort_comparator #dup_sort_comparator ();
+            #name
+            #flags
+            options
+        }}
+    }
+}
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "Databases can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(input, "Databases can only be derived for structs"))
+        }
+    };
+
+    let krate = crate_path(input)?;
+    let fields = fields.iter().map(DatabaseField::parse).collect::<syn::Result<Vec<_>>>()?;
+    let count = fields.iter().filter(|field| field.name.is_some()).count() as u32;
+
+    let idents: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+    let bindings: Vec<_> = (0..fields.len()).map(|i| format_ident!("__database_{}", i)).collect();
+    let options: Vec<_> = fields.iter().map(|field| field.options(&krate)).collect();
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #krate::Databases for #ident #ty_generics #where_clause {
+            const COUNT: u32 = #count;
+
+            fn open<__Tls>(
+                env: &#krate::Env<__Tls>,
+                rtxn: &#krate::RoTxn,
+            ) -> #krate::Result<::std::option::Option<Self>> {
+                #(
+                    let #bindings = #options;
+                    let #bindings = match #bindings.open(rtxn)? {
+                        ::std::option::Option::Some(database) => database,
+                        ::std::option::Option::None => {
+                            return ::std::result::Result::Ok(::std::option::Option::None)
+                        }
+                    };
+                )*
+                ::std::result::Result::Ok(::std::option::Option::Some(#ident {
+                    #(#idents: #bindings),*
+                }))
+            }
+
+            fn create<__Tls>(
+                env: &#krate::Env<__Tls>,
+                wtxn: &mut #krate::RwTxn,
+            ) -> #krate::Result<Self> {
+                #(
+                    let #bindings = #options;
+                    let #bindings = #bindings.create(wtxn)?;
+                )*
+                ::std::result::Result::Ok(#ident { #(#idents: #bindings),* })
+            }
+        }
+    })
+}
@@ -0,0 +1,120 @@
+#![doc(
+    html_favicon_url = "https://raw.githubusercontent.com/meilisearch/heed/main/assets/heed-pigeon.ico?raw=true"
+)]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/meilisearch/heed/main/assets/heed-pigeon-logo.png?raw=true"
+)]
+
+//! Contains the derive macros of heed, they are re-exported by heed when the `derive` feature is enabled.
+
+#![warn(missing_docs)]
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, LitStr, Path};
+
+mod codec;
+mod databases;
+
+/// Implements the `heed::Databases` trait for a struct of `Database` fields.
+///
+/// The databases are named after the fields, this and the way they are opened
+/// can be customized with the `#[heed(...)]` field attribute:
+///
+/// - `name = "..."` sets the name of the database,
+/// - `unnamed` uses the unnamed database of the environment,
+/// - `flags(DUP_SORT, ...)` sets the `DatabaseFlags` of the database,
+/// - `key_comparator = Type` and `dup_sort_comparator = Type` set the comparators
+///   of the database, they are inferred from the type of the field otherwise.
+///
+/// The `#[heed(crate = "heed3")]` container attribute must be used when
+/// the derive is used with the `heed3` crate.
+///
+/// ```ignore
+/// use heed::types::*;
+/// use heed::{Database, Databases, EnvOpenOptions};
+///
+/// #[derive(Databases)]
+/// struct MyDatabases {
+///     users: Database<Str, SerdeJson<User>>,
+///     #[heed(name = "user-by-email")]
+///     user_by_email: Database<Str, Str>,
+///     #[heed(flags(DUP_SORT))]
+///     followers: Database<Str, Str>,
+/// }
+///
+/// let env = unsafe { EnvOpenOptions::new().max_dbs(MyDatabases::COUNT).open(path)? };
+/// let mut wtxn = env.write_txn()?;
+/// let dbs = MyDatabases::create(&env, &mut wtxn)?;
+/// wtxn.commit()?;
+/// ```
+#[proc_macro_derive(Databases, attributes(heed))]
+pub fn derive_databases(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    databases::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Implements the `BytesEncode` and `BytesDecode` traits for a struct or an enum, the type
+/// is its own codec. It also implements the `heed::types::FieldCodec` trait so that it can
+/// be used as a field of another type deriving `HeedCodec`.
+///
+/// The encoding is order-preserving: the fields are written one after the other, the
+/// integers in big-endian, so that the keys sort in field order under the `DefaultComparator`.
+/// The `String`s and `Vec<u8>`s are escaped and terminated like the elements of a
+/// `heed::types::Tuple`, unless they are the last field.
+/// Integers, booleans, byte arrays, `String`s, `Vec<u8>`s and types deriving `HeedCodec`
+/// are supported. Enums are encoded as a tag byte, the index of the variant, followed by
+/// the fields of the variant.
+///
+/// The encoding can be customized with the `#[heed(...)]` attributes:
+///
+/// - `little_endian` on a field encodes its integers in little-endian, which is not
+///   order-preserving,
+/// - `length_prefix = u8` on a `String` or `Vec<u8>` field prefixes it by its length instead
+///   of escaping and terminating it, which is not order-preserving as it sorts by length first,
+/// - `tag = 3` on an enum variant sets its tag byte.
+///
+/// The `#[heed(crate = "heed3")]` container attribute must be used when
+/// the derive is used with the `heed3` crate.
+///
+/// ```ignore
+/// use heed::types::*;
+/// use heed::{Database, HeedCodec};
+///
+/// #[derive(HeedCodec)]
+/// enum Level {
+///     Debug,
+///     Warn,
+///     Error,
+/// }
+///
+/// #[derive(HeedCodec)]
+/// struct LogKey {
+///     timestamp: u32,
+///     level: Level,
+/// }
+///
+/// let db: Database<LogKey, Str> = env.create_database(&mut wtxn, Some("logs"))?;
+/// db.put(&mut wtxn, &LogKey { timestamp: 1608326232, level: Level::Debug }, "this is a very old log")?;
+/// ```
+#[proc_macro_derive(HeedCodec, attributes(heed))]
+pub fn derive_heed_codec(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    codec::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Parses the `#[heed(crate = "...")]` container attribute.
+pub(crate) fn crate_path(input: &DeriveInput) -> syn::Result<Path> {
+    let mut krate = syn::parse_quote!(::heed);
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("heed")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                let lit: LitStr = meta.value()?.parse()?;
+                krate = lit.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported heed container attribute"))
+            }
+        })?;
+    }
+    Ok(krate)
+}
@@ -0,0 +1,94 @@
+use std::borrow::Cow;
+
+use heed::{BytesDecode, BytesEncode, HeedCodec};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, HeedCodec)]
+enum Level {
+    Debug,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, HeedCodec)]
+struct Service {
+    name: String,
+    region: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, HeedCodec)]
+struct LogKey {
+    service: Service,
+    level: Level,
+    offset: i32,
+    message: String,
+    id: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, HeedCodec)]
+enum Tagged {
+    #[heed(tag = 2)]
+    Second,
+    #[heed(tag = 1)]
+    First(String),
+}
+
+fn encode<'a, C: BytesEncode<'a>>(item: &'a C::EItem) -> Cow<'a, [u8]> {
+    C::bytes_encode(item).unwrap()
+}
+
+fn keys() -> Vec<LogKey> {
+    let services = [("b", &b"eu"[..]), ("aa", b"eu"), ("a", b"us\0"), ("a", b"us"), ("a\0", b"")];
+    let mut keys = Vec::new();
+    for (name, region) in services {
+        for level in [Level::Error, Level::Debug, Level::Warn] {
+            for offset in [300, -1, i32::MIN, 0] {
+                for message in ["", "\0", "b", "aa"] {
+                    keys.push(LogKey {
+                        service: Service { name: name.into(), region: region.into() },
+                        level: level.clone(),
+                        offset,
+                        message: message.into(),
+                        id: vec![offset as u8, 0],
+                    });
+                }
+            }
+        }
+    }
+    keys
+}
+
+#[test]
+fn keys_sort_in_field_order() {
+    let mut keys = keys();
+    keys.sort();
+
+    let encoded: Vec<_> = keys.iter().map(encode::<LogKey>).collect();
+    for (window, keys) in encoded.windows(2).zip(keys.windows(2)) {
+        assert!(window[0] < window[1], "{:?} doesn't sort before {:?}", keys[0], keys[1]);
+    }
+}
+
+#[test]
+fn keys_round_trip() {
+    for key in keys() {
+        let bytes = encode::<LogKey>(&key);
+        assert_eq!(LogKey::bytes_decode(&bytes).unwrap(), key);
+    }
+}
+
+#[test]
+fn variants_sort_by_tag() {
+    let first = encode::<Tagged>(&Tagged::First("z".into())).into_owned();
+    let second = encode::<Tagged>(&Tagged::Second).into_owned();
+    assert!(first < second);
+    assert_eq!(Tagged::bytes_decode(&first).unwrap(), Tagged::First("z".into()));
+    assert_eq!(Tagged::bytes_decode(&second).unwrap(), Tagged::Second);
+}
+
+#[test]
+fn invalid_bytes() {
+    let key = encode::<Level>(&Level::Warn).into_owned();
+    assert!(Level::bytes_decode(&[3]).is_err());
+    assert!(Level::bytes_decode(&[key[0], 0]).is_err());
+    assert!(Service::bytes_decode(b"name-without-terminator").is_err());
+}
@@ -0,0 +1,156 @@
+use std::cmp::Ordering;
+
+use heed::byteorder::{BigEndian, NativeEndian};
+use heed::types::*;
+use heed::{Comparator, Database, Databases, Env, EnvOpenOptions, IntegerComparator, Result};
+
+/// Sorts the keys in the reverse lexicographic order.
+enum ReverseComparator {}
+
+impl Comparator for ReverseComparator {
+    fn compare(a: &[u8], b: &[u8]) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+#[derive(Databases)]
+struct Named {
+    users: Database<U32<BigEndian>, Str>,
+    #[heed(name = "user-by-name")]
+    user_by_name: Database<Str, U32<BigEndian>>,
+}
+
+#[derive(Databases)]
+struct Mixed {
+    #[heed(unnamed)]
+    main: Database<Str, Str>,
+    #[heed(name = "mixed-other")]
+    other: Database<Str, Str>,
+}
+
+#[derive(Databases)]
+struct Flagged {
+    #[heed(flags(DUP_SORT))]
+    followers: Database<Str, Str>,
+    #[heed(flags(DUP_SORT, DUP_FIXED))]
+    scores: Database<Str, U32<BigEndian>>,
+}
+
+#[derive(Databases)]
+struct Comparators {
+    #[heed(key_comparator = ReverseComparator)]
+    reversed: Database<Str, Unit, ReverseComparator>,
+    inferred: Database<Str, Unit, ReverseComparator>,
+    #[heed(key_comparator = IntegerComparator)]
+    integers: Database<U32<NativeEndian>, Unit, IntegerComparator>,
+    #[heed(flags(DUP_SORT), dup_sort_comparator = ReverseComparator)]
+    reversed_dups: Database<Str, Str, heed::DefaultComparator, ReverseComparator>,
+}
+
+fn env(max_dbs: u32) -> Result<(tempfile::TempDir, Env)> {
+    let dir = tempfile::tempdir()?;
+    let env = unsafe { EnvOpenOptions::new().max_dbs(max_dbs).open(dir.path())? };
+    Ok((dir, env))
+}
+
+#[test]
+fn count_named_databases() {
+    assert_eq!(Named::COUNT, 2);
+    assert_eq!(Mixed::COUNT, 1);
+    assert_eq!(Flagged::COUNT, 2);
+    assert_eq!(Comparators::COUNT, 4);
+}
+
+#[test]
+fn open_and_create_named_databases() -> Result<()> {
+    let (_dir, env) = env(Named::COUNT)?;
+
+    let rtxn = env.read_txn()?;
+    assert!(Named::open(&env, &rtxn)?.is_none());
+    drop(rtxn);
+
+    let mut wtxn = env.write_txn()?;
+    let dbs = Named::create(&env, &mut wtxn)?;
+    dbs.users.put(&mut wtxn, &0, "kero")?;
+    dbs.user_by_name.put(&mut wtxn, "kero", &0)?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let users = env.open_database::<U32<BigEndian>, Str>(&rtxn, Some("users"))?.unwrap();
+    assert_eq!(users.get(&rtxn, &0)?, Some("kero"));
+    let by_name = env.open_database::<Str, U32<BigEndian>>(&rtxn, Some("user-by-name"))?.unwrap();
+    assert_eq!(by_name.get(&rtxn, "kero")?, Some(0));
+    assert!(env.open_database::<Str, Str>(&rtxn, Some("user_by_name"))?.is_none());
+
+    let dbs = Named::open(&env, &rtxn)?.unwrap();
+    assert_eq!(dbs.user_by_name.get(&rtxn, "kero")?, Some(0));
+
+    Ok(())
+}
+
+#[test]
+fn unnamed_database() -> Result<()> {
+    let (_dir, env) = env(Mixed::COUNT)?;
+
+    let mut wtxn = env.write_txn()?;
+    let dbs = Mixed::create(&env, &mut wtxn)?;
+    dbs.main.put(&mut wtxn, "hello", "world")?;
+    dbs.other.put(&mut wtxn, "hello", "there")?;
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let main = env.open_database::<Str, Str>(&rtxn, None)?.unwrap();
+    assert_eq!(main.get(&rtxn, "hello")?, Some("world"));
+    assert!(env.open_database::<Str, Str>(&rtxn, Some("main"))?.is_none());
+    let other = env.open_database::<Str, Str>(&rtxn, Some("mixed-other"))?.unwrap();
+    assert_eq!(other.get(&rtxn, "hello")?, Some("there"));
+
+    Ok(())
+}
+
+#[test]
+fn database_flags() -> Result<()> {
+    let (_dir, env) = env(Flagged::COUNT)?;
+
+    let mut wtxn = env.write_txn()?;
+    let dbs = Flagged::create(&env, &mut wtxn)?;
+    dbs.followers.put(&mut wtxn, "kero", "loic")?;
+    dbs.followers.put(&mut wtxn, "kero", "tamo")?;
+    assert_eq!(dbs.followers.count_duplicates(&wtxn, "kero")?, 2);
+
+    dbs.scores.put_duplicates_fixed(&mut wtxn, "kero", &[1, 2, 3])?;
+    assert_eq!(dbs.scores.count_duplicates(&wtxn, "kero")?, 3);
+
+    Ok(())
+}
+
+#[test]
+fn database_comparators() -> Result<()> {
+    let (_dir, env) = env(Comparators::COUNT)?;
+
+    let mut wtxn = env.write_txn()?;
+    let dbs = Comparators::create(&env, &mut wtxn)?;
+    for key in ["a", "b", "c"] {
+        dbs.reversed.put(&mut wtxn, key, &())?;
+        dbs.inferred.put(&mut wtxn, key, &())?;
+        dbs.reversed_dups.put(&mut wtxn, "key", key)?;
+    }
+    for key in [256, 1, 2] {
+        dbs.integers.put(&mut wtxn, &key, &())?;
+    }
+
+    let keys: Vec<_> =
+        dbs.reversed.iter(&wtxn)?.map(|r| r.map(|(k, ())| k)).collect::<Result<_>>()?;
+    assert_eq!(keys, ["c", "b", "a"]);
+    let keys: Vec<_> =
+        dbs.inferred.iter(&wtxn)?.map(|r| r.map(|(k, ())| k)).collect::<Result<_>>()?;
+    assert_eq!(keys, ["c", "b", "a"]);
+    let keys: Vec<_> =
+        dbs.integers.iter(&wtxn)?.map(|r| r.map(|(k, ())| k)).collect::<Result<_>>()?;
+    assert_eq!(keys, [1, 2, 256]);
+    let values: Vec<_> =
+        dbs.reversed_dups.iter(&wtxn)?.map(|r| r.map(|(_, v)| v)).collect::<Result<_>>()?;
+    assert_eq!(values, ["c", "b", "a"]);
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+use heed::types::*;
+use heed::{Database, Databases};
+
+#[derive(Databases)]
+enum MyDatabases {
+    Users(Database<Str, Str>),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use heed::types::*;
+use heed::{Database, Databases};
+
+#[derive(Databases)]
+struct MyDatabases {
+    #[heed(key_comparator)]
+    users: Database<Str, Str>,
+}
+
+fn main() {}
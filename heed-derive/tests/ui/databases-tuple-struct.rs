@@ -0,0 +1,7 @@
+use heed::types::*;
+use heed::{Database, Databases};
+
+#[derive(Databases)]
+struct MyDatabases(Database<Str, Str>);
+
+fn main() {}
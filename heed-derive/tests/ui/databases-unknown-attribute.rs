@@ -0,0 +1,10 @@
+use heed::types::*;
+use heed::{Database, Databases};
+
+#[derive(Databases)]
+struct MyDatabases {
+    #[heed(nmae = "users")]
+    users: Database<Str, Str>,
+}
+
+fn main() {}
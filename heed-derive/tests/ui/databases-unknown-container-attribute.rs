@@ -0,0 +1,10 @@
+use heed::types::*;
+use heed::{Database, Databases};
+
+#[derive(Databases)]
+#[heed(krate = "heed3")]
+struct MyDatabases {
+    users: Database<Str, Str>,
+}
+
+fn main() {}
@@ -12,6 +12,7 @@
 use std::borrow::Cow;
 use std::cmp::{Ord, Ordering};
 use std::error::Error as StdError;
+use std::io;
 
 /// A boxed `Send + Sync + 'static` error.
 pub type BoxedError = Box<dyn StdError + Send + Sync + 'static>;
@@ -25,6 +26,34 @@ pub trait BytesEncode<'a> {
     fn bytes_encode(item: &'a Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError>;
 }
 
+/// An extension of [`BytesEncode`] that encodes items into caller-provided buffers.
+///
+/// `Database::put_into` encodes the keys and values of these codecs into buffers reused
+/// by the transaction. Both methods have a default implementation, an empty `impl` is
+/// enough for a codec that always allocates.
+pub trait BytesEncodeInto<'a>: BytesEncode<'a> {
+    /// Returns the number of bytes the given item is encoded into, if it is known
+    /// without encoding it.
+    ///
+    /// It is only used to reserve the capacity of the buffers, the size isn't trusted.
+    fn size_hint(item: &Self::EItem) -> Option<usize> {
+        let _ = item;
+        None
+    }
+
+    /// Encode the given item into the writer, without allocating when possible.
+    ///
+    /// The default implementation writes the bytes returned by [`BytesEncode::bytes_encode`],
+    /// codecs that allocate should write the bytes directly into the writer instead.
+    fn bytes_encode_into<W: io::Write>(
+        item: &'a Self::EItem,
+        writer: &mut W,
+    ) -> Result<(), BoxedError> {
+        let bytes = Self::bytes_encode(item)?;
+        writer.write_all(&bytes).map_err(Into::into)
+    }
+}
+
 /// A trait that represents a decoding structure.
 pub trait BytesDecode<'a> {
     /// The type to decode.
@@ -34,6 +63,21 @@ pub trait BytesDecode<'a> {
     fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError>;
 }
 
+/// Define a function that merges raw values together.
+///
+/// Merging updates a value without going through its codec, e.g. to add a number to a counter
+/// or to union a bitmap with another one, directly on the bytes stored in the database.
+pub trait MergeFunction {
+    /// Merges the operands, in order, into the existing value of the given key and writes the
+    /// merged value into `output`, which is empty. `existing` is `None` when the key doesn't exist.
+    fn merge(
+        key: &[u8],
+        existing: Option<&[u8]>,
+        operands: &[&[u8]],
+        output: &mut Vec<u8>,
+    ) -> Result<(), BoxedError>;
+}
+
 /// Define a custom key comparison function for a database.
 ///
 /// The comparison function is called whenever it is necessary to compare a key specified
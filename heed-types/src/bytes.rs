@@ -1,6 +1,7 @@
 use std::borrow::Cow;
+use std::io;
 
-use heed_traits::{BoxedError, BytesDecode, BytesEncode};
+use heed_traits::{BoxedError, BytesDecode, BytesEncode, BytesEncodeInto};
 
 /// Describes a byte slice `[u8]` that is totally borrowed and doesn't depend on
 /// any [memory alignment].
@@ -16,6 +17,19 @@ impl<'a> BytesEncode<'a> for Bytes {
     }
 }
 
+impl<'a> BytesEncodeInto<'a> for Bytes {
+    fn size_hint(item: &Self::EItem) -> Option<usize> {
+        Some(item.len())
+    }
+
+    fn bytes_encode_into<W: io::Write>(
+        item: &'a Self::EItem,
+        writer: &mut W,
+    ) -> Result<(), BoxedError> {
+        writer.write_all(item).map_err(Into::into)
+    }
+}
+
 impl<'a> BytesDecode<'a> for Bytes {
     type DItem = &'a [u8];
 
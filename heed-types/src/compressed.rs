@@ -0,0 +1,448 @@
+#[cfg(feature = "zstd")]
+use std::any::TypeId;
+use std::borrow::Cow;
+#[cfg(feature = "zstd")]
+use std::cell::RefCell;
+#[cfg(feature = "zstd")]
+use std::collections::hash_map::{Entry, HashMap};
+use std::marker::PhantomData;
+use std::{error, fmt, io};
+
+use heed_traits::{BoxedError, BytesDecode, BytesEncode, BytesEncodeInto};
+
+/// The version of the [`Compressed`] format, stored in the high bits of the header byte.
+const VERSION: u8 = 1;
+
+/// The algorithm identifier of the values stored without compression.
+const RAW: u8 = 0;
+
+/// The default size, in bytes, under which the values are stored without compression.
+pub const DEFAULT_COMPRESSION_THRESHOLD: usize = 128;
+
+/// The default maximum size, in bytes, of a decompressed value.
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+/// A codec wrapper that compresses the bytes produced by the inner codec `C`
+/// with the compression algorithm `A`.
+///
+/// The encoded bytes start with a header byte that stores the version of the format
+/// and the algorithm used. The values smaller than `THRESHOLD` bytes, larger than
+/// `MAX_SIZE` bytes, or that don't shrink once compressed, are stored raw after the header.
+/// The decompression fails instead of allocating more than `MAX_SIZE` bytes, whatever
+/// the size stored in the compressed bytes.
+///
+/// Compressed bytes can't be borrowed, therefore the inner codec must decode owned
+/// types, like the serde codecs do. Note that compression doesn't preserve the order
+/// of the bytes, use it for values, not keys.
+///
+/// ```
+/// # #[cfg(all(feature = "lz4", feature = "serde-json"))] {
+/// use heed_traits::{BytesDecode, BytesEncode};
+/// use heed_types::{Compressed, Lz4, SerdeJson};
+///
+/// type Codec = Compressed<SerdeJson<Vec<String>>, Lz4>;
+///
+/// let names = vec![String::from("kerollmops"); 100];
+/// let bytes = Codec::bytes_encode(&names)?;
+/// assert!(bytes.len() < serde_json::to_vec(&names)?.len());
+/// assert_eq!(Codec::bytes_decode(&bytes)?, names);
+/// # }
+/// # Ok::<(), heed_traits::BoxedError>(())
+/// ```
+pub struct Compressed<
+    C,
+    A,
+    const THRESHOLD: usize = DEFAULT_COMPRESSION_THRESHOLD,
+    const MAX_SIZE: usize = DEFAULT_MAX_DECOMPRESSED_SIZE,
+>(PhantomData<(C, A)>);
+
+/// A compression algorithm that can be used by the [`Compressed`] codec.
+pub trait Compression {
+    /// Identifies the algorithm in the header byte, it must be between `1` and `15`.
+    ///
+    /// The identifiers up to `7` are reserved for the algorithms provided by this crate.
+    const ID: u8;
+
+    /// Compresses the bytes.
+    fn compress(bytes: &[u8]) -> Result<Vec<u8>, BoxedError>;
+
+    /// Decompresses the bytes produced by [`Compression::compress`].
+    ///
+    /// It must return a [`CompressionError::TooLarge`] error instead of
+    /// decompressing more than `max_size` bytes.
+    fn decompress(bytes: &[u8], max_size: usize) -> Result<Vec<u8>, BoxedError>;
+}
+
+fn header(id: u8) -> u8 {
+    debug_assert!(id <= 0x0F, "the algorithm identifier must fit in four bits");
+    VERSION << 4 | id
+}
+
+impl<'a, C, A, const THRESHOLD: usize, const MAX_SIZE: usize> BytesEncode<'a>
+    for Compressed<C, A, THRESHOLD, MAX_SIZE>
+where
+    C: BytesEncode<'a>,
+    A: Compression,
+{
+    type EItem = C::EItem;
+
+    fn bytes_encode(item: &'a Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+        let mut output = Vec::new();
+        Self::bytes_encode_into(item, &mut output)?;
+        Ok(Cow::Owned(output))
+    }
+}
+
+impl<'a, C, A, const THRESHOLD: usize, const MAX_SIZE: usize> BytesEncodeInto<'a>
+    for Compressed<C, A, THRESHOLD, MAX_SIZE>
+where
+    C: BytesEncode<'a>,
+    A: Compression,
+{
+    fn bytes_encode_into<W: io::Write>(
+        item: &'a Self::EItem,
+        writer: &mut W,
+    ) -> Result<(), BoxedError> {
+        let bytes = C::bytes_encode(item)?;
+
+        if (THRESHOLD..=MAX_SIZE).contains(&bytes.len()) {
+            let compressed = A::compress(&bytes)?;
+            if compressed.len() < bytes.len() {
+                writer.write_all(&[header(A::ID)])?;
+                return writer.write_all(&compressed).map_err(Into::into);
+            }
+        }
+
+        writer.write_all(&[header(RAW)])?;
+        writer.write_all(&bytes).map_err(Into::into)
+    }
+}
+
+impl<'a, C, A, T, const THRESHOLD: usize, const MAX_SIZE: usize> BytesDecode<'a>
+    for Compressed<C, A, THRESHOLD, MAX_SIZE>
+where
+    C: for<'b> BytesDecode<'b, DItem = T>,
+    T: 'a,
+    A: Compression,
+{
+    type DItem = T;
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        let (&header, bytes) = bytes.split_first().ok_or(CompressionError::MissingHeader)?;
+
+        let version = header >> 4;
+        if version != VERSION {
+            return Err(CompressionError::UnsupportedVersion(version).into());
+        }
+
+        match header & 0x0F {
+            RAW => C::bytes_decode(bytes),
+            id if id == A::ID => C::bytes_decode(&A::decompress(bytes, MAX_SIZE)?),
+            id => Err(CompressionError::UnexpectedAlgorithm(id).into()),
+        }
+    }
+}
+
+unsafe impl<C, A, const THRESHOLD: usize, const MAX_SIZE: usize> Send
+    for Compressed<C, A, THRESHOLD, MAX_SIZE>
+{
+}
+
+unsafe impl<C, A, const THRESHOLD: usize, const MAX_SIZE: usize> Sync
+    for Compressed<C, A, THRESHOLD, MAX_SIZE>
+{
+}
+
+/// Checks that the decompressed size announced by the compressed bytes is allowed.
+#[cfg(any(test, feature = "lz4", feature = "zstd"))]
+fn check_size(size: usize, max_size: usize) -> Result<usize, CompressionError> {
+    if size <= max_size {
+        Ok(size)
+    } else {
+        Err(CompressionError::TooLarge(size))
+    }
+}
+
+/// The [LZ4](https://lz4.org) compression algorithm, fast but with a moderate ratio.
+#[cfg(feature = "lz4")]
+pub enum Lz4 {}
+
+#[cfg(feature = "lz4")]
+impl Compression for Lz4 {
+    const ID: u8 = 1;
+
+    fn compress(bytes: &[u8]) -> Result<Vec<u8>, BoxedError> {
+        Ok(lz4_flex::compress_prepend_size(bytes))
+    }
+
+    fn decompress(bytes: &[u8], max_size: usize) -> Result<Vec<u8>, BoxedError> {
+        let (size, bytes) = lz4_flex::block::uncompressed_size(bytes)?;
+        lz4_flex::decompress(bytes, check_size(size, max_size)?).map_err(Into::into)
+    }
+}
+
+/// The [Zstandard](https://facebook.github.io/zstd) compression algorithm
+/// with the given compression `LEVEL`.
+#[cfg(feature = "zstd")]
+pub enum Zstd<const LEVEL: i32 = { zstd::DEFAULT_COMPRESSION_LEVEL }> {}
+
+/// Returns the decompressed size of the Zstandard frame, written by the bulk compressor.
+#[cfg(feature = "zstd")]
+fn zstd_content_size(bytes: &[u8], max_size: usize) -> Result<usize, CompressionError> {
+    match zstd::zstd_safe::get_frame_content_size(bytes) {
+        Ok(Some(size)) => check_size(usize::try_from(size).unwrap_or(usize::MAX), max_size),
+        _ => Err(CompressionError::InvalidFrame),
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<const LEVEL: i32> Compression for Zstd<LEVEL> {
+    const ID: u8 = 2;
+
+    fn compress(bytes: &[u8]) -> Result<Vec<u8>, BoxedError> {
+        zstd::bulk::compress(bytes, LEVEL).map_err(Into::into)
+    }
+
+    fn decompress(bytes: &[u8], max_size: usize) -> Result<Vec<u8>, BoxedError> {
+        zstd::bulk::decompress(bytes, zstd_content_size(bytes, max_size)?).map_err(Into::into)
+    }
+}
+
+/// A trained Zstandard dictionary, used by the [`ZstdDict`] compression algorithm.
+///
+/// Dictionaries can be trained on a sample of the values with [`zstd::dict::from_samples`],
+/// they greatly improve the compression ratio of many small and similar values.
+/// The dictionary must not change once values have been compressed with it.
+///
+/// ```no_run
+/// # #[cfg(all(feature = "zstd", feature = "serde-json"))] {
+/// use std::sync::OnceLock;
+///
+/// use heed_types::{Compressed, SerdeJson, ZstdDict, ZstdDictionary};
+///
+/// enum Users {}
+///
+/// impl ZstdDictionary for Users {
+///     fn dictionary() -> &'static [u8] {
+///         static DICTIONARY: OnceLock<Vec<u8>> = OnceLock::new();
+///         // The dictionary trained with `zstd::dict::from_samples`.
+///         DICTIONARY.get_or_init(|| std::fs::read("users.dict").unwrap())
+///     }
+/// }
+///
+/// type UserCodec = Compressed<SerdeJson<Vec<String>>, ZstdDict<Users>>;
+/// # }
+/// ```
+#[cfg(feature = "zstd")]
+pub trait ZstdDictionary {
+    /// Returns the dictionary bytes.
+    fn dictionary() -> &'static [u8];
+}
+
+/// The [Zstandard](https://facebook.github.io/zstd) compression algorithm
+/// with the trained dictionary `D` and the given compression `LEVEL`.
+///
+/// The dictionary is loaded once per thread in a compression context that is reused.
+#[cfg(feature = "zstd")]
+pub struct ZstdDict<D, const LEVEL: i32 = { zstd::DEFAULT_COMPRESSION_LEVEL }>(PhantomData<D>);
+
+#[cfg(feature = "zstd")]
+thread_local! {
+    /// The compressors of the [`ZstdDict`] algorithms, by dictionary and level.
+    static ZSTD_COMPRESSORS: RefCell<HashMap<(TypeId, i32), zstd::bulk::Compressor<'static>>> =
+        RefCell::new(HashMap::new());
+
+    /// The decompressors of the [`ZstdDict`] algorithms, by dictionary.
+    static ZSTD_DECOMPRESSORS: RefCell<HashMap<TypeId, zstd::bulk::Decompressor<'static>>> =
+        RefCell::new(HashMap::new());
+}
+
+#[cfg(feature = "zstd")]
+impl<D: ZstdDictionary + 'static, const LEVEL: i32> Compression for ZstdDict<D, LEVEL> {
+    const ID: u8 = 3;
+
+    fn compress(bytes: &[u8]) -> Result<Vec<u8>, BoxedError> {
+        ZSTD_COMPRESSORS.with_borrow_mut(|compressors| {
+            let compressor = match compressors.entry((TypeId::of::<D>(), LEVEL)) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(zstd::bulk::Compressor::with_dictionary(LEVEL, D::dictionary())?)
+                }
+            };
+            compressor.compress(bytes).map_err(Into::into)
+        })
+    }
+
+    fn decompress(bytes: &[u8], max_size: usize) -> Result<Vec<u8>, BoxedError> {
+        let size = zstd_content_size(bytes, max_size)?;
+        ZSTD_DECOMPRESSORS.with_borrow_mut(|decompressors| {
+            let decompressor = match decompressors.entry(TypeId::of::<D>()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(zstd::bulk::Decompressor::with_dictionary(D::dictionary())?)
+                }
+            };
+            decompressor.decompress(bytes, size).map_err(Into::into)
+        })
+    }
+}
+
+/// An error that occurred while decoding a [`Compressed`] value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompressionError {
+    /// The bytes are empty and don't start with the header byte.
+    MissingHeader,
+    /// The bytes were written by an unsupported version of the format.
+    UnsupportedVersion(u8),
+    /// The bytes were compressed with another algorithm.
+    UnexpectedAlgorithm(u8),
+    /// The compressed bytes are not a valid frame.
+    InvalidFrame,
+    /// The decompressed value would be larger than the maximum size, it is the announced size.
+    TooLarge(usize),
+}
+
+impl fmt::Display for CompressionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompressionError::MissingHeader => f.write_str("the header byte is missing"),
+            CompressionError::UnsupportedVersion(version) => {
+                write!(f, "unsupported compression format version {version}")
+            }
+            CompressionError::UnexpectedAlgorithm(id) => {
+                write!(f, "the value was compressed with the unexpected algorithm {id}")
+            }
+            CompressionError::InvalidFrame => f.write_str("the compressed frame is invalid"),
+            CompressionError::TooLarge(size) => {
+                write!(f, "the decompressed value of {size} bytes exceeds the maximum size")
+            }
+        }
+    }
+}
+
+impl error::Error for CompressionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes into an owned vector, as required by the `Compressed` codec.
+    enum OwnedBytes {}
+
+    impl<'a> BytesEncode<'a> for OwnedBytes {
+        type EItem = [u8];
+
+        fn bytes_encode(item: &'a Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+            Ok(Cow::Borrowed(item))
+        }
+    }
+
+    impl BytesDecode<'_> for OwnedBytes {
+        type DItem = Vec<u8>;
+
+        fn bytes_decode(bytes: &[u8]) -> Result<Self::DItem, BoxedError> {
+            Ok(bytes.to_vec())
+        }
+    }
+
+    /// "Compresses" the bytes by dropping the last one, which must be a zero.
+    enum Truncate {}
+
+    impl Compression for Truncate {
+        const ID: u8 = 15;
+
+        fn compress(bytes: &[u8]) -> Result<Vec<u8>, BoxedError> {
+            Ok(bytes[..bytes.len() - 1].to_vec())
+        }
+
+        fn decompress(bytes: &[u8], max_size: usize) -> Result<Vec<u8>, BoxedError> {
+            check_size(bytes.len() + 1, max_size)?;
+            let mut bytes = bytes.to_vec();
+            bytes.push(0);
+            Ok(bytes)
+        }
+    }
+
+    type Codec = Compressed<OwnedBytes, Truncate, 4>;
+
+    #[test]
+    fn threshold_and_header() {
+        let bytes = Codec::bytes_encode(&[1, 0]).unwrap();
+        assert_eq!(bytes[..], [VERSION << 4, 1, 0]);
+        assert_eq!(Codec::bytes_decode(&bytes).unwrap(), [1, 0]);
+
+        let bytes = Codec::bytes_encode(&[1, 2, 3, 0]).unwrap();
+        assert_eq!(bytes[..], [VERSION << 4 | 15, 1, 2, 3]);
+        assert_eq!(Codec::bytes_decode(&bytes).unwrap(), [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn invalid_headers() {
+        for bytes in [&[][..], &[0x20, 1], &[0x1E, 1]] {
+            assert!(Codec::bytes_decode(bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn max_decompressed_size() {
+        type Capped = Compressed<OwnedBytes, Truncate, 4, 6>;
+
+        let bytes = Capped::bytes_encode(&[1, 2, 3, 4, 5, 6, 0]).unwrap();
+        assert_eq!(bytes[0], VERSION << 4);
+        assert_eq!(Capped::bytes_decode(&bytes).unwrap(), [1, 2, 3, 4, 5, 6, 0]);
+
+        let bytes = Codec::bytes_encode(&[1, 2, 3, 4, 5, 6, 0]).unwrap();
+        let error = Capped::bytes_decode(&bytes).unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(CompressionError::TooLarge(7))));
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn lz4_round_trip() {
+        type Codec = Compressed<OwnedBytes, Lz4>;
+        let values: Vec<u8> = (0..1000).map(|i| (i % 10) as u8).collect();
+        let bytes = Codec::bytes_encode(&values).unwrap();
+        assert!(bytes.len() < values.len());
+        assert_eq!(Codec::bytes_decode(&bytes).unwrap(), values);
+        assert!(Compressed::<OwnedBytes, Truncate>::bytes_decode(&bytes).is_err());
+
+        let error = Compressed::<OwnedBytes, Lz4, 0, 999>::bytes_decode(&bytes).unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(CompressionError::TooLarge(1000))));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_round_trip() {
+        type Codec = Compressed<OwnedBytes, Zstd<19>>;
+        let values: Vec<u8> = (0..1000).map(|i| (i % 10) as u8).collect();
+        let bytes = Codec::bytes_encode(&values).unwrap();
+        assert!(bytes.len() < values.len());
+        assert_eq!(Codec::bytes_decode(&bytes).unwrap(), values);
+        assert!(Compressed::<OwnedBytes, Truncate>::bytes_decode(&bytes).is_err());
+
+        let error = Compressed::<OwnedBytes, Zstd<19>, 0, 999>::bytes_decode(&bytes).unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(CompressionError::TooLarge(1000))));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_dictionary_round_trip() {
+        enum Dictionary {}
+
+        impl ZstdDictionary for Dictionary {
+            fn dictionary() -> &'static [u8] {
+                b"a raw content dictionary about heed, lmdb and zstd"
+            }
+        }
+
+        type Codec = Compressed<OwnedBytes, ZstdDict<Dictionary>, 0>;
+        for count in 3..6 {
+            // The cached contexts must not keep anything from the previous values.
+            let value = b"heed, lmdb and zstd".repeat(count);
+            let bytes = Codec::bytes_encode(&value).unwrap();
+            assert_eq!(bytes[0], VERSION << 4 | 3);
+            assert_eq!(Codec::bytes_decode(&bytes).unwrap(), value);
+            assert!(Compressed::<OwnedBytes, Zstd>::bytes_decode(&bytes).is_err());
+        }
+    }
+}
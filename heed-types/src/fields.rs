@@ -0,0 +1,326 @@
+use std::borrow::Cow;
+use std::{error, fmt};
+
+use heed_traits::BoxedError;
+
+use crate::tuple::{self, TupleError};
+
+/// The size of the length prefix written before a variable-length field.
+///
+/// The length-prefixed fields are more compact when they contain many `0x00` bytes,
+/// but they sort by length first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LengthPrefix {
+    /// The length is written as a `u8`.
+    U8,
+    /// The length is written as a `u16`.
+    U16,
+    /// The length is written as a `u32`.
+    U32,
+    /// The length is written as a `u64`.
+    U64,
+}
+
+/// Describes how a field must be encoded by a [`FieldCodec`].
+///
+/// It is generated by the `HeedCodec` derive macro from the field attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldOptions {
+    /// Encode the integers in little-endian instead of the order-preserving big-endian.
+    pub little_endian: bool,
+    /// The length prefix of the variable-length fields.
+    ///
+    /// If not specified, the field is escaped and terminated like the elements of a
+    /// [`Tuple`](crate::Tuple), unless it is the last one, in which case it takes
+    /// the remaining bytes.
+    pub length_prefix: Option<LengthPrefix>,
+    /// Whether this field is the last one of the encoded bytes.
+    pub last: bool,
+}
+
+impl FieldOptions {
+    /// The options of a top-level item, the only field of the encoded bytes.
+    pub const ROOT: FieldOptions =
+        FieldOptions { little_endian: false, length_prefix: None, last: true };
+}
+
+/// A type that can be encoded as a field of a type deriving `HeedCodec`.
+///
+/// The encodings are order-preserving by default: the integers are encoded in big-endian
+/// with the sign bit flipped for the signed ones, and the fields are written one after
+/// the other, so that the bytes sort in field order under the lexicographic comparator.
+/// The variable-length fields are escaped and terminated, unless they have a [`LengthPrefix`].
+pub trait FieldCodec: Sized {
+    /// Appends the encoded field to the output.
+    fn encode_field(&self, options: FieldOptions, output: &mut Vec<u8>) -> Result<(), BoxedError>;
+
+    /// Decodes the field from the input and advances it past the field bytes.
+    fn decode_field(input: &mut &[u8], options: FieldOptions) -> Result<Self, BoxedError>;
+}
+
+/// Extracts the given number of bytes from the input.
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], FieldError> {
+    if input.len() < len {
+        return Err(FieldError::UnexpectedEnd);
+    }
+    let (bytes, remaining) = input.split_at(len);
+    *input = remaining;
+    Ok(bytes)
+}
+
+macro_rules! impl_unsigned {
+    ($($native:ident),*) => {$(
+        impl FieldCodec for $native {
+            fn encode_field(&self, options: FieldOptions, output: &mut Vec<u8>) -> Result<(), BoxedError> {
+                if options.little_endian {
+                    output.extend_from_slice(&self.to_le_bytes());
+                } else {
+                    output.extend_from_slice(&self.to_be_bytes());
+                }
+                Ok(())
+            }
+
+            fn decode_field(input: &mut &[u8], options: FieldOptions) -> Result<Self, BoxedError> {
+                let bytes = take(input, std::mem::size_of::<$native>())?.try_into().unwrap();
+                if options.little_endian {
+                    Ok($native::from_le_bytes(bytes))
+                } else {
+                    Ok($native::from_be_bytes(bytes))
+                }
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($native:ident => $unsigned:ident),*) => {$(
+        impl FieldCodec for $native {
+            fn encode_field(&self, options: FieldOptions, output: &mut Vec<u8>) -> Result<(), BoxedError> {
+                if options.little_endian {
+                    output.extend_from_slice(&self.to_le_bytes());
+                } else {
+                    // Flipping the sign bit makes the negative numbers sort before the positive ones.
+                    let flipped = (*self as $unsigned) ^ (1 << ($unsigned::BITS - 1));
+                    output.extend_from_slice(&flipped.to_be_bytes());
+                }
+                Ok(())
+            }
+
+            fn decode_field(input: &mut &[u8], options: FieldOptions) -> Result<Self, BoxedError> {
+                let bytes = take(input, std::mem::size_of::<$native>())?.try_into().unwrap();
+                if options.little_endian {
+                    Ok($native::from_le_bytes(bytes))
+                } else {
+                    let flipped = $unsigned::from_be_bytes(bytes);
+                    Ok((flipped ^ (1 << ($unsigned::BITS - 1))) as $native)
+                }
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128);
+impl_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+impl FieldCodec for bool {
+    fn encode_field(&self, _options: FieldOptions, output: &mut Vec<u8>) -> Result<(), BoxedError> {
+        output.push(*self as u8);
+        Ok(())
+    }
+
+    fn decode_field(input: &mut &[u8], _options: FieldOptions) -> Result<Self, BoxedError> {
+        match take(input, 1)?[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            byte => Err(FieldError::InvalidBool(byte).into()),
+        }
+    }
+}
+
+impl<const N: usize> FieldCodec for [u8; N] {
+    fn encode_field(&self, _options: FieldOptions, output: &mut Vec<u8>) -> Result<(), BoxedError> {
+        output.extend_from_slice(self);
+        Ok(())
+    }
+
+    fn decode_field(input: &mut &[u8], _options: FieldOptions) -> Result<Self, BoxedError> {
+        Ok(take(input, N)?.try_into().unwrap())
+    }
+}
+
+/// Appends the variable-length bytes, prefixed by their length or escaped and terminated.
+fn encode_variable(
+    bytes: &[u8],
+    options: FieldOptions,
+    output: &mut Vec<u8>,
+) -> Result<(), BoxedError> {
+    let len = bytes.len();
+    match options.length_prefix {
+        None => {
+            tuple::encode_variable(bytes, options.last, output);
+            return Ok(());
+        }
+        Some(LengthPrefix::U8) => {
+            let len = u8::try_from(len).map_err(|_| FieldError::LengthOverflow)?;
+            len.encode_field(options, output)?;
+        }
+        Some(LengthPrefix::U16) => {
+            let len = u16::try_from(len).map_err(|_| FieldError::LengthOverflow)?;
+            len.encode_field(options, output)?;
+        }
+        Some(LengthPrefix::U32) => {
+            let len = u32::try_from(len).map_err(|_| FieldError::LengthOverflow)?;
+            len.encode_field(options, output)?;
+        }
+        Some(LengthPrefix::U64) => (len as u64).encode_field(options, output)?,
+    }
+    output.extend_from_slice(bytes);
+    Ok(())
+}
+
+/// Extracts the variable-length bytes, they are only copied if they contain escaped bytes.
+fn decode_variable<'a>(
+    input: &mut &'a [u8],
+    options: FieldOptions,
+) -> Result<Cow<'a, [u8]>, BoxedError> {
+    let len = match options.length_prefix {
+        None => {
+            return tuple::decode_variable(input, options.last).map_err(|e| match e {
+                TupleError::InvalidEscape(byte) => FieldError::InvalidEscape(byte).into(),
+                _ => FieldError::UnexpectedEnd.into(),
+            })
+        }
+        Some(LengthPrefix::U8) => u8::decode_field(input, options)? as usize,
+        Some(LengthPrefix::U16) => u16::decode_field(input, options)? as usize,
+        Some(LengthPrefix::U32) => u32::decode_field(input, options)? as usize,
+        Some(LengthPrefix::U64) => {
+            let len = u64::decode_field(input, options)?;
+            usize::try_from(len).map_err(|_| FieldError::LengthOverflow)?
+        }
+    };
+    Ok(Cow::Borrowed(take(input, len)?))
+}
+
+impl FieldCodec for Vec<u8> {
+    fn encode_field(&self, options: FieldOptions, output: &mut Vec<u8>) -> Result<(), BoxedError> {
+        encode_variable(self, options, output)
+    }
+
+    fn decode_field(input: &mut &[u8], options: FieldOptions) -> Result<Self, BoxedError> {
+        decode_variable(input, options).map(Cow::into_owned)
+    }
+}
+
+impl FieldCodec for String {
+    fn encode_field(&self, options: FieldOptions, output: &mut Vec<u8>) -> Result<(), BoxedError> {
+        encode_variable(self.as_bytes(), options, output)
+    }
+
+    fn decode_field(input: &mut &[u8], options: FieldOptions) -> Result<Self, BoxedError> {
+        let bytes = decode_variable(input, options)?;
+        Ok(String::from_utf8(bytes.into_owned())?)
+    }
+}
+
+/// An error that occurred while encoding or decoding a [`FieldCodec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldError {
+    /// The bytes ended before the end of the field.
+    UnexpectedEnd,
+    /// There are remaining bytes after the last field.
+    TrailingBytes,
+    /// The length of a field doesn't fit in its length prefix.
+    LengthOverflow,
+    /// A boolean is neither encoded as a `0` nor a `1`.
+    InvalidBool(u8),
+    /// The escape byte of a variable-length field is followed by an unexpected byte.
+    InvalidEscape(u8),
+    /// The tag byte doesn't correspond to any enum variant.
+    UnknownTag(u8),
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldError::UnexpectedEnd => f.write_str("the bytes ended before the end of the field"),
+            FieldError::TrailingBytes => {
+                f.write_str("there are remaining bytes after the last field")
+            }
+            FieldError::LengthOverflow => {
+                f.write_str("the length of the field doesn't fit in its length prefix")
+            }
+            FieldError::InvalidBool(byte) => write!(f, "invalid boolean byte {byte}"),
+            FieldError::InvalidEscape(byte) => {
+                write!(f, "the escape byte is followed by the unexpected {byte:#04x} byte")
+            }
+            FieldError::UnknownTag(tag) => write!(f, "unknown enum tag {tag}"),
+        }
+    }
+}
+
+impl error::Error for FieldError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode<T: FieldCodec>(item: &T, options: FieldOptions) -> Vec<u8> {
+        let mut output = Vec::new();
+        item.encode_field(options, &mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn signed_integers_preserve_order() {
+        let values = [i32::MIN, -300, -1, 0, 1, 300, i32::MAX];
+        let encoded: Vec<_> = values.iter().map(|v| encode(v, FieldOptions::ROOT)).collect();
+        assert!(encoded.windows(2).all(|w| w[0] < w[1]));
+
+        for (value, bytes) in values.iter().zip(&encoded) {
+            let mut input = &bytes[..];
+            assert_eq!(i32::decode_field(&mut input, FieldOptions::ROOT).unwrap(), *value);
+            assert!(input.is_empty());
+        }
+    }
+
+    #[test]
+    fn escaped_fields_preserve_order() {
+        let not_last = FieldOptions { last: false, ..FieldOptions::ROOT };
+        let bytes = encode(&String::from("a\0b"), not_last);
+        assert_eq!(bytes, b"a\0\xffb\0\x01");
+
+        let values = ["", "\0", "\0\0", "a", "a\0", "aa", "b"].map(String::from);
+        let encoded: Vec<_> = values.iter().map(|v| encode(v, not_last)).collect();
+        assert!(encoded.windows(2).all(|w| w[0] < w[1]));
+
+        for (value, bytes) in values.iter().zip(&encoded) {
+            let mut input = &bytes[..];
+            assert_eq!(&String::decode_field(&mut input, not_last).unwrap(), value);
+            assert!(input.is_empty());
+        }
+
+        let mut input = &b"a\0\x02"[..];
+        let error = Vec::<u8>::decode_field(&mut input, not_last).unwrap_err();
+        assert_eq!(error.downcast_ref(), Some(&FieldError::InvalidEscape(2)));
+    }
+
+    #[test]
+    fn variable_length_prefixes() {
+        let not_last = FieldOptions { last: false, ..FieldOptions::ROOT };
+        let u8_prefix = FieldOptions { length_prefix: Some(LengthPrefix::U8), ..not_last };
+        let bytes = encode(&String::from("hello"), u8_prefix);
+        assert_eq!(bytes, b"\x05hello");
+
+        let u8_prefix =
+            FieldOptions { length_prefix: Some(LengthPrefix::U8), ..FieldOptions::ROOT };
+        let bytes = encode(&vec![1u8, 2, 3], u8_prefix);
+        assert_eq!(bytes, [3, 1, 2, 3]);
+
+        let mut input = &bytes[..];
+        assert_eq!(Vec::<u8>::decode_field(&mut input, u8_prefix).unwrap(), [1, 2, 3]);
+
+        let too_long = vec![0u8; 256];
+        let mut output = Vec::new();
+        assert!(too_long.encode_field(u8_prefix, &mut output).is_err());
+    }
+}
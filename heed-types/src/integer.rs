@@ -1,9 +1,10 @@
 use std::borrow::Cow;
+use std::io;
 use std::marker::PhantomData;
 use std::mem::size_of;
 
-use byteorder::{ByteOrder, ReadBytesExt};
-use heed_traits::{BoxedError, BytesDecode, BytesEncode};
+use byteorder::{ByteOrder, ReadBytesExt, WriteBytesExt};
+use heed_traits::{BoxedError, BytesDecode, BytesEncode, BytesEncodeInto};
 
 /// Encodable version of [`u8`].
 pub struct U8;
@@ -16,6 +17,19 @@ impl BytesEncode<'_> for U8 {
     }
 }
 
+impl BytesEncodeInto<'_> for U8 {
+    fn size_hint(_item: &Self::EItem) -> Option<usize> {
+        Some(1)
+    }
+
+    fn bytes_encode_into<W: io::Write>(
+        item: &Self::EItem,
+        writer: &mut W,
+    ) -> Result<(), BoxedError> {
+        writer.write_u8(*item).map_err(Into::into)
+    }
+}
+
 impl BytesDecode<'_> for U8 {
     type DItem = u8;
 
@@ -35,6 +49,19 @@ impl BytesEncode<'_> for I8 {
     }
 }
 
+impl BytesEncodeInto<'_> for I8 {
+    fn size_hint(_item: &Self::EItem) -> Option<usize> {
+        Some(1)
+    }
+
+    fn bytes_encode_into<W: io::Write>(
+        item: &Self::EItem,
+        writer: &mut W,
+    ) -> Result<(), BoxedError> {
+        writer.write_i8(*item).map_err(Into::into)
+    }
+}
+
 impl BytesDecode<'_> for I8 {
     type DItem = i8;
 
@@ -61,6 +88,19 @@ macro_rules! define_type {
             }
         }
 
+        impl<O: ByteOrder> BytesEncodeInto<'_> for $name<O> {
+            fn size_hint(_item: &Self::EItem) -> Option<usize> {
+                Some(size_of::<Self::EItem>())
+            }
+
+            fn bytes_encode_into<W: io::Write>(
+                item: &Self::EItem,
+                writer: &mut W,
+            ) -> Result<(), BoxedError> {
+                writer.$write_method::<O>(*item).map_err(Into::into)
+            }
+        }
+
         impl<O: ByteOrder> BytesDecode<'_> for $name<O> {
             type DItem = $native;
 
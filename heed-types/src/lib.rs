@@ -10,12 +10,23 @@
 #![warn(missing_docs)]
 
 mod bytes;
+mod compressed;
 mod decode_ignore;
+mod fields;
 mod integer;
 mod lazy_decode;
+mod merge;
+mod ordered;
 mod str;
+mod tuple;
 mod unit;
 
+#[cfg(feature = "bytemuck")]
+mod pod;
+
+#[cfg(feature = "roaring")]
+mod roaring;
+
 #[cfg(feature = "serde-bincode")]
 mod serde_bincode;
 
@@ -26,9 +37,26 @@ mod serde_json;
 mod serde_rmp;
 
 pub use self::bytes::Bytes;
+#[cfg(feature = "lz4")]
+pub use self::compressed::Lz4;
+pub use self::compressed::{
+    Compressed, Compression, CompressionError, DEFAULT_COMPRESSION_THRESHOLD,
+    DEFAULT_MAX_DECOMPRESSED_SIZE,
+};
+#[cfg(feature = "zstd")]
+pub use self::compressed::{Zstd, ZstdDict, ZstdDictionary};
 pub use self::decode_ignore::DecodeIgnore;
+pub use self::fields::{FieldCodec, FieldError, FieldOptions, LengthPrefix};
 pub use self::integer::*;
 pub use self::lazy_decode::{Lazy, LazyDecode};
+pub use self::merge::{IntegerAdd, MergeError};
+pub use self::ordered::{
+    OrderedF32, OrderedF64, OrderedI128, OrderedI16, OrderedI32, OrderedI64, OrderedI8,
+};
+#[cfg(feature = "bytemuck")]
+pub use self::pod::{Pod, PodSlice};
+#[cfg(feature = "roaring")]
+pub use self::roaring::{RoaringBitmapCodec, RoaringBitmapUnion};
 #[cfg(feature = "serde-bincode")]
 pub use self::serde_bincode::SerdeBincode;
 #[cfg(feature = "serde-json")]
@@ -36,4 +64,5 @@ pub use self::serde_json::SerdeJson;
 #[cfg(feature = "serde-rmp")]
 pub use self::serde_rmp::SerdeRmp;
 pub use self::str::Str;
+pub use self::tuple::{Tuple, TupleElement, TupleError, TuplePrefix};
 pub use self::unit::Unit;
@@ -0,0 +1,125 @@
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::{error, fmt};
+
+use byteorder::ByteOrder;
+use heed_traits::{BoxedError, MergeFunction};
+
+use crate::{I128, I16, I32, I64, U128, U16, U32, U64};
+
+/// A [`MergeFunction`] that adds integers together, e.g. `IntegerAdd<U64<BigEndian>>`.
+///
+/// A missing value counts as zero and an overflow is reported as an error.
+pub struct IntegerAdd<C>(PhantomData<C>);
+
+macro_rules! impl_integer_add {
+    ($name:ident, $native:ident, $read_method:ident, $write_method:ident) => {
+        impl<O: ByteOrder> MergeFunction for IntegerAdd<$name<O>> {
+            fn merge(
+                _key: &[u8],
+                existing: Option<&[u8]>,
+                operands: &[&[u8]],
+                output: &mut Vec<u8>,
+            ) -> Result<(), BoxedError> {
+                let read = |bytes: &[u8]| match bytes.len() {
+                    size if size == size_of::<$native>() => Ok(O::$read_method(bytes)),
+                    found => Err(MergeError::InvalidSize { expected: size_of::<$native>(), found }),
+                };
+
+                let mut sum = existing.map_or(Ok(0), read)?;
+                for operand in operands {
+                    sum = sum.checked_add(read(operand)?).ok_or(MergeError::Overflow)?;
+                }
+
+                output.resize(size_of::<$native>(), 0);
+                O::$write_method(output, sum);
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_integer_add!(U16, u16, read_u16, write_u16);
+impl_integer_add!(U32, u32, read_u32, write_u32);
+impl_integer_add!(U64, u64, read_u64, write_u64);
+impl_integer_add!(U128, u128, read_u128, write_u128);
+impl_integer_add!(I16, i16, read_i16, write_i16);
+impl_integer_add!(I32, i32, read_i32, write_i32);
+impl_integer_add!(I64, i64, read_i64, write_i64);
+impl_integer_add!(I128, i128, read_i128, write_i128);
+
+/// An error that occurred while merging values with one of the ready-made merge functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergeError {
+    /// A value doesn't have the size of the merged type.
+    InvalidSize {
+        /// The size of the merged type.
+        expected: usize,
+        /// The size of the value.
+        found: usize,
+    },
+    /// The merged value doesn't fit in its type.
+    Overflow,
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MergeError::InvalidSize { expected, found } => {
+                write!(f, "expected a value of {expected} bytes but found {found} bytes")
+            }
+            MergeError::Overflow => f.write_str("the merged value overflowed"),
+        }
+    }
+}
+
+impl error::Error for MergeError {}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{BigEndian, LittleEndian};
+
+    use super::*;
+
+    fn merge<M: MergeFunction>(existing: Option<&[u8]>, operands: &[&[u8]]) -> Vec<u8> {
+        let mut output = Vec::new();
+        M::merge(b"key", existing, operands, &mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn integer_add() {
+        let sum = merge::<IntegerAdd<U64<BigEndian>>>(None, &[&1u64.to_be_bytes()]);
+        assert_eq!(sum, 1u64.to_be_bytes());
+
+        let existing = 40u64.to_be_bytes();
+        let sum = merge::<IntegerAdd<U64<BigEndian>>>(
+            Some(&existing),
+            &[&1u64.to_be_bytes(), &1u64.to_be_bytes()],
+        );
+        assert_eq!(sum, 42u64.to_be_bytes());
+
+        let existing = 10i32.to_le_bytes();
+        let sum =
+            merge::<IntegerAdd<I32<LittleEndian>>>(Some(&existing), &[&(-15i32).to_le_bytes()]);
+        assert_eq!(sum, (-5i32).to_le_bytes());
+    }
+
+    #[test]
+    fn integer_add_errors() {
+        let mut output = Vec::new();
+        let existing = u16::MAX.to_be_bytes();
+        let error = IntegerAdd::<U16<BigEndian>>::merge(
+            b"key",
+            Some(&existing),
+            &[&1u16.to_be_bytes()],
+            &mut output,
+        )
+        .unwrap_err();
+        assert_eq!(error.downcast_ref(), Some(&MergeError::Overflow));
+
+        let error =
+            IntegerAdd::<U32<BigEndian>>::merge(b"key", None, &[&[0, 1]], &mut output).unwrap_err();
+        assert_eq!(error.downcast_ref(), Some(&MergeError::InvalidSize { expected: 4, found: 2 }));
+    }
+}
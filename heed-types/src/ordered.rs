@@ -0,0 +1,201 @@
+use std::borrow::Cow;
+use std::io;
+use std::mem::size_of;
+
+use heed_traits::{BoxedError, BytesDecode, BytesEncode, BytesEncodeInto};
+
+macro_rules! define_signed {
+    ($name:ident, $native:ident, $unsigned:ident) => {
+        #[doc = "Order-preserving encodable version of [`"]
+        #[doc = stringify!($native)]
+        #[doc = "`]."]
+        ///
+        /// The integer is written in big-endian with its sign bit flipped,
+        /// this way the negative numbers sort before the positive ones.
+        pub struct $name;
+
+        impl BytesEncode<'_> for $name {
+            type EItem = $native;
+
+            fn bytes_encode(item: &Self::EItem) -> Result<Cow<'_, [u8]>, BoxedError> {
+                let flipped = (*item as $unsigned) ^ (1 << ($unsigned::BITS - 1));
+                Ok(Cow::from(flipped.to_be_bytes().to_vec()))
+            }
+        }
+
+        impl BytesEncodeInto<'_> for $name {
+            fn size_hint(_item: &Self::EItem) -> Option<usize> {
+                Some(size_of::<$native>())
+            }
+
+            fn bytes_encode_into<W: io::Write>(
+                item: &Self::EItem,
+                writer: &mut W,
+            ) -> Result<(), BoxedError> {
+                let flipped = (*item as $unsigned) ^ (1 << ($unsigned::BITS - 1));
+                writer.write_all(&flipped.to_be_bytes()).map_err(Into::into)
+            }
+        }
+
+        impl BytesDecode<'_> for $name {
+            type DItem = $native;
+
+            fn bytes_decode(bytes: &'_ [u8]) -> Result<Self::DItem, BoxedError> {
+                let flipped = $unsigned::from_be_bytes(bytes.try_into()?);
+                Ok((flipped ^ (1 << ($unsigned::BITS - 1))) as $native)
+            }
+        }
+    };
+}
+
+define_signed!(OrderedI8, i8, u8);
+define_signed!(OrderedI16, i16, u16);
+define_signed!(OrderedI32, i32, u32);
+define_signed!(OrderedI64, i64, u64);
+define_signed!(OrderedI128, i128, u128);
+
+macro_rules! define_float {
+    ($name:ident, $native:ident, $bits:ident) => {
+        #[doc = "Order-preserving encodable version of [`"]
+        #[doc = stringify!($native)]
+        #[doc = "`]."]
+        ///
+        /// The bytes follow the IEEE 754 total order, the one of
+        #[doc = concat!("[`", stringify!($native), "::total_cmp`]:")]
+        /// negative NaNs, negative infinity, negative numbers, `-0.0`, `+0.0`,
+        /// positive numbers, positive infinity and positive NaNs. The values,
+        /// including the NaN payloads, round-trip exactly.
+        pub struct $name;
+
+        impl $name {
+            fn ordered_bits(item: $native) -> $bits {
+                let bits = item.to_bits();
+                // Flipping all the bits of the negative numbers reverses their order
+                // and flipping the sign bit of the others moves them after the negatives.
+                if bits >> ($bits::BITS - 1) == 1 {
+                    !bits
+                } else {
+                    bits ^ (1 << ($bits::BITS - 1))
+                }
+            }
+        }
+
+        impl BytesEncode<'_> for $name {
+            type EItem = $native;
+
+            fn bytes_encode(item: &Self::EItem) -> Result<Cow<'_, [u8]>, BoxedError> {
+                Ok(Cow::from($name::ordered_bits(*item).to_be_bytes().to_vec()))
+            }
+        }
+
+        impl BytesEncodeInto<'_> for $name {
+            fn size_hint(_item: &Self::EItem) -> Option<usize> {
+                Some(size_of::<$native>())
+            }
+
+            fn bytes_encode_into<W: io::Write>(
+                item: &Self::EItem,
+                writer: &mut W,
+            ) -> Result<(), BoxedError> {
+                writer.write_all(&$name::ordered_bits(*item).to_be_bytes()).map_err(Into::into)
+            }
+        }
+
+        impl BytesDecode<'_> for $name {
+            type DItem = $native;
+
+            fn bytes_decode(bytes: &'_ [u8]) -> Result<Self::DItem, BoxedError> {
+                let ordered = $bits::from_be_bytes(bytes.try_into()?);
+                let bits = if ordered >> ($bits::BITS - 1) == 1 {
+                    ordered ^ (1 << ($bits::BITS - 1))
+                } else {
+                    !ordered
+                };
+                Ok($native::from_bits(bits))
+            }
+        }
+    };
+}
+
+define_float!(OrderedF32, f32, u32);
+define_float!(OrderedF64, f64, u64);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn encode<'a, C: BytesEncode<'a>>(item: &'a C::EItem) -> Vec<u8> {
+        C::bytes_encode(item).unwrap().into_owned()
+    }
+
+    macro_rules! signed_tests {
+        ($($test:ident: $codec:ident, $native:ident);*) => {$(
+            proptest! {
+                #[test]
+                fn $test(a: $native, b: $native) {
+                    let (ea, eb) = (encode::<$codec>(&a), encode::<$codec>(&b));
+                    prop_assert_eq!(ea.cmp(&eb), a.cmp(&b));
+                    prop_assert_eq!($codec::bytes_decode(&ea).unwrap(), a);
+                }
+            }
+        )*};
+    }
+
+    signed_tests! {
+        i8_order: OrderedI8, i8;
+        i16_order: OrderedI16, i16;
+        i32_order: OrderedI32, i32;
+        i64_order: OrderedI64, i64;
+        i128_order: OrderedI128, i128
+    }
+
+    macro_rules! float_tests {
+        ($($test:ident: $codec:ident, $native:ident, $bits:ident);*) => {$(
+            proptest! {
+                // Generating the bits covers every float, including all the NaN payloads.
+                #[test]
+                fn $test(
+                    a in any::<$bits>().prop_map($native::from_bits),
+                    b in any::<$bits>().prop_map($native::from_bits),
+                ) {
+                    let (ea, eb) = (encode::<$codec>(&a), encode::<$codec>(&b));
+                    prop_assert_eq!(ea.cmp(&eb), a.total_cmp(&b));
+                    prop_assert_eq!($codec::bytes_decode(&ea).unwrap().to_bits(), a.to_bits());
+                }
+            }
+        )*};
+    }
+
+    float_tests! {
+        f32_order: OrderedF32, f32, u32;
+        f64_order: OrderedF64, f64, u64
+    }
+
+    #[test]
+    fn float_special_values() {
+        let values = [
+            f64::NEG_INFINITY,
+            f64::MIN,
+            -1.0,
+            -f64::MIN_POSITIVE,
+            -0.0,
+            0.0,
+            f64::MIN_POSITIVE,
+            1.0,
+            f64::MAX,
+            f64::INFINITY,
+            f64::NAN,
+        ];
+        let encoded: Vec<_> = values.iter().map(encode::<OrderedF64>).collect();
+        assert!(encoded.windows(2).all(|w| w[0] < w[1]));
+        assert!(encode::<OrderedF64>(&-f64::NAN) < encoded[0]);
+    }
+
+    #[test]
+    fn wrong_length() {
+        assert!(OrderedI32::bytes_decode(&[0, 0, 0]).is_err());
+        assert!(OrderedF32::bytes_decode(&[0, 0, 0, 0, 0]).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use std::borrow::Cow;
+use std::io;
+use std::marker::PhantomData;
+use std::mem::{size_of, size_of_val};
+
+use bytemuck::{Pod as BytemuckPod, PodCastError};
+use heed_traits::{BoxedError, BytesDecode, BytesEncode, BytesEncodeInto};
+
+/// Describes a type that is [`bytemuck::Pod`] and is read directly from the memory map.
+///
+/// LMDB doesn't guarantee the alignment of the values it stores, the type is borrowed
+/// when the bytes are aligned enough and copied into a [`Cow::Owned`] when they are not.
+/// Types with an alignment of one, like byte arrays, are always borrowed.
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// use heed_traits::{BytesDecode, BytesEncode, BytesEncodeInto};
+/// use heed_types::Pod;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+/// #[repr(C)]
+/// struct Point {
+///     x: [u8; 4],
+///     y: [u8; 4],
+/// }
+///
+/// let point = Point { x: 1u32.to_be_bytes(), y: 2u32.to_be_bytes() };
+/// let bytes = Pod::<Point>::bytes_encode(&point)?;
+/// let decoded = Pod::<Point>::bytes_decode(&bytes)?;
+/// assert!(matches!(decoded, Cow::Borrowed(p) if *p == point));
+/// # Ok::<(), heed_traits::BoxedError>(())
+/// ```
+pub struct Pod<T>(PhantomData<T>);
+
+impl<'a, T: BytemuckPod> BytesEncode<'a> for Pod<T> {
+    type EItem = T;
+
+    fn bytes_encode(item: &'a Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+        Ok(Cow::Borrowed(bytemuck::bytes_of(item)))
+    }
+}
+
+impl<'a, T: BytemuckPod> BytesEncodeInto<'a> for Pod<T> {
+    fn size_hint(_item: &Self::EItem) -> Option<usize> {
+        Some(size_of::<T>())
+    }
+
+    fn bytes_encode_into<W: io::Write>(
+        item: &'a Self::EItem,
+        writer: &mut W,
+    ) -> Result<(), BoxedError> {
+        writer.write_all(bytemuck::bytes_of(item)).map_err(Into::into)
+    }
+}
+
+impl<'a, T: BytemuckPod> BytesDecode<'a> for Pod<T> {
+    type DItem = Cow<'a, T>;
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        match bytemuck::try_from_bytes(bytes) {
+            Ok(item) => Ok(Cow::Borrowed(item)),
+            Err(PodCastError::TargetAlignmentGreaterAndInputNotAligned) => {
+                Ok(Cow::Owned(bytemuck::pod_read_unaligned(bytes)))
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+unsafe impl<T> Send for Pod<T> {}
+
+unsafe impl<T> Sync for Pod<T> {}
+
+/// Describes a slice of [`bytemuck::Pod`] types that is read directly from the memory map.
+///
+/// Like [`Pod`], the slice is borrowed when the bytes are aligned enough
+/// and copied into a [`Cow::Owned`] when they are not.
+///
+/// ```
+/// use heed_traits::{BytesDecode, BytesEncode, BytesEncodeInto};
+/// use heed_types::PodSlice;
+///
+/// let bytes = PodSlice::<u32>::bytes_encode(&[1, 2, 3])?;
+/// let decoded = PodSlice::<u32>::bytes_decode(&bytes)?;
+/// assert_eq!(&*decoded, &[1, 2, 3]);
+/// # Ok::<(), heed_traits::BoxedError>(())
+/// ```
+pub struct PodSlice<T>(PhantomData<T>);
+
+impl<'a, T: BytemuckPod> BytesEncode<'a> for PodSlice<T> {
+    type EItem = [T];
+
+    fn bytes_encode(item: &'a Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+        Ok(Cow::Borrowed(bytemuck::cast_slice(item)))
+    }
+}
+
+impl<'a, T: BytemuckPod> BytesEncodeInto<'a> for PodSlice<T> {
+    fn size_hint(item: &Self::EItem) -> Option<usize> {
+        Some(size_of_val(item))
+    }
+
+    fn bytes_encode_into<W: io::Write>(
+        item: &'a Self::EItem,
+        writer: &mut W,
+    ) -> Result<(), BoxedError> {
+        writer.write_all(bytemuck::cast_slice(item)).map_err(Into::into)
+    }
+}
+
+impl<'a, T: BytemuckPod> BytesDecode<'a> for PodSlice<T> {
+    type DItem = Cow<'a, [T]>;
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        match bytemuck::try_cast_slice(bytes) {
+            Ok(items) => Ok(Cow::Borrowed(items)),
+            Err(PodCastError::TargetAlignmentGreaterAndInputNotAligned) => {
+                if size_of::<T>() == 0 || !bytes.len().is_multiple_of(size_of::<T>()) {
+                    Err(PodCastError::OutputSliceWouldHaveSlop.into())
+                } else {
+                    Ok(Cow::Owned(bytemuck::pod_collect_to_vec(bytes)))
+                }
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+unsafe impl<T> Send for PodSlice<T> {}
+
+unsafe impl<T> Sync for PodSlice<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unaligned_bytes_are_copied() {
+        let items: [u64; 3] = [1, u64::MAX, 42];
+        let encoded = PodSlice::<u64>::bytes_encode(&items).unwrap();
+
+        // Shift the bytes by one to make sure they are misaligned.
+        let mut buffer = vec![0u8; encoded.len() + 1];
+        buffer[1..].copy_from_slice(&encoded);
+        let decoded = PodSlice::<u64>::bytes_decode(&buffer[1..]).unwrap();
+        assert!(matches!(decoded, Cow::Owned(_)));
+        assert_eq!(&*decoded, &items);
+
+        let decoded = Pod::<u64>::bytes_decode(&buffer[1..9]).unwrap();
+        assert!(matches!(decoded, Cow::Owned(1)));
+
+        assert!(Pod::<u64>::bytes_decode(&buffer[1..8]).is_err());
+        assert!(PodSlice::<u64>::bytes_decode(&buffer[1..12]).is_err());
+    }
+
+    #[test]
+    fn aligned_bytes_are_borrowed() {
+        let items: [u64; 2] = [7, 8];
+        let decoded = PodSlice::<u64>::bytes_decode(bytemuck::cast_slice(&items)).unwrap();
+        assert!(matches!(decoded, Cow::Borrowed(_)));
+        let decoded = Pod::<u64>::bytes_decode(bytemuck::bytes_of(&items[0])).unwrap();
+        assert!(matches!(decoded, Cow::Borrowed(7)));
+    }
+}
@@ -0,0 +1,86 @@
+use std::borrow::Cow;
+use std::io;
+
+use heed_traits::{BoxedError, BytesDecode, BytesEncode, BytesEncodeInto, MergeFunction};
+use roaring::RoaringBitmap;
+
+/// Describes a [`RoaringBitmap`] stored in its portable serialization format.
+pub enum RoaringBitmapCodec {}
+
+impl BytesEncode<'_> for RoaringBitmapCodec {
+    type EItem = RoaringBitmap;
+
+    fn bytes_encode(item: &Self::EItem) -> Result<Cow<'_, [u8]>, BoxedError> {
+        let mut bytes = Vec::with_capacity(item.serialized_size());
+        item.serialize_into(&mut bytes)?;
+        Ok(Cow::Owned(bytes))
+    }
+}
+
+impl BytesEncodeInto<'_> for RoaringBitmapCodec {
+    fn size_hint(item: &Self::EItem) -> Option<usize> {
+        Some(item.serialized_size())
+    }
+
+    fn bytes_encode_into<W: io::Write>(
+        item: &Self::EItem,
+        writer: &mut W,
+    ) -> Result<(), BoxedError> {
+        item.serialize_into(writer).map_err(Into::into)
+    }
+}
+
+impl BytesDecode<'_> for RoaringBitmapCodec {
+    type DItem = RoaringBitmap;
+
+    fn bytes_decode(bytes: &[u8]) -> Result<Self::DItem, BoxedError> {
+        RoaringBitmap::deserialize_from(bytes).map_err(Into::into)
+    }
+}
+
+/// A [`MergeFunction`] that unions [`RoaringBitmapCodec`] values together.
+///
+/// A missing value counts as an empty bitmap.
+pub enum RoaringBitmapUnion {}
+
+impl MergeFunction for RoaringBitmapUnion {
+    fn merge(
+        _key: &[u8],
+        existing: Option<&[u8]>,
+        operands: &[&[u8]],
+        output: &mut Vec<u8>,
+    ) -> Result<(), BoxedError> {
+        let mut bitmap = match existing {
+            Some(bytes) => RoaringBitmap::deserialize_from(bytes)?,
+            None => RoaringBitmap::new(),
+        };
+        for operand in operands {
+            bitmap |= RoaringBitmap::deserialize_from(*operand)?;
+        }
+
+        output.reserve(bitmap.serialized_size());
+        bitmap.serialize_into(output).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union() {
+        let bitmaps = [[1, 2], [2, 3], [10, 10]].map(RoaringBitmap::from_iter);
+        let [existing, first, second] =
+            bitmaps.each_ref().map(|bitmap| RoaringBitmapCodec::bytes_encode(bitmap).unwrap());
+
+        let mut output = Vec::new();
+        RoaringBitmapUnion::merge(b"key", Some(&existing), &[&first, &second], &mut output)
+            .unwrap();
+        let bitmap = RoaringBitmapCodec::bytes_decode(&output).unwrap();
+        assert_eq!(bitmap, RoaringBitmap::from_iter([1, 2, 3, 10]));
+
+        output.clear();
+        RoaringBitmapUnion::merge(b"key", None, &[&second], &mut output).unwrap();
+        assert_eq!(output, second.as_ref());
+    }
+}
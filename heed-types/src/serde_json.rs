@@ -1,6 +1,7 @@
 use std::borrow::Cow;
+use std::io;
 
-use heed_traits::{BoxedError, BytesDecode, BytesEncode};
+use heed_traits::{BoxedError, BytesDecode, BytesEncode, BytesEncodeInto};
 use serde::{Deserialize, Serialize};
 
 /// Describes a type that is [`Serialize`]/[`Deserialize`] and uses `serde_json` to do so.
@@ -19,6 +20,18 @@ where
     }
 }
 
+impl<'a, T: 'a> BytesEncodeInto<'a> for SerdeJson<T>
+where
+    T: Serialize,
+{
+    fn bytes_encode_into<W: io::Write>(
+        item: &'a Self::EItem,
+        writer: &mut W,
+    ) -> Result<(), BoxedError> {
+        serde_json::to_writer(writer, item).map_err(Into::into)
+    }
+}
+
 impl<'a, T: 'a> BytesDecode<'a> for SerdeJson<T>
 where
     T: Deserialize<'a>,
@@ -1,7 +1,7 @@
 use std::borrow::Cow;
-use std::str;
+use std::{io, str};
 
-use heed_traits::{BoxedError, BytesDecode, BytesEncode};
+use heed_traits::{BoxedError, BytesDecode, BytesEncode, BytesEncodeInto};
 
 /// Describes a [`prim@str`].
 pub enum Str {}
@@ -14,6 +14,19 @@ impl BytesEncode<'_> for Str {
     }
 }
 
+impl BytesEncodeInto<'_> for Str {
+    fn size_hint(item: &Self::EItem) -> Option<usize> {
+        Some(item.len())
+    }
+
+    fn bytes_encode_into<W: io::Write>(
+        item: &Self::EItem,
+        writer: &mut W,
+    ) -> Result<(), BoxedError> {
+        writer.write_all(item.as_bytes()).map_err(Into::into)
+    }
+}
+
 impl<'a> BytesDecode<'a> for Str {
     type DItem = &'a str;
 
@@ -0,0 +1,514 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::{error, fmt, str};
+
+use byteorder::{BigEndian, ByteOrder};
+use heed_traits::{BoxedError, BytesDecode, BytesEncode, BytesEncodeInto};
+
+use crate::{
+    Bytes, OrderedF32, OrderedF64, OrderedI128, OrderedI16, OrderedI32, OrderedI64, OrderedI8, Str,
+    U128, U16, U32, U64, U8,
+};
+
+/// An order-preserving codec for tuples of [`TupleElement`]s, in the spirit of
+/// the [FoundationDB tuple layer](https://github.com/apple/foundationdb/blob/main/design/tuple.md).
+///
+/// Fixed-size elements, like integers, are written as is. Variable-size elements, like
+/// strings and byte slices, are escaped and terminated unless they are the last element,
+/// this way the lexicographic order of the bytes matches the order of the tuples.
+///
+/// Only the order-preserving codecs are elements: the unsigned integers in big-endian,
+/// and the signed integers and floats through [`OrderedI32`], [`OrderedF64`] and the like.
+///
+/// Use the [`TuplePrefix`] codec to encode the first elements of a tuple and iterate
+/// over the entries starting with them with `Database::prefix_iter`.
+///
+/// ```
+/// use byteorder::BigEndian;
+/// use heed_types::{Str, Tuple, U32};
+/// use heed_traits::{BytesDecode, BytesEncode};
+///
+/// type Key = Tuple<(Str, U32<BigEndian>, Str)>;
+///
+/// let a = Key::bytes_encode(&("hello", 1, "world"))?;
+/// let b = Key::bytes_encode(&("hello", 2, "a"))?;
+/// let c = Key::bytes_encode(&("hello\0", 0, "a"))?;
+/// assert!(a < b && b < c);
+///
+/// let (hello, one, world) = Key::bytes_decode(&a)?;
+/// assert_eq!((&*hello, one, &*world), ("hello", 1, "world"));
+/// # Ok::<(), heed_traits::BoxedError>(())
+/// ```
+pub struct Tuple<T>(PhantomData<T>);
+
+/// Encodes the first elements of a [`Tuple`] such that the encoded bytes are a prefix
+/// of the encoding of all the tuples starting with those elements.
+///
+/// The prefix must have fewer elements than the tuple: its last element is terminated,
+/// unlike the last element of a tuple, use the [`Tuple`] codec to encode a complete key.
+///
+/// ```
+/// use byteorder::BigEndian;
+/// use heed_types::{Str, Tuple, TuplePrefix, U32};
+/// use heed_traits::BytesEncode;
+///
+/// let key = Tuple::<(Str, U32<BigEndian>, Str)>::bytes_encode(&("hello", 1, "world"))?;
+/// let prefix = TuplePrefix::<(Str, U32<BigEndian>)>::bytes_encode(&("hello", 1))?;
+/// assert!(key.starts_with(&prefix));
+/// # Ok::<(), heed_traits::BoxedError>(())
+/// ```
+pub struct TuplePrefix<T>(PhantomData<T>);
+
+/// An element of a [`Tuple`].
+///
+/// The lexicographic order of the encoded elements must match the order of the elements.
+pub trait TupleElement<'a> {
+    /// The type to encode.
+    type EItem: 'a;
+
+    /// The type to decode.
+    type DItem: 'a;
+
+    /// Appends the encoded element to the output.
+    ///
+    /// The variable-size elements must be escaped and terminated unless `last` is true.
+    fn encode_element(
+        item: &Self::EItem,
+        last: bool,
+        output: &mut Vec<u8>,
+    ) -> Result<(), BoxedError>;
+
+    /// Decodes the element from the input and advances it past the element bytes.
+    fn decode_element(input: &mut &'a [u8], last: bool) -> Result<Self::DItem, BoxedError>;
+}
+
+/// The byte that starts the escape and terminator sequences.
+const ESCAPE: u8 = 0x00;
+/// Follows the [`ESCAPE`] byte to represent a `0x00` byte of the element.
+const ESCAPED_NUL: u8 = 0xFF;
+/// Follows the [`ESCAPE`] byte to terminate the element, it sorts before [`ESCAPED_NUL`].
+const TERMINATOR: u8 = 0x01;
+
+/// Appends the variable-size bytes, escaped and terminated if they are not the last element.
+pub(crate) fn encode_variable(bytes: &[u8], last: bool, output: &mut Vec<u8>) {
+    if last {
+        output.extend_from_slice(bytes);
+    } else {
+        for &byte in bytes {
+            output.push(byte);
+            if byte == ESCAPE {
+                output.push(ESCAPED_NUL);
+            }
+        }
+        output.extend_from_slice(&[ESCAPE, TERMINATOR]);
+    }
+}
+
+/// Extracts the variable-size bytes, they are only copied if they contain escaped bytes.
+pub(crate) fn decode_variable<'a>(
+    input: &mut &'a [u8],
+    last: bool,
+) -> Result<Cow<'a, [u8]>, TupleError> {
+    if last {
+        let bytes = *input;
+        *input = &[];
+        return Ok(Cow::Borrowed(bytes));
+    }
+
+    let bytes = *input;
+    let mut unescaped: Option<Vec<u8>> = None;
+    let mut start = 0;
+    let mut i = 0;
+    loop {
+        match bytes.get(i) {
+            Some(&ESCAPE) => match bytes.get(i + 1) {
+                Some(&TERMINATOR) => break,
+                Some(&ESCAPED_NUL) => {
+                    let unescaped = unescaped.get_or_insert_with(Vec::new);
+                    unescaped.extend_from_slice(&bytes[start..=i]);
+                    start = i + 2;
+                    i += 2;
+                }
+                Some(&byte) => return Err(TupleError::InvalidEscape(byte)),
+                None => return Err(TupleError::UnexpectedEnd),
+            },
+            Some(_) => i += 1,
+            None => return Err(TupleError::UnexpectedEnd),
+        }
+    }
+
+    *input = &bytes[i + 2..];
+    match unescaped {
+        Some(mut unescaped) => {
+            unescaped.extend_from_slice(&bytes[start..i]);
+            Ok(Cow::Owned(unescaped))
+        }
+        None => Ok(Cow::Borrowed(&bytes[..i])),
+    }
+}
+
+/// Extracts the given number of bytes from the input.
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], TupleError> {
+    if input.len() < len {
+        return Err(TupleError::UnexpectedEnd);
+    }
+    let (bytes, remaining) = input.split_at(len);
+    *input = remaining;
+    Ok(bytes)
+}
+
+impl<'a> TupleElement<'a> for Str {
+    type EItem = &'a str;
+    type DItem = Cow<'a, str>;
+
+    fn encode_element(
+        item: &Self::EItem,
+        last: bool,
+        output: &mut Vec<u8>,
+    ) -> Result<(), BoxedError> {
+        encode_variable(item.as_bytes(), last, output);
+        Ok(())
+    }
+
+    fn decode_element(input: &mut &'a [u8], last: bool) -> Result<Self::DItem, BoxedError> {
+        match decode_variable(input, last)? {
+            Cow::Borrowed(bytes) => str::from_utf8(bytes).map(Cow::Borrowed).map_err(Into::into),
+            Cow::Owned(bytes) => String::from_utf8(bytes).map(Cow::Owned).map_err(Into::into),
+        }
+    }
+}
+
+impl<'a> TupleElement<'a> for Bytes {
+    type EItem = &'a [u8];
+    type DItem = Cow<'a, [u8]>;
+
+    fn encode_element(
+        item: &Self::EItem,
+        last: bool,
+        output: &mut Vec<u8>,
+    ) -> Result<(), BoxedError> {
+        encode_variable(item, last, output);
+        Ok(())
+    }
+
+    fn decode_element(input: &mut &'a [u8], last: bool) -> Result<Self::DItem, BoxedError> {
+        decode_variable(input, last).map_err(Into::into)
+    }
+}
+
+impl TupleElement<'_> for U8 {
+    type EItem = u8;
+    type DItem = u8;
+
+    fn encode_element(
+        item: &Self::EItem,
+        _last: bool,
+        output: &mut Vec<u8>,
+    ) -> Result<(), BoxedError> {
+        output.push(*item);
+        Ok(())
+    }
+
+    fn decode_element(input: &mut &[u8], _last: bool) -> Result<Self::DItem, BoxedError> {
+        Ok(take(input, 1)?[0])
+    }
+}
+
+macro_rules! impl_integer_element {
+    ($($name:ident, $native:ident, $read_method:ident, $write_method:ident);* $(;)?) => {$(
+        impl TupleElement<'_> for $name<BigEndian> {
+            type EItem = $native;
+            type DItem = $native;
+
+            fn encode_element(
+                item: &Self::EItem,
+                _last: bool,
+                output: &mut Vec<u8>,
+            ) -> Result<(), BoxedError> {
+                let mut buf = [0; size_of::<$native>()];
+                BigEndian::$write_method(&mut buf, *item);
+                output.extend_from_slice(&buf);
+                Ok(())
+            }
+
+            fn decode_element(input: &mut &[u8], _last: bool) -> Result<Self::DItem, BoxedError> {
+                Ok(BigEndian::$read_method(take(input, size_of::<$native>())?))
+            }
+        }
+    )*};
+}
+
+impl_integer_element! {
+    U16, u16, read_u16, write_u16;
+    U32, u32, read_u32, write_u32;
+    U64, u64, read_u64, write_u64;
+    U128, u128, read_u128, write_u128;
+}
+
+macro_rules! impl_ordered_element {
+    ($($name:ident, $native:ident);* $(;)?) => {$(
+        impl TupleElement<'_> for $name {
+            type EItem = $native;
+            type DItem = $native;
+
+            fn encode_element(
+                item: &Self::EItem,
+                _last: bool,
+                output: &mut Vec<u8>,
+            ) -> Result<(), BoxedError> {
+                output.extend_from_slice(&$name::bytes_encode(item)?);
+                Ok(())
+            }
+
+            fn decode_element(input: &mut &[u8], _last: bool) -> Result<Self::DItem, BoxedError> {
+                $name::bytes_decode(take(input, size_of::<$native>())?)
+            }
+        }
+    )*};
+}
+
+impl_ordered_element! {
+    OrderedI8, i8;
+    OrderedI16, i16;
+    OrderedI32, i32;
+    OrderedI64, i64;
+    OrderedI128, i128;
+    OrderedF32, f32;
+    OrderedF64, f64;
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident: $index:tt),+; $last_name:ident: $last_index:tt) => {
+        impl<'a, $($name,)+ $last_name> BytesEncode<'a> for Tuple<($($name,)+ $last_name,)>
+        where
+            $($name: TupleElement<'a>,)+
+            $last_name: TupleElement<'a>,
+        {
+            type EItem = ($($name::EItem,)+ $last_name::EItem,);
+
+            fn bytes_encode(item: &'a Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+                let mut output = Vec::new();
+                $($name::encode_element(&item.$index, false, &mut output)?;)+
+                $last_name::encode_element(&item.$last_index, true, &mut output)?;
+                Ok(Cow::Owned(output))
+            }
+        }
+
+        impl<'a, $($name,)+ $last_name> BytesEncodeInto<'a> for Tuple<($($name,)+ $last_name,)>
+        where
+            $($name: TupleElement<'a>,)+
+            $last_name: TupleElement<'a>,
+        {
+        }
+
+        impl<'a, $($name,)+ $last_name> BytesDecode<'a> for Tuple<($($name,)+ $last_name,)>
+        where
+            $($name: TupleElement<'a>,)+
+            $last_name: TupleElement<'a>,
+        {
+            type DItem = ($($name::DItem,)+ $last_name::DItem,);
+
+            fn bytes_decode(mut bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+                let item = (
+                    $($name::decode_element(&mut bytes, false)?,)+
+                    $last_name::decode_element(&mut bytes, true)?,
+                );
+                if bytes.is_empty() {
+                    Ok(item)
+                } else {
+                    Err(TupleError::TrailingBytes.into())
+                }
+            }
+        }
+
+        impl<'a, $($name,)+ $last_name> BytesEncode<'a> for TuplePrefix<($($name,)+ $last_name,)>
+        where
+            $($name: TupleElement<'a>,)+
+            $last_name: TupleElement<'a>,
+        {
+            type EItem = ($($name::EItem,)+ $last_name::EItem,);
+
+            fn bytes_encode(item: &'a Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+                let mut output = Vec::new();
+                $($name::encode_element(&item.$index, false, &mut output)?;)+
+                $last_name::encode_element(&item.$last_index, false, &mut output)?;
+                Ok(Cow::Owned(output))
+            }
+        }
+
+        impl<'a, $($name,)+ $last_name> BytesEncodeInto<'a>
+            for TuplePrefix<($($name,)+ $last_name,)>
+        where
+            $($name: TupleElement<'a>,)+
+            $last_name: TupleElement<'a>,
+        {
+        }
+    };
+}
+
+impl<'a, A: TupleElement<'a>> BytesEncode<'a> for Tuple<(A,)> {
+    type EItem = (A::EItem,);
+
+    fn bytes_encode(item: &'a Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+        let mut output = Vec::new();
+        A::encode_element(&item.0, true, &mut output)?;
+        Ok(Cow::Owned(output))
+    }
+}
+
+impl<'a, A: TupleElement<'a>> BytesEncodeInto<'a> for Tuple<(A,)> {}
+
+impl<'a, A: TupleElement<'a>> BytesDecode<'a> for Tuple<(A,)> {
+    type DItem = (A::DItem,);
+
+    fn bytes_decode(mut bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        let item = (A::decode_element(&mut bytes, true)?,);
+        if bytes.is_empty() {
+            Ok(item)
+        } else {
+            Err(TupleError::TrailingBytes.into())
+        }
+    }
+}
+
+impl<'a, A: TupleElement<'a>> BytesEncode<'a> for TuplePrefix<(A,)> {
+    type EItem = (A::EItem,);
+
+    fn bytes_encode(item: &'a Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+        let mut output = Vec::new();
+        A::encode_element(&item.0, false, &mut output)?;
+        Ok(Cow::Owned(output))
+    }
+}
+
+impl<'a, A: TupleElement<'a>> BytesEncodeInto<'a> for TuplePrefix<(A,)> {}
+
+impl_tuple!(A: 0; B: 1);
+impl_tuple!(A: 0, B: 1; C: 2);
+impl_tuple!(A: 0, B: 1, C: 2; D: 3);
+impl_tuple!(A: 0, B: 1, C: 2, D: 3; E: 4);
+impl_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4; F: 5);
+impl_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5; G: 6);
+impl_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6; H: 7);
+
+/// An error that occurred while decoding a [`Tuple`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TupleError {
+    /// The bytes ended before the end of an element.
+    UnexpectedEnd,
+    /// There are remaining bytes after the last element.
+    TrailingBytes,
+    /// The escape byte is followed by an unexpected byte.
+    InvalidEscape(u8),
+}
+
+impl fmt::Display for TupleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TupleError::UnexpectedEnd => {
+                f.write_str("the bytes ended before the end of an element")
+            }
+            TupleError::TrailingBytes => {
+                f.write_str("there are remaining bytes after the last element")
+            }
+            TupleError::InvalidEscape(byte) => {
+                write!(f, "the escape byte is followed by the unexpected {byte:#04x} byte")
+            }
+        }
+    }
+}
+
+impl error::Error for TupleError {}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    type Key = Tuple<(Bytes, U16<BigEndian>, Str)>;
+
+    #[test]
+    fn bytes_order_matches_tuple_order() {
+        let mut tuples: Vec<(&[u8], u16, &str)> = vec![
+            (b"", 0, ""),
+            (b"", 1, "a"),
+            (b"\0", 0, ""),
+            (b"\0\0", 0, "z"),
+            (b"\0\x01", 0, ""),
+            (b"a", 0xFFFF, ""),
+            (b"a\0", 0, ""),
+            (b"a\xFF", 0, ""),
+            (b"b", 0, "\0"),
+        ];
+        tuples.sort();
+
+        let encoded: Vec<_> =
+            tuples.iter().map(|tuple| Key::bytes_encode(tuple).unwrap().into_owned()).collect();
+        assert!(encoded.windows(2).all(|w| w[0] < w[1]));
+
+        for (tuple, bytes) in tuples.iter().zip(&encoded) {
+            let (a, b, c) = Key::bytes_decode(bytes).unwrap();
+            assert_eq!((&*a, b, &*c), *tuple);
+        }
+    }
+
+    #[test]
+    fn borrowed_when_not_escaped() {
+        let item = (&b"hello"[..], 1, "world");
+        let bytes = Key::bytes_encode(&item).unwrap();
+        let (a, _, c) = Key::bytes_decode(&bytes).unwrap();
+        assert!(matches!(a, Cow::Borrowed(b"hello")));
+        assert!(matches!(c, Cow::Borrowed("world")));
+
+        let item = (&b"hel\0lo"[..], 1, "wo\0rld");
+        let bytes = Key::bytes_encode(&item).unwrap();
+        let (a, _, c) = Key::bytes_decode(&bytes).unwrap();
+        assert!(matches!(a, Cow::Owned(ref a) if a == b"hel\0lo"));
+        assert!(matches!(c, Cow::Borrowed("wo\0rld")));
+    }
+
+    #[test]
+    fn prefix_is_a_prefix() {
+        let item = (&b"a\0b"[..], 42, "world");
+        let key = Key::bytes_encode(&item).unwrap();
+        let item = (&b"a\0b"[..],);
+        let short_prefix = TuplePrefix::<(Bytes,)>::bytes_encode(&item).unwrap();
+        assert!(key.starts_with(&short_prefix));
+        let item = (&b"a\0b"[..], 42);
+        let prefix = TuplePrefix::<(Bytes, U16<BigEndian>)>::bytes_encode(&item).unwrap();
+        assert!(key.starts_with(&prefix));
+
+        let item = (&b"a\0bc"[..], 42, "world");
+        let other = Key::bytes_encode(&item).unwrap();
+        assert!(!other.starts_with(&short_prefix));
+    }
+
+    proptest! {
+        #[test]
+        fn mixed_elements_order(
+            a: (Vec<u8>, u32, i64, f64, String),
+            b: (Vec<u8>, u32, i64, f64, String),
+        ) {
+            type Mixed = Tuple<(Bytes, U32<BigEndian>, OrderedI64, OrderedF64, Str)>;
+
+            let encode = |t: &(Vec<u8>, u32, i64, f64, String)| {
+                let item = (&t.0[..], t.1, t.2, t.3, &t.4[..]);
+                Mixed::bytes_encode(&item).unwrap().into_owned()
+            };
+            let order = a.0.cmp(&b.0)
+                .then(a.1.cmp(&b.1))
+                .then(a.2.cmp(&b.2))
+                .then(a.3.total_cmp(&b.3))
+                .then(a.4.cmp(&b.4));
+            let (ea, eb) = (encode(&a), encode(&b));
+            prop_assert_eq!(ea.cmp(&eb), order);
+
+            let prefix = (&a.0[..], a.1, a.2);
+            let prefix =
+                TuplePrefix::<(Bytes, U32<BigEndian>, OrderedI64)>::bytes_encode(&prefix).unwrap();
+            prop_assert!(ea.starts_with(&prefix));
+        }
+    }
+}
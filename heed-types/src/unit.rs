@@ -1,7 +1,7 @@
 use std::borrow::Cow;
-use std::{error, fmt};
+use std::{error, fmt, io};
 
-use heed_traits::{BoxedError, BytesDecode, BytesEncode};
+use heed_traits::{BoxedError, BytesDecode, BytesEncode, BytesEncodeInto};
 
 /// Describes the unit `()` type.
 pub enum Unit {}
@@ -14,6 +14,19 @@ impl BytesEncode<'_> for Unit {
     }
 }
 
+impl BytesEncodeInto<'_> for Unit {
+    fn size_hint(_item: &Self::EItem) -> Option<usize> {
+        Some(0)
+    }
+
+    fn bytes_encode_into<W: io::Write>(
+        _item: &Self::EItem,
+        _writer: &mut W,
+    ) -> Result<(), BoxedError> {
+        Ok(())
+    }
+}
+
 impl BytesDecode<'_> for Unit {
     type DItem = ();
 
@@ -0,0 +1,68 @@
+use std::error::Error;
+
+use heed::types::*;
+use heed::{Database, EnvOpenOptions, HeedCodec};
+
+#[derive(Debug, PartialEq, Eq, HeedCodec)]
+enum Level {
+    Debug,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, PartialEq, Eq, HeedCodec)]
+struct LogKey {
+    timestamp: u32,
+    level: Level,
+    // The strings are escaped and terminated, "billing" sorts before "cron"
+    service: String,
+    // The last field doesn't need to be escaped
+    id: Vec<u8>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = tempfile::tempdir()?;
+
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024) // 10MB
+            .max_dbs(3000)
+            .open(path)?
+    };
+
+    let mut wtxn = env.write_txn()?;
+    let db: Database<LogKey, Str> = env.create_database(&mut wtxn, Some("logs"))?;
+
+    let logs = [
+        (1608326232, Level::Warn, "api", "this is a very old warning"),
+        (1608326232, Level::Debug, "api", "this is a very old debug log"),
+        (1708326232, Level::Error, "cron", "this is an error"),
+        (1708326232, Level::Error, "billing", "this is another error"),
+    ];
+
+    for (timestamp, level, service, message) in logs {
+        let key = LogKey { timestamp, level, service: service.to_string(), id: vec![0, 1] };
+        db.put(&mut wtxn, &key, message)?;
+    }
+
+    // The keys are sorted by timestamp, level and service.
+    let messages: Vec<_> =
+        db.iter(&wtxn)?.map(|result| result.map(|(_, msg)| msg)).collect::<Result<_, _>>()?;
+    assert_eq!(
+        messages,
+        [
+            "this is a very old debug log",
+            "this is a very old warning",
+            "this is another error",
+            "this is an error",
+        ]
+    );
+
+    let (key, _) = db.last(&wtxn)?.unwrap();
+    assert_eq!(key.level, Level::Error);
+    assert_eq!(key.service, "cron");
+
+    wtxn.commit()?;
+
+    Ok(())
+}
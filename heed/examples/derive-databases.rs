@@ -0,0 +1,50 @@
+use std::error::Error;
+
+use heed::byteorder::BigEndian;
+use heed::types::*;
+use heed::{Database, Databases, EnvOpenOptions};
+
+#[derive(Databases)]
+struct MyDatabases {
+    users: Database<U32<BigEndian>, Str>,
+    #[heed(name = "user-by-name")]
+    user_by_name: Database<Str, U32<BigEndian>>,
+    #[heed(flags(DUP_SORT))]
+    followers: Database<U32<BigEndian>, U32<BigEndian>>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = tempfile::tempdir()?;
+
+    let env = unsafe {
+        EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024) // 10MB
+            .max_dbs(MyDatabases::COUNT)
+            .open(path)?
+    };
+
+    // The databases don't exist yet
+    let rtxn = env.read_txn()?;
+    assert!(MyDatabases::open(&env, &rtxn)?.is_none());
+    drop(rtxn);
+
+    let mut wtxn = env.write_txn()?;
+    let dbs = MyDatabases::create(&env, &mut wtxn)?;
+
+    dbs.users.put(&mut wtxn, &0, "kero")?;
+    dbs.users.put(&mut wtxn, &1, "loic")?;
+    dbs.user_by_name.put(&mut wtxn, "kero", &0)?;
+    dbs.user_by_name.put(&mut wtxn, "loic", &1)?;
+    dbs.followers.put(&mut wtxn, &0, &1)?;
+    dbs.followers.put(&mut wtxn, &1, &0)?;
+
+    wtxn.commit()?;
+
+    let rtxn = env.read_txn()?;
+    let dbs = MyDatabases::open(&env, &rtxn)?.unwrap();
+    let kero = dbs.user_by_name.get(&rtxn, "kero")?.unwrap();
+    let followers: Vec<_> = dbs.followers.get_duplicates(&rtxn, &kero)?.unwrap().collect();
+    println!("{kero} is followed by {followers:?}");
+
+    Ok(())
+}
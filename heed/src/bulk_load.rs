@@ -0,0 +1,368 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{marker, mem, process, vec};
+
+use crate::{Comparator, PutFlags, Result, RwCursor};
+
+/// The default amount of memory used to sort entries before spilling them to disk.
+const DEFAULT_MAX_MEMORY: usize = 256 * 1024 * 1024;
+
+/// The maximum number of temporary files before they are merged into a single one.
+const MAX_RUNS: usize = 64;
+
+/// An approximation of the memory used by an entry in addition to its bytes.
+const ENTRY_OVERHEAD: usize = 2 * mem::size_of::<Vec<u8>>();
+
+/// Options which can be used to configure how entries are bulk loaded into a database.
+///
+/// The entries are sorted in memory, up to [`Self::max_memory`] bytes. Beyond that the sorted
+/// entries are written to a temporary file and the remaining ones are sorted separately,
+/// all the files are finally merged while the entries are appended to the database.
+///
+/// ```
+/// use heed::BulkLoadOptions;
+///
+/// let options = BulkLoadOptions::new()
+///     .max_memory(64 * 1024 * 1024) // 64MiB
+///     .temp_dir(std::env::temp_dir());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkLoadOptions {
+    max_memory: usize,
+    temp_dir: Option<PathBuf>,
+}
+
+impl Default for BulkLoadOptions {
+    fn default() -> Self {
+        BulkLoadOptions::new()
+    }
+}
+
+impl BulkLoadOptions {
+    /// Creates a blank new set of options ready for configuration.
+    pub fn new() -> BulkLoadOptions {
+        BulkLoadOptions { max_memory: DEFAULT_MAX_MEMORY, temp_dir: None }
+    }
+
+    /// Sets the maximum number of bytes of entries kept in memory before they are
+    /// spilled to a temporary file. Defaults to 256MiB.
+    pub fn max_memory(mut self, bytes: usize) -> Self {
+        self.max_memory = bytes;
+        self
+    }
+
+    /// Sets the directory where the temporary files are created.
+    ///
+    /// Defaults to the directory returned by [`std::env::temp_dir`].
+    pub fn temp_dir<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.temp_dir = Some(path.as_ref().to_path_buf());
+        self
+    }
+}
+
+/// Compares two entries, the values are only compared in `DUP_SORT` databases.
+fn compare_entries<C: Comparator, CDUP: Comparator>(
+    dup_sort: bool,
+    (akey, adata): (&[u8], &[u8]),
+    (bkey, bdata): (&[u8], &[u8]),
+) -> Ordering {
+    let ordering = C::compare(akey, bkey);
+    if dup_sort {
+        ordering.then_with(|| CDUP::compare(adata, bdata))
+    } else {
+        ordering
+    }
+}
+
+/// Sorts the entries in memory and spills them to temporary files when they don't fit.
+pub(crate) struct Sorter<'o, C, CDUP> {
+    options: &'o BulkLoadOptions,
+    dup_sort: bool,
+    entries: Vec<(Vec<u8>, Vec<u8>)>,
+    memory: usize,
+    runs: Vec<Run>,
+    _phantom: marker::PhantomData<(C, CDUP)>,
+}
+
+impl<'o, C: Comparator, CDUP: Comparator> Sorter<'o, C, CDUP> {
+    pub(crate) fn new(options: &'o BulkLoadOptions, dup_sort: bool) -> Sorter<'o, C, CDUP> {
+        Sorter {
+            options,
+            dup_sort,
+            entries: Vec::new(),
+            memory: 0,
+            runs: Vec::new(),
+            _phantom: marker::PhantomData,
+        }
+    }
+
+    pub(crate) fn insert(&mut self, key: &[u8], data: &[u8]) -> io::Result<()> {
+        self.memory += key.len() + data.len() + ENTRY_OVERHEAD;
+        self.entries.push((key.to_vec(), data.to_vec()));
+        if self.memory > self.options.max_memory {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    /// Sorts the entries and removes the duplicated ones, the last inserted one wins.
+    fn sort_entries(&mut self) {
+        let dup_sort = self.dup_sort;
+        let compare = |a: &(Vec<u8>, Vec<u8>), b: &(Vec<u8>, Vec<u8>)| {
+            compare_entries::<C, CDUP>(dup_sort, (&a.0, &a.1), (&b.0, &b.1))
+        };
+
+        // The sort is stable, the last inserted entry is the last of its equals.
+        self.entries.sort_by(compare);
+        self.entries.dedup_by(|later, kept| {
+            let equal = compare(later, kept).is_eq();
+            if equal {
+                mem::swap(later, kept);
+            }
+            equal
+        });
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        self.sort_entries();
+        let mut writer = RunWriter::create(self.temp_dir())?;
+        for (key, data) in self.entries.drain(..) {
+            writer.write_entry(&key, &data)?;
+        }
+        self.runs.push(writer.into_run()?);
+        self.memory = 0;
+
+        // Merge the temporary files to avoid running out of file descriptors.
+        if self.runs.len() >= MAX_RUNS {
+            let sources = self.runs.drain(..).map(Source::Run).collect();
+            let mut merged = SortedEntries::<C, CDUP>::new(self.dup_sort, sources)?;
+            let mut writer = RunWriter::create(self.temp_dir())?;
+            while let Some((key, data, _)) = merged.next_entry()? {
+                writer.write_entry(key, data)?;
+            }
+            self.runs.push(writer.into_run()?);
+        }
+
+        Ok(())
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        match &self.options.temp_dir {
+            Some(dir) => dir.clone(),
+            None => std::env::temp_dir(),
+        }
+    }
+
+    /// Returns the entries in order, merging the temporary files with the remaining entries.
+    pub(crate) fn into_sorted_entries(mut self) -> io::Result<SortedEntries<C, CDUP>> {
+        self.sort_entries();
+        let mut sources: Vec<_> = self.runs.into_iter().map(Source::Run).collect();
+        sources.push(Source::Memory(mem::take(&mut self.entries).into_iter()));
+        SortedEntries::new(self.dup_sort, sources)
+    }
+}
+
+/// Writes sorted entries into a new temporary file.
+struct RunWriter {
+    writer: BufWriter<File>,
+    path: TempPath,
+}
+
+impl RunWriter {
+    fn create(dir: PathBuf) -> io::Result<RunWriter> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos());
+        let counter = COUNTER.fetch_add(1, AtomicOrdering::Relaxed);
+        let name = format!("heed-bulk-load-{}-{nanos}-{counter}.run", process::id());
+        let path = TempPath(dir.join(name));
+        let file = OpenOptions::new().read(true).write(true).create_new(true).open(&path.0)?;
+
+        Ok(RunWriter { writer: BufWriter::new(file), path })
+    }
+
+    fn write_entry(&mut self, key: &[u8], data: &[u8]) -> io::Result<()> {
+        self.writer.write_all(&(key.len() as u64).to_le_bytes())?;
+        self.writer.write_all(key)?;
+        self.writer.write_all(&(data.len() as u64).to_le_bytes())?;
+        self.writer.write_all(data)
+    }
+
+    fn into_run(self) -> io::Result<Run> {
+        let mut file = self.writer.into_inner().map_err(io::IntoInnerError::into_error)?;
+        file.rewind()?;
+        Ok(Run { reader: BufReader::new(file), _path: self.path })
+    }
+}
+
+/// A temporary file containing sorted entries, removed once dropped.
+struct Run {
+    reader: BufReader<File>,
+    _path: TempPath,
+}
+
+impl Run {
+    fn next_entry(&mut self) -> io::Result<Option<(Vec<u8>, Vec<u8>)>> {
+        if self.reader.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+        let key = read_bytes(&mut self.reader)?;
+        let data = read_bytes(&mut self.reader)?;
+        Ok(Some((key, data)))
+    }
+}
+
+fn read_bytes<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut len = [0; mem::size_of::<u64>()];
+    reader.read_exact(&mut len)?;
+    let len = usize::try_from(u64::from_le_bytes(len))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// A path removed once dropped.
+struct TempPath(PathBuf);
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+enum Source {
+    Run(Run),
+    Memory(vec::IntoIter<(Vec<u8>, Vec<u8>)>),
+}
+
+impl Source {
+    fn next_entry(&mut self) -> io::Result<Option<(Vec<u8>, Vec<u8>)>> {
+        match self {
+            Source::Run(run) => run.next_entry(),
+            Source::Memory(entries) => Ok(entries.next()),
+        }
+    }
+}
+
+/// The next entry of a source, ordered by entry and then by source.
+struct HeapEntry<C, CDUP> {
+    dup_sort: bool,
+    key: Vec<u8>,
+    data: Vec<u8>,
+    source: usize,
+    _phantom: marker::PhantomData<(C, CDUP)>,
+}
+
+impl<C: Comparator, CDUP: Comparator> HeapEntry<C, CDUP> {
+    fn compare_entry(&self, other: &Self) -> Ordering {
+        compare_entries::<C, CDUP>(
+            self.dup_sort,
+            (&self.key, &self.data),
+            (&other.key, &other.data),
+        )
+    }
+}
+
+impl<C: Comparator, CDUP: Comparator> Ord for HeapEntry<C, CDUP> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The heap is a max-heap, we reverse the order to get the smallest entry first.
+        self.compare_entry(other).then(self.source.cmp(&other.source)).reverse()
+    }
+}
+
+impl<C: Comparator, CDUP: Comparator> PartialOrd for HeapEntry<C, CDUP> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Comparator, CDUP: Comparator> PartialEq for HeapEntry<C, CDUP> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl<C: Comparator, CDUP: Comparator> Eq for HeapEntry<C, CDUP> {}
+
+/// Merges the sorted sources into a single sequence of sorted and unique entries.
+pub(crate) struct SortedEntries<C, CDUP> {
+    dup_sort: bool,
+    sources: Vec<Source>,
+    heap: BinaryHeap<HeapEntry<C, CDUP>>,
+    current: Option<HeapEntry<C, CDUP>>,
+}
+
+impl<C: Comparator, CDUP: Comparator> SortedEntries<C, CDUP> {
+    fn new(dup_sort: bool, mut sources: Vec<Source>) -> io::Result<SortedEntries<C, CDUP>> {
+        let mut heap = BinaryHeap::with_capacity(sources.len());
+        for (source, entries) in sources.iter_mut().enumerate() {
+            if let Some((key, data)) = entries.next_entry()? {
+                heap.push(HeapEntry { dup_sort, key, data, source, _phantom: marker::PhantomData });
+            }
+        }
+        Ok(SortedEntries { dup_sort, sources, heap, current: None })
+    }
+
+    /// Returns the key of the next entry, without consuming it.
+    pub(crate) fn peek_key(&self) -> Option<&[u8]> {
+        self.heap.peek().map(|entry| &entry.key[..])
+    }
+
+    /// Pops the smallest entry of the heap and pushes the next entry of its source.
+    fn pop(&mut self) -> io::Result<Option<HeapEntry<C, CDUP>>> {
+        let Some(entry) = self.heap.pop() else { return Ok(None) };
+        if let Some((key, data)) = self.sources[entry.source].next_entry()? {
+            let dup_sort = self.dup_sort;
+            let source = entry.source;
+            self.heap.push(HeapEntry {
+                dup_sort,
+                key,
+                data,
+                source,
+                _phantom: marker::PhantomData,
+            });
+        }
+        Ok(Some(entry))
+    }
+
+    /// Returns the next entry, the entries of later sources win over the equal ones.
+    ///
+    /// The returned boolean tells whether the key is the same as the one of the previous entry.
+    #[allow(clippy::type_complexity)]
+    fn next_entry(&mut self) -> io::Result<Option<(&[u8], &[u8], bool)>> {
+        let Some(mut entry) = self.pop()? else {
+            self.current = None;
+            return Ok(None);
+        };
+
+        while self.heap.peek().is_some_and(|next| next.compare_entry(&entry).is_eq()) {
+            if let Some(next) = self.pop()? {
+                entry = next;
+            }
+        }
+
+        let same_key =
+            self.current.as_ref().is_some_and(|c| C::compare(&c.key, &entry.key).is_eq());
+        let entry = self.current.insert(entry);
+        Ok(Some((&entry.key, &entry.data, same_key)))
+    }
+
+    /// Appends up to `limit` entries with the cursor and returns the number of entries written.
+    pub(crate) fn append_to(&mut self, cursor: &mut RwCursor, limit: usize) -> Result<usize> {
+        let mut written = 0;
+        while written < limit {
+            let Some((key, data, same_key)) = self.next_entry()? else { break };
+            let flags = if same_key { PutFlags::APPEND_DUP } else { PutFlags::APPEND };
+            // SAFETY: the entries are owned and are not borrowed from the database
+            unsafe { cursor.put_current_with_flags(flags, key, data)? };
+            written += 1;
+        }
+        Ok(written)
+    }
+}
@@ -1,21 +1,34 @@
 use std::ops::{Deref, DerefMut};
-use std::{marker, mem, ptr};
+use std::{mem, ptr};
 
+use crate::envs::EnvInner;
 use crate::mdb::error::mdb_result;
 use crate::mdb::ffi;
 use crate::*;
 
 pub struct RoCursor<'txn> {
     cursor: *mut ffi::MDB_cursor,
-    _marker: marker::PhantomData<&'txn ()>,
+    env: &'txn EnvInner,
+    dbi: ffi::MDB_dbi,
 }
 
 impl<'txn> RoCursor<'txn> {
     pub(crate) fn new<T>(txn: &'txn RoTxn<T>, dbi: ffi::MDB_dbi) -> Result<RoCursor<'txn>> {
         let mut cursor: *mut ffi::MDB_cursor = ptr::null_mut();
-        let mut txn = txn.txn_ptr();
-        unsafe { mdb_result(ffi::mdb_cursor_open(txn.as_mut(), dbi, &mut cursor))? }
-        Ok(RoCursor { cursor, _marker: marker::PhantomData })
+        let mut txn_ptr = txn.txn_ptr();
+        unsafe { mdb_result(ffi::mdb_cursor_open(txn_ptr.as_mut(), dbi, &mut cursor))? }
+        Ok(RoCursor { cursor, env: txn.env_inner(), dbi })
+    }
+
+    /// Attaches the database, the operation and the key to the error
+    /// if the environment records the context of the errors.
+    pub(crate) fn error_context(
+        &self,
+        error: Error,
+        operation: &'static str,
+        key: Option<&[u8]>,
+    ) -> Error {
+        self.env.error_context(error, self.dbi, operation, key)
     }
 
     pub fn current(&mut self) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
@@ -173,6 +186,128 @@ impl<'txn> RoCursor<'txn> {
         }
     }
 
+    /// Moves the cursor on the given key and value, the database must be `DUP_SORT`.
+    pub fn move_on_key_data(&mut self, key: &[u8], data: &[u8]) -> Result<bool> {
+        let mut key_val = unsafe { crate::into_val(key) };
+        let mut data_val = unsafe { crate::into_val(data) };
+
+        // Move the cursor to the specified key and value
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_get(
+                self.cursor,
+                &mut key_val,
+                &mut data_val,
+                ffi::cursor_op::MDB_GET_BOTH,
+            ))
+        };
+
+        match result {
+            Ok(()) => Ok(true),
+            Err(e) if e.not_found() => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Moves the cursor on the first value of the given key greater than or equal
+    /// to the given one, the database must be `DUP_SORT`.
+    pub fn move_on_key_data_greater_than_or_equal_to(
+        &mut self,
+        key: &[u8],
+        data: &[u8],
+    ) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
+        let mut key_val = unsafe { crate::into_val(key) };
+        let mut data_val = unsafe { crate::into_val(data) };
+
+        // Move the cursor to the specified key and the nearest value
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_get(
+                self.cursor,
+                &mut key_val,
+                &mut data_val,
+                ffi::cursor_op::MDB_GET_BOTH_RANGE,
+            ))
+        };
+
+        // The key is not updated by LMDB and still points to the given one
+        match result {
+            Ok(()) => self.current(),
+            Err(e) if e.not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Moves the cursor on the given key and returns up to a page of its values,
+    /// the database must be `DUP_FIXED`.
+    ///
+    /// The values are returned as a single contiguous slice of fixed-size items.
+    pub fn move_on_key_multiple(&mut self, key: &[u8]) -> Result<Option<&'txn [u8]>> {
+        let mut key_val = unsafe { crate::into_val(key) };
+        let mut data_val = ffi::MDB_val { mv_size: 0, mv_data: ptr::null_mut() };
+
+        // Move the cursor to the specified key
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_get(
+                self.cursor,
+                &mut key_val,
+                &mut data_val,
+                ffi::cursor_op::MDB_SET,
+            ))
+        };
+
+        match result {
+            Ok(()) => (),
+            Err(e) if e.not_found() => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+
+        // LMDB leaves the data untouched when the key has a single value,
+        // this is why we reuse the value returned by the previous operation.
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_get(
+                self.cursor,
+                &mut key_val,
+                &mut data_val,
+                ffi::cursor_op::MDB_GET_MULTIPLE,
+            ))
+        };
+
+        match result {
+            Ok(()) => Ok(Some(unsafe { crate::from_val(data_val) })),
+            Err(e) if e.not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns up to a page of the next values of the key the cursor is pointing on,
+    /// the database must be `DUP_FIXED`.
+    pub fn move_on_next_multiple(&mut self) -> Result<Option<&'txn [u8]>> {
+        let mut key_val = mem::MaybeUninit::uninit();
+        let mut data_val = mem::MaybeUninit::uninit();
+
+        // Move the cursor to the next page of values
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_get(
+                self.cursor,
+                key_val.as_mut_ptr(),
+                data_val.as_mut_ptr(),
+                ffi::cursor_op::MDB_NEXT_MULTIPLE,
+            ))
+        };
+
+        match result {
+            Ok(()) => Ok(Some(unsafe { crate::from_val(data_val.assume_init()) })),
+            Err(e) if e.not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns the number of values of the key the cursor is pointing on.
+    pub fn count_duplicates(&self) -> Result<usize> {
+        let mut count = 0;
+        unsafe { mdb_result(ffi::mdb_cursor_count(self.cursor, &mut count))? };
+        Ok(count)
+    }
+
     pub fn move_on_prev(&mut self, op: MoveOperation) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
         let mut key_val = mem::MaybeUninit::uninit();
         let mut data_val = mem::MaybeUninit::uninit();
@@ -363,6 +498,86 @@ impl<'txn> RwCursor<'txn> {
         }
     }
 
+    /// Replace the value of the current entry by writing it directly into the space reserved
+    /// by LMDB, the value is rewritten in place when it keeps the same size.
+    ///
+    /// The given key **must** be equal to the one this cursor is pointing otherwise the database
+    /// can be put into an inconsistent state.
+    ///
+    /// Returns `true` if the entry was successfully written.
+    ///
+    /// # Safety
+    ///
+    /// Please read the safety notes of the [`Self::put_current`] method.
+    pub unsafe fn put_current_reserved<F>(
+        &mut self,
+        key: &[u8],
+        data_size: usize,
+        write_func: F,
+    ) -> Result<bool>
+    where
+        F: FnOnce(&mut ReservedSpace) -> io::Result<()>,
+    {
+        let mut key_val = crate::into_val(key);
+        let mut reserved = ffi::reserve_size_val(data_size);
+        let flags = ffi::MDB_CURRENT | ffi::MDB_RESERVE;
+
+        let result =
+            mdb_result(ffi::mdb_cursor_put(self.cursor.cursor, &mut key_val, &mut reserved, flags));
+
+        match result {
+            Ok(()) => (),
+            Err(e) if e.not_found() => return Ok(false),
+            Err(e) => return Err(e.into()),
+        }
+
+        let mut reserved = ReservedSpace::from_val(reserved);
+        write_func(&mut reserved)?;
+
+        if reserved.remaining() == 0 {
+            Ok(true)
+        } else {
+            Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
+        }
+    }
+
+    /// Insert many fixed-size values under the given key in a single call,
+    /// the database must be `DUP_FIXED`.
+    ///
+    /// The `data` slice is the concatenation of the values, all of `data_size` bytes.
+    /// Returns the number of values written.
+    ///
+    /// # Safety
+    ///
+    /// Please read the safety notes of the [`Self::put_current`] method.
+    pub unsafe fn put_multiple(
+        &mut self,
+        flags: PutFlags,
+        key: &[u8],
+        data: &[u8],
+        data_size: usize,
+    ) -> Result<usize> {
+        debug_assert!(data_size != 0 && data.len().is_multiple_of(data_size));
+
+        let mut key_val = crate::into_val(key);
+        // LMDB expects an array of two values: the first one points to the values
+        // and gives the size of one of them, the second one gives their count.
+        let mut data_vals = [
+            ffi::MDB_val { mv_size: data_size, mv_data: data.as_ptr() as *mut libc::c_void },
+            ffi::MDB_val { mv_size: data.len() / data_size, mv_data: ptr::null_mut() },
+        ];
+        let flags = ffi::MDB_MULTIPLE | flags.bits();
+
+        mdb_result(ffi::mdb_cursor_put(
+            self.cursor.cursor,
+            &mut key_val,
+            data_vals.as_mut_ptr(),
+            flags,
+        ))?;
+
+        Ok(data_vals[1].mv_size)
+    }
+
     /// Append the given key/value pair to the end of the database.
     ///
     /// If a key is inserted that is less than any previous key a `KeyExist` error
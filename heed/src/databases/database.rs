@@ -1,13 +1,19 @@
-use std::borrow::Cow;
+use std::borrow::{Borrow, Cow};
+use std::io::Write;
 use std::ops::{Bound, RangeBounds};
 use std::{any, fmt, marker, mem, ptr};
 
 use heed_traits::{Comparator, LexicographicComparator};
-use types::{DecodeIgnore, LazyDecode};
+use types::{Bytes, DecodeIgnore, LazyDecode};
 
+use super::encode_into;
+use crate::bulk_load::{SortedEntries, Sorter};
 use crate::cursor::MoveOperation;
 use crate::envs::DefaultComparator;
+use crate::error_context::Operation;
 use crate::iteration_method::MoveOnCurrentKeyDuplicates;
+use crate::iterator::SortedLookup;
+use crate::key_range::interpolate_keys;
 use crate::mdb::error::mdb_result;
 use crate::mdb::ffi;
 use crate::mdb::lmdb_flags::{AllDatabaseFlags, DatabaseFlags};
@@ -121,6 +127,10 @@ impl<'e, 'n, T, KC, DC, C, CDUP> DatabaseOpenOptions<'e, 'n, T, KC, DC, C, CDUP>
     }
 
     /// Specify the set of flags used to open the database.
+    ///
+    /// The flags don't change the type of the database: [`Self::open`] and [`Self::create`]
+    /// return a [`Database`] even with [`DatabaseFlags::DUP_SORT`]. Use [`Self::open_multimap`]
+    /// and [`Self::create_multimap`] to get a [`MultimapDatabase`] instead.
     pub fn flags(&mut self, flags: DatabaseFlags) -> &mut Self {
         self.flags = AllDatabaseFlags::from_bits(flags.bits()).unwrap();
         self
@@ -184,6 +194,57 @@ impl<'e, 'n, T, KC, DC, C, CDUP> DatabaseOpenOptions<'e, 'n, T, KC, DC, C, CDUP>
             Err(e) => Err(e),
         }
     }
+
+    /// Opens a typed multimap database that already exists in this environment.
+    ///
+    /// The [`DatabaseFlags::DUP_SORT`] flag is always added to the flags of the options.
+    /// Opening a database that was created without it returns an [`MdbError::Incompatible`] error.
+    ///
+    /// See [`Self::open`] for the restrictions that also apply here.
+    pub fn open_multimap(&self, rtxn: &RoTxn) -> Result<Option<MultimapDatabase<KC, DC, C, CDUP>>>
+    where
+        KC: 'static,
+        DC: 'static,
+        C: Comparator + 'static,
+        CDUP: Comparator + 'static,
+    {
+        assert_eq_env_txn!(self.env, rtxn);
+
+        let flags = self.flags | AllDatabaseFlags::DUP_SORT;
+        match self.env.raw_init_database::<C, CDUP>(rtxn.txn_ptr(), self.name, flags) {
+            Ok(dbi) => {
+                let inner = Database::new(self.env.env_mut_ptr().as_ptr() as _, dbi);
+                MultimapDatabase::new(rtxn, inner).map(Some)
+            }
+            Err(Error::Mdb(e)) if e.not_found() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Creates a typed multimap database that can already exist in this environment.
+    ///
+    /// The [`DatabaseFlags::DUP_SORT`] flag is always added to the flags of the options.
+    /// Opening a database that was created without it returns an [`MdbError::Incompatible`] error.
+    ///
+    /// See [`Self::create`] for the restrictions that also apply here.
+    pub fn create_multimap(&self, wtxn: &mut RwTxn) -> Result<MultimapDatabase<KC, DC, C, CDUP>>
+    where
+        KC: 'static,
+        DC: 'static,
+        C: Comparator + 'static,
+        CDUP: Comparator + 'static,
+    {
+        assert_eq_env_txn!(self.env, wtxn);
+
+        let flags = self.flags | AllDatabaseFlags::DUP_SORT | AllDatabaseFlags::CREATE;
+        match self.env.raw_init_database::<C, CDUP>(wtxn.txn_ptr(), self.name, flags) {
+            Ok(dbi) => {
+                let inner = Database::new(self.env.env_mut_ptr().as_ptr() as _, dbi);
+                MultimapDatabase::new(wtxn, inner)
+            }
+            Err(e) => Err(e),
+        }
+    }
 }
 
 impl<T, KC, DC, C, CDUP> Clone for DatabaseOpenOptions<'_, '_, T, KC, DC, C, CDUP> {
@@ -202,6 +263,9 @@ impl<T, KC, DC, C, CDUP> Copy for DatabaseOpenOptions<'_, '_, T, KC, DC, C, CDUP
 /// Thanks to their bytes representation, heed is able to iterate over them
 /// from the lowest to the highest.
 ///
+/// Note that the big-endian signed integers sort the negative numbers after the positive ones,
+/// use the order-preserving [`types::OrderedI64`] or [`types::OrderedF64`] codecs instead.
+///
 /// ```
 /// # use std::fs;
 /// # use std::path::Path;
@@ -361,7 +425,8 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> =
+            KC::bytes_encode(key).map_err(self.operation(txn, "get").encoding(None))?;
 
         let mut key_val = unsafe { crate::into_val(&key_bytes) };
         let mut data_val = mem::MaybeUninit::uninit();
@@ -378,12 +443,142 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
         match result {
             Ok(()) => {
                 let data = unsafe { crate::from_val(data_val.assume_init()) };
-                let data = DC::bytes_decode(data).map_err(Error::Decoding)?;
+                let data = DC::bytes_decode(data)
+                    .map_err(self.operation(txn, "get").decoding(Some(&key_bytes)))?;
                 Ok(Some(data))
             }
             Err(e) if e.not_found() => Ok(None),
-            Err(e) => Err(e.into()),
+            Err(e) => Err(self.operation(txn, "get").error(e, Some(&key_bytes))),
+        }
+    }
+
+    /// Retrieves the values associated with many keys, using a single cursor.
+    ///
+    /// The keys are encoded and sorted with the comparator `C` so that the cursor only moves
+    /// forward, which is much cheaper than descending the B-tree for every key like
+    /// [`Database::get`] does. The values are returned in the order of the given keys,
+    /// with `None` for the keys that don't exist.
+    ///
+    /// Use [`Database::get_many_sorted`] to stream the values of keys that are already sorted.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEU32 = U32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<Str, BEU32> = env.create_database(&mut wtxn, Some("get-many"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, "i-am-forty-two", &42)?;
+    /// db.put(&mut wtxn, "i-am-twenty-seven", &27)?;
+    ///
+    /// let keys = ["i-am-twenty-seven", "i-am-twenty-one", "i-am-forty-two"];
+    /// let ret = db.get_many(&wtxn, keys)?;
+    /// assert_eq!(ret, [Some(27), None, Some(42)]);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn get_many<'a, 'txn, I>(&self, txn: &'txn RoTxn, keys: I) -> Result<Vec<Option<DC::DItem>>>
+    where
+        I: IntoIterator<Item = &'a KC::EItem>,
+        KC: BytesEncode<'a> + 'a,
+        DC: BytesDecode<'txn>,
+        C: Comparator,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let keys = keys
+            .into_iter()
+            .map(|key| KC::bytes_encode(key).map(Cow::into_owned))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(self.operation(txn, "get_many").encoding(None))?;
+
+        let mut order: Vec<usize> = (0..keys.len()).collect();
+        order.sort_by(|&a, &b| C::compare(&keys[a], &keys[b]));
+
+        let mut lookup = SortedLookup::<C>::new(RoCursor::new(txn, self.dbi)?);
+        let mut values: Vec<_> = keys.iter().map(|_| None).collect();
+        for index in order {
+            let key = &keys[index];
+            if let Some(data) = lookup.get(key)? {
+                let data = DC::bytes_decode(data)
+                    .map_err(|e| lookup.error_context(Error::Decoding(e), Some(key)))?;
+                values[index] = Some(data);
+            }
         }
+
+        Ok(values)
+    }
+
+    /// Returns an iterator over the values associated with the given keys, using a single cursor.
+    ///
+    /// This is the streaming version of [`Database::get_many`]: the keys are encoded and
+    /// looked up one at a time, as the iterator advances. They must be sorted according to
+    /// the comparator `C` for the cursor to only move forward; keys out of order are still
+    /// found but each of them pays for a full lookup.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEU32 = U32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<BEU32, BEU32> = env.create_database(&mut wtxn, Some("get-many"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// for i in 0..100 {
+    ///     db.put(&mut wtxn, &(i * 2), &i)?;
+    /// }
+    ///
+    /// let keys: Vec<u32> = (0..200).collect();
+    /// let found = db
+    ///     .get_many_sorted(&wtxn, &keys)?
+    ///     .filter_map(|value| value.transpose())
+    ///     .collect::<heed::Result<Vec<_>>>()?;
+    /// assert_eq!(found, (0..100).collect::<Vec<_>>());
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn get_many_sorted<'a, 'txn, I>(
+        &self,
+        txn: &'txn RoTxn,
+        keys: I,
+    ) -> Result<RoGetMany<'txn, I::IntoIter, KC, DC, C>>
+    where
+        I: IntoIterator<Item = &'a KC::EItem>,
+        KC: BytesEncode<'a> + 'a,
+        C: Comparator,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        RoCursor::new(txn, self.dbi).map(|cursor| RoGetMany::new(cursor, keys.into_iter()))
     }
 
     /// Returns an iterator over all of the values of a single key.
@@ -450,7 +645,8 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
         assert_eq_env_db_txn!(self, txn);
 
         let mut cursor = RoCursor::new(txn, self.dbi)?;
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> =
+            KC::bytes_encode(key).map_err(self.operation(txn, "get_duplicates").encoding(None))?;
         if cursor.move_on_key(&key_bytes)? {
             Ok(Some(RoIter::new(cursor)))
         } else {
@@ -458,18 +654,16 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
         }
     }
 
-    /// Retrieves the key/value pair lower than the given one in this database.
-    ///
-    /// If the database if empty or there is no key lower than the given one,
-    /// then `None` is returned.
+    /// Returns the number of values of the given key, `0` if the key doesn't exist.
     ///
-    /// Comparisons are made by using the bytes representation of the key.
+    /// The count is read from LMDB without walking the duplicate values.
+    /// The database must be [`DUP_SORT`](DatabaseFlags::DUP_SORT),
+    /// an [`MdbError::Incompatible`] error is returned otherwise.
     ///
     /// ```
     /// # use std::fs;
     /// # use std::path::Path;
-    /// # use heed::EnvOpenOptions;
-    /// use heed::Database;
+    /// # use heed::{DatabaseFlags, EnvOpenOptions};
     /// use heed::types::*;
     /// use heed::byteorder::BigEndian;
     ///
@@ -480,65 +674,57 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEU32 = U32<BigEndian>;
+    /// type BEI64 = I64<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db = env.create_database::<BEU32, Unit>(&mut wtxn, Some("get-lt-u32"))?;
+    /// let db = env.database_options()
+    ///     .types::<BEI64, BEI64>()
+    ///     .flags(DatabaseFlags::DUP_SORT)
+    ///     .name("dup-sort")
+    ///     .create(&mut wtxn)?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &27, &())?;
-    /// db.put(&mut wtxn, &42, &())?;
-    /// db.put(&mut wtxn, &43, &())?;
-    ///
-    /// let ret = db.get_lower_than(&wtxn, &4404)?;
-    /// assert_eq!(ret, Some((43, ())));
-    ///
-    /// let ret = db.get_lower_than(&wtxn, &43)?;
-    /// assert_eq!(ret, Some((42, ())));
+    /// db.put(&mut wtxn, &68, &120)?;
+    /// db.put(&mut wtxn, &68, &121)?;
+    /// db.put(&mut wtxn, &92, &32)?;
     ///
-    /// let ret = db.get_lower_than(&wtxn, &27)?;
-    /// assert_eq!(ret, None);
+    /// assert_eq!(db.count_duplicates(&wtxn, &68)?, 2);
+    /// assert_eq!(db.count_duplicates(&wtxn, &92)?, 1);
+    /// assert_eq!(db.count_duplicates(&wtxn, &35)?, 0);
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn get_lower_than<'a, 'txn>(
-        &self,
-        txn: &'txn RoTxn,
-        key: &'a KC::EItem,
-    ) -> Result<Option<(KC::DItem, DC::DItem)>>
+    pub fn count_duplicates<'a>(&self, txn: &RoTxn, key: &'a KC::EItem) -> Result<usize>
     where
-        KC: BytesEncode<'a> + BytesDecode<'txn>,
-        DC: BytesDecode<'txn>,
+        KC: BytesEncode<'a>,
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let mut cursor = RoCursor::new(txn, self.dbi)?;
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        cursor.move_on_key_greater_than_or_equal_to(&key_bytes)?;
+        if !self.is_dup_sort(txn)? {
+            return Err(Error::Mdb(MdbError::Incompatible));
+        }
 
-        match cursor.move_on_prev(MoveOperation::NoDup) {
-            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
-                (Ok(key), Ok(data)) => Ok(Some((key, data))),
-                (Err(e), _) | (_, Err(e)) => Err(Error::Decoding(e)),
-            },
-            Ok(None) => Ok(None),
-            Err(e) => Err(e),
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(self.operation(txn, "count_duplicates").encoding(None))?;
+
+        let mut cursor = RoCursor::new(txn, self.dbi)?;
+        if cursor.move_on_key(&key_bytes)? {
+            cursor.count_duplicates()
+        } else {
+            Ok(0)
         }
     }
 
-    /// Retrieves the key/value pair lower than or equal to the given one in this database.
-    ///
-    /// If the database if empty or there is no key lower than or equal to the given one,
-    /// then `None` is returned.
+    /// Returns whether the given key/value pair exists in this database.
     ///
-    /// Comparisons are made by using the bytes representation of the key.
+    /// The database must be [`DUP_SORT`](DatabaseFlags::DUP_SORT),
+    /// an [`MdbError::Incompatible`] error is returned otherwise.
     ///
     /// ```
     /// # use std::fs;
     /// # use std::path::Path;
-    /// # use heed::EnvOpenOptions;
-    /// use heed::Database;
+    /// # use heed::{DatabaseFlags, EnvOpenOptions};
     /// use heed::types::*;
     /// use heed::byteorder::BigEndian;
     ///
@@ -549,69 +735,62 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEU32 = U32<BigEndian>;
+    /// type BEI64 = I64<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db = env.create_database::<BEU32, Unit>(&mut wtxn, Some("get-lt-u32"))?;
+    /// let db = env.database_options()
+    ///     .types::<BEI64, BEI64>()
+    ///     .flags(DatabaseFlags::DUP_SORT)
+    ///     .name("dup-sort")
+    ///     .create(&mut wtxn)?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &27, &())?;
-    /// db.put(&mut wtxn, &42, &())?;
-    /// db.put(&mut wtxn, &43, &())?;
-    ///
-    /// let ret = db.get_lower_than_or_equal_to(&wtxn, &4404)?;
-    /// assert_eq!(ret, Some((43, ())));
-    ///
-    /// let ret = db.get_lower_than_or_equal_to(&wtxn, &43)?;
-    /// assert_eq!(ret, Some((43, ())));
+    /// db.put(&mut wtxn, &68, &120)?;
+    /// db.put(&mut wtxn, &68, &121)?;
+    /// db.put(&mut wtxn, &92, &32)?;
     ///
-    /// let ret = db.get_lower_than_or_equal_to(&wtxn, &26)?;
-    /// assert_eq!(ret, None);
+    /// assert!(db.contains_duplicate(&wtxn, &68, &121)?);
+    /// assert!(!db.contains_duplicate(&wtxn, &68, &32)?);
+    /// assert!(!db.contains_duplicate(&wtxn, &35, &120)?);
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn get_lower_than_or_equal_to<'a, 'txn>(
+    pub fn contains_duplicate<'a>(
         &self,
-        txn: &'txn RoTxn,
+        txn: &RoTxn,
         key: &'a KC::EItem,
-    ) -> Result<Option<(KC::DItem, DC::DItem)>>
+        data: &'a DC::EItem,
+    ) -> Result<bool>
     where
-        KC: BytesEncode<'a> + BytesDecode<'txn>,
-        DC: BytesDecode<'txn>,
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let mut cursor = RoCursor::new(txn, self.dbi)?;
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let result = match cursor.move_on_key_greater_than_or_equal_to(&key_bytes) {
-            Ok(Some((key, data))) if key == &key_bytes[..] => Ok(Some((key, data))),
-            Ok(_) => cursor.move_on_prev(MoveOperation::NoDup),
-            Err(e) => Err(e),
-        };
-
-        match result {
-            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
-                (Ok(key), Ok(data)) => Ok(Some((key, data))),
-                (Err(e), _) | (_, Err(e)) => Err(Error::Decoding(e)),
-            },
-            Ok(None) => Ok(None),
-            Err(e) => Err(e),
+        if !self.is_dup_sort(txn)? {
+            return Err(Error::Mdb(MdbError::Incompatible));
         }
+
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(self.operation(txn, "contains_duplicate").encoding(None))?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data)
+            .map_err(self.operation(txn, "contains_duplicate").encoding(Some(&key_bytes)))?;
+
+        let mut cursor = RoCursor::new(txn, self.dbi)?;
+        cursor.move_on_key_data(&key_bytes, &data_bytes)
     }
 
-    /// Retrieves the key/value pair greater than the given one in this database.
+    /// Returns an iterator over the values of a single key that are in the given range.
     ///
-    /// If the database if empty or there is no key greater than the given one,
-    /// then `None` is returned.
-    ///
-    /// Comparisons are made by using the bytes representation of the key.
+    /// The values are compared with the duplicate comparator `CDUP`. The database must be
+    /// [`DUP_SORT`](DatabaseFlags::DUP_SORT), an [`MdbError::Incompatible`] error is returned
+    /// otherwise. The iterator is empty if the key doesn't exist.
     ///
     /// ```
     /// # use std::fs;
     /// # use std::path::Path;
-    /// # use heed::EnvOpenOptions;
-    /// use heed::Database;
+    /// # use heed::{DatabaseFlags, EnvOpenOptions};
     /// use heed::types::*;
     /// use heed::byteorder::BigEndian;
     ///
@@ -622,68 +801,59 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEU32 = U32<BigEndian>;
+    /// type BEI64 = I64<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db = env.create_database::<BEU32, Unit>(&mut wtxn, Some("get-lt-u32"))?;
+    /// let db = env.database_options()
+    ///     .types::<BEI64, BEI64>()
+    ///     .flags(DatabaseFlags::DUP_SORT)
+    ///     .name("dup-sort")
+    ///     .create(&mut wtxn)?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &27, &())?;
-    /// db.put(&mut wtxn, &42, &())?;
-    /// db.put(&mut wtxn, &43, &())?;
-    ///
-    /// let ret = db.get_greater_than(&wtxn, &0)?;
-    /// assert_eq!(ret, Some((27, ())));
-    ///
-    /// let ret = db.get_greater_than(&wtxn, &42)?;
-    /// assert_eq!(ret, Some((43, ())));
+    /// db.put(&mut wtxn, &68, &120)?;
+    /// db.put(&mut wtxn, &68, &121)?;
+    /// db.put(&mut wtxn, &68, &122)?;
+    /// db.put(&mut wtxn, &68, &123)?;
+    /// db.put(&mut wtxn, &92, &32)?;
     ///
-    /// let ret = db.get_greater_than(&wtxn, &43)?;
-    /// assert_eq!(ret, None);
+    /// let range = 121..=122;
+    /// let mut iter = db.duplicates_range(&wtxn, &68, &range)?;
+    /// assert_eq!(iter.next().transpose()?, Some((68, 121)));
+    /// assert_eq!(iter.next().transpose()?, Some((68, 122)));
+    /// assert_eq!(iter.next().transpose()?, None);
     ///
+    /// drop(iter);
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn get_greater_than<'a, 'txn>(
+    pub fn duplicates_range<'a, 'txn, R>(
         &self,
         txn: &'txn RoTxn,
         key: &'a KC::EItem,
-    ) -> Result<Option<(KC::DItem, DC::DItem)>>
+        range: &'a R,
+    ) -> Result<RoDupRange<'txn, KC, DC, CDUP>>
     where
-        KC: BytesEncode<'a> + BytesDecode<'txn>,
-        DC: BytesDecode<'txn>,
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+        R: RangeBounds<DC::EItem>,
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let mut cursor = RoCursor::new(txn, self.dbi)?;
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let entry = match cursor.move_on_key_greater_than_or_equal_to(&key_bytes)? {
-            Some((key, data)) if key > &key_bytes[..] => Some((key, data)),
-            Some((_key, _data)) => cursor.move_on_next(MoveOperation::NoDup)?,
-            None => None,
-        };
-
-        match entry {
-            Some((key, data)) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
-                (Ok(key), Ok(data)) => Ok(Some((key, data))),
-                (Err(e), _) | (_, Err(e)) => Err(Error::Decoding(e)),
-            },
-            None => Ok(None),
-        }
+        let (key, start_bound, end_bound) =
+            self.encode_duplicates_range(txn, "duplicates_range", key, range)?;
+        RoCursor::new(txn, self.dbi)
+            .map(|cursor| RoDupRange::new(cursor, key, start_bound, end_bound))
     }
 
-    /// Retrieves the key/value pair greater than or equal to the given one in this database.
+    /// Returns a reversed iterator over the values of a single key that are in the given range.
     ///
-    /// If the database if empty or there is no key greater than or equal to the given one,
-    /// then `None` is returned.
-    ///
-    /// Comparisons are made by using the bytes representation of the key.
+    /// For more info, see [`Database::duplicates_range`].
     ///
     /// ```
     /// # use std::fs;
     /// # use std::path::Path;
-    /// # use heed::EnvOpenOptions;
-    /// use heed::Database;
+    /// # use heed::{DatabaseFlags, EnvOpenOptions};
     /// use heed::types::*;
     /// use heed::byteorder::BigEndian;
     ///
@@ -694,62 +864,106 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEU32 = U32<BigEndian>;
+    /// type BEI64 = I64<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db = env.create_database::<BEU32, Unit>(&mut wtxn, Some("get-lt-u32"))?;
+    /// let db = env.database_options()
+    ///     .types::<BEI64, BEI64>()
+    ///     .flags(DatabaseFlags::DUP_SORT)
+    ///     .name("dup-sort")
+    ///     .create(&mut wtxn)?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &27, &())?;
-    /// db.put(&mut wtxn, &42, &())?;
-    /// db.put(&mut wtxn, &43, &())?;
-    ///
-    /// let ret = db.get_greater_than_or_equal_to(&wtxn, &0)?;
-    /// assert_eq!(ret, Some((27, ())));
-    ///
-    /// let ret = db.get_greater_than_or_equal_to(&wtxn, &42)?;
-    /// assert_eq!(ret, Some((42, ())));
+    /// db.put(&mut wtxn, &68, &120)?;
+    /// db.put(&mut wtxn, &68, &121)?;
+    /// db.put(&mut wtxn, &68, &122)?;
+    /// db.put(&mut wtxn, &68, &123)?;
+    /// db.put(&mut wtxn, &92, &32)?;
     ///
-    /// let ret = db.get_greater_than_or_equal_to(&wtxn, &44)?;
-    /// assert_eq!(ret, None);
+    /// let range = 121..;
+    /// let mut iter = db.rev_duplicates_range(&wtxn, &68, &range)?;
+    /// assert_eq!(iter.next().transpose()?, Some((68, 123)));
+    /// assert_eq!(iter.next().transpose()?, Some((68, 122)));
+    /// assert_eq!(iter.next().transpose()?, Some((68, 121)));
+    /// assert_eq!(iter.next().transpose()?, None);
     ///
+    /// drop(iter);
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn get_greater_than_or_equal_to<'a, 'txn>(
+    pub fn rev_duplicates_range<'a, 'txn, R>(
         &self,
         txn: &'txn RoTxn,
         key: &'a KC::EItem,
-    ) -> Result<Option<(KC::DItem, DC::DItem)>>
+        range: &'a R,
+    ) -> Result<RoRevDupRange<'txn, KC, DC, CDUP>>
     where
-        KC: BytesEncode<'a> + BytesDecode<'txn>,
-        DC: BytesDecode<'txn>,
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+        R: RangeBounds<DC::EItem>,
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let mut cursor = RoCursor::new(txn, self.dbi)?;
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        match cursor.move_on_key_greater_than_or_equal_to(&key_bytes) {
-            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
-                (Ok(key), Ok(data)) => Ok(Some((key, data))),
-                (Err(e), _) | (_, Err(e)) => Err(Error::Decoding(e)),
-            },
-            Ok(None) => Ok(None),
-            Err(e) => Err(e),
+        let (key, start_bound, end_bound) =
+            self.encode_duplicates_range(txn, "rev_duplicates_range", key, range)?;
+        RoCursor::new(txn, self.dbi)
+            .map(|cursor| RoRevDupRange::new(cursor, key, start_bound, end_bound))
+    }
+
+    /// Encodes the key and the bounds of the values of a duplicates range.
+    #[allow(clippy::type_complexity)]
+    fn encode_duplicates_range<'a, R>(
+        &self,
+        txn: &RoTxn,
+        operation: &'static str,
+        key: &'a KC::EItem,
+        range: &'a R,
+    ) -> Result<(Vec<u8>, Bound<Vec<u8>>, Bound<Vec<u8>>)>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+        R: RangeBounds<DC::EItem>,
+    {
+        if !self.is_dup_sort(txn)? {
+            return Err(Error::Mdb(MdbError::Incompatible));
         }
+
+        let key_bytes = KC::bytes_encode(key)
+            .map_err(self.operation(txn, operation).encoding(None))?
+            .into_owned();
+
+        let encode_bound = |bound| match bound {
+            Bound::Included(data) => {
+                DC::bytes_encode(data).map(|bytes| Bound::Included(bytes.into_owned()))
+            }
+            Bound::Excluded(data) => {
+                DC::bytes_encode(data).map(|bytes| Bound::Excluded(bytes.into_owned()))
+            }
+            Bound::Unbounded => Ok(Bound::Unbounded),
+        };
+
+        let start_bound = encode_bound(range.start_bound())
+            .map_err(self.operation(txn, operation).encoding(Some(&key_bytes)))?;
+        let end_bound = encode_bound(range.end_bound())
+            .map_err(self.operation(txn, operation).encoding(Some(&key_bytes)))?;
+
+        Ok((key_bytes, start_bound, end_bound))
     }
 
-    /// Retrieves the first key/value pair of this database.
+    /// Returns the values of a single key by pages of contiguous fixed-size values.
     ///
-    /// If the database if empty, then `None` is returned.
+    /// Every item of the iterator is the raw concatenation of up to a page of values, which
+    /// is far cheaper than decoding them one by one. The pages can be decoded in bulk by
+    /// remapping the iterator, e.g. with `PodSlice` when the `bytemuck` feature is enabled.
     ///
-    /// Comparisons are made by using the bytes representation of the key.
+    /// The database must be [`DUP_FIXED`](DatabaseFlags::DUP_FIXED),
+    /// an [`MdbError::Incompatible`] error is returned otherwise.
+    /// Returns `None` if the key doesn't exist.
     ///
     /// ```
     /// # use std::fs;
     /// # use std::path::Path;
-    /// # use heed::EnvOpenOptions;
-    /// use heed::Database;
+    /// # use heed::{DatabaseFlags, EnvOpenOptions};
     /// use heed::types::*;
     /// use heed::byteorder::BigEndian;
     ///
@@ -760,50 +974,70 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEI32 = I32<BigEndian>;
+    /// type BEI64 = I64<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("first-i32"))?;
+    /// let db = env.database_options()
+    ///     .types::<BEI64, BEI64>()
+    ///     .flags(DatabaseFlags::DUP_SORT | DatabaseFlags::DUP_FIXED)
+    ///     .name("dup-fixed")
+    ///     .create(&mut wtxn)?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
-    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    /// db.put_duplicates_fixed(&mut wtxn, &68, &[120, 121, 122])?;
+    /// db.put(&mut wtxn, &92, &32)?;
     ///
-    /// let ret = db.first(&wtxn)?;
-    /// assert_eq!(ret, Some((27, "i-am-twenty-seven")));
+    /// let mut values = Vec::new();
+    /// for page in db.get_duplicates_fixed(&wtxn, &68)?.unwrap() {
+    ///     for bytes in page?.chunks_exact(8) {
+    ///         values.push(i64::from_be_bytes(bytes.try_into()?));
+    ///     }
+    /// }
+    /// assert_eq!(values, [120, 121, 122]);
+    /// assert!(db.get_duplicates_fixed(&wtxn, &35)?.is_none());
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn first<'txn>(&self, txn: &'txn RoTxn) -> Result<Option<(KC::DItem, DC::DItem)>>
+    pub fn get_duplicates_fixed<'a, 'txn>(
+        &self,
+        txn: &'txn RoTxn,
+        key: &'a KC::EItem,
+    ) -> Result<Option<RoDupFixedChunks<'txn, Bytes>>>
     where
-        KC: BytesDecode<'txn>,
-        DC: BytesDecode<'txn>,
+        KC: BytesEncode<'a>,
     {
         assert_eq_env_db_txn!(self, txn);
 
+        if !self.is_dup_fixed(txn)? {
+            return Err(Error::Mdb(MdbError::Incompatible));
+        }
+
+        let key_bytes = KC::bytes_encode(key)
+            .map_err(self.operation(txn, "get_duplicates_fixed").encoding(None))?
+            .into_owned();
+
         let mut cursor = RoCursor::new(txn, self.dbi)?;
-        match cursor.move_on_first(MoveOperation::Any) {
-            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
-                (Ok(key), Ok(data)) => Ok(Some((key, data))),
-                (Err(e), _) | (_, Err(e)) => Err(Error::Decoding(e)),
-            },
-            Ok(None) => Ok(None),
-            Err(e) => Err(e),
+        if cursor.move_on_key(&key_bytes)? {
+            Ok(Some(RoDupFixedChunks::new(cursor, key_bytes)))
+        } else {
+            Ok(None)
         }
     }
 
-    /// Retrieves the last key/value pair of this database.
+    /// Inserts many values under a single key in one call.
     ///
-    /// If the database if empty, then `None` is returned.
+    /// The values are encoded one after the other and written by LMDB in bulk. Values
+    /// that already exist are left untouched. All the values must be encoded to the same
+    /// size, an [`MdbError::BadValSize`] error is returned otherwise.
     ///
-    /// Comparisons are made by using the bytes representation of the key.
+    /// The database must be [`DUP_FIXED`](DatabaseFlags::DUP_FIXED),
+    /// an [`MdbError::Incompatible`] error is returned otherwise.
     ///
     /// ```
     /// # use std::fs;
     /// # use std::path::Path;
-    /// # use heed::EnvOpenOptions;
-    /// use heed::Database;
+    /// # use heed::{DatabaseFlags, EnvOpenOptions};
     /// use heed::types::*;
     /// use heed::byteorder::BigEndian;
     ///
@@ -814,40 +1048,76 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEI32 = I32<BigEndian>;
+    /// type BEU32 = U32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("last-i32"))?;
+    /// let db = env.database_options()
+    ///     .types::<Str, BEU32>()
+    ///     .flags(DatabaseFlags::DUP_SORT | DatabaseFlags::DUP_FIXED)
+    ///     .name("dup-fixed")
+    ///     .create(&mut wtxn)?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
-    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    /// let values: Vec<u32> = (0..1000).rev().collect();
+    /// db.put_duplicates_fixed(&mut wtxn, "numbers", &values)?;
     ///
-    /// let ret = db.last(&wtxn)?;
-    /// assert_eq!(ret, Some((42, "i-am-forty-two")));
+    /// let mut iter = db.get_duplicates(&wtxn, "numbers")?.unwrap();
+    /// assert_eq!(iter.next().transpose()?, Some(("numbers", 0)));
+    /// assert_eq!(iter.last().transpose()?, Some(("numbers", 999)));
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn last<'txn>(&self, txn: &'txn RoTxn) -> Result<Option<(KC::DItem, DC::DItem)>>
+    pub fn put_duplicates_fixed<'a>(
+        &self,
+        txn: &mut RwTxn,
+        key: &'a KC::EItem,
+        data: &'a [DC::EItem],
+    ) -> Result<()>
     where
-        KC: BytesDecode<'txn>,
-        DC: BytesDecode<'txn>,
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+        DC::EItem: Sized,
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let mut cursor = RoCursor::new(txn, self.dbi)?;
-        match cursor.move_on_last(MoveOperation::Any) {
-            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
-                (Ok(key), Ok(data)) => Ok(Some((key, data))),
-                (Err(e), _) | (_, Err(e)) => Err(Error::Decoding(e)),
-            },
-            Ok(None) => Ok(None),
-            Err(e) => Err(e),
+        if !self.is_dup_fixed(txn)? {
+            return Err(Error::Mdb(MdbError::Incompatible));
+        }
+
+        let scratch = &mut txn.scratch;
+        scratch.recycle();
+
+        let context = self.operation(&txn.txn, "put_duplicates_fixed");
+
+        encode_into::<KC>(key, &mut scratch.key).map_err(context.encoding(None))?;
+
+        let mut data_size = None;
+        for item in data {
+            let len = scratch.data.len();
+            encode_into::<DC>(item, &mut scratch.data)
+                .map_err(context.encoding(Some(&scratch.key)))?;
+            let size = scratch.data.len() - len;
+            if size == 0 || *data_size.get_or_insert(size) != size {
+                return Err(context.error(Error::Mdb(MdbError::BadValSize), Some(&scratch.key)));
+            }
         }
+
+        let Some(data_size) = data_size else { return Ok(()) };
+
+        let scratch = &txn.scratch;
+        let mut cursor = RwCursor::new(txn, self.dbi)?;
+        unsafe { cursor.put_multiple(PutFlags::empty(), &scratch.key, &scratch.data, data_size)? };
+
+        Ok(())
     }
 
-    /// Returns the number of elements in this database.
+    /// Retrieves the key/value pair lower than the given one in this database.
+    ///
+    /// If the database if empty or there is no key lower than the given one,
+    /// then `None` is returned.
+    ///
+    /// Comparisons are made by using the bytes representation of the key.
     ///
     /// ```
     /// # use std::fs;
@@ -864,33 +1134,62 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEI32 = I32<BigEndian>;
+    /// type BEU32 = U32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    /// let db = env.create_database::<BEU32, Unit>(&mut wtxn, Some("get-lt-u32"))?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
-    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
-    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
-    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
+    /// db.put(&mut wtxn, &27, &())?;
+    /// db.put(&mut wtxn, &42, &())?;
+    /// db.put(&mut wtxn, &43, &())?;
     ///
-    /// let ret = db.len(&wtxn)?;
-    /// assert_eq!(ret, 4);
+    /// let ret = db.get_lower_than(&wtxn, &4404)?;
+    /// assert_eq!(ret, Some((43, ())));
     ///
-    /// db.delete(&mut wtxn, &27)?;
+    /// let ret = db.get_lower_than(&wtxn, &43)?;
+    /// assert_eq!(ret, Some((42, ())));
     ///
-    /// let ret = db.len(&wtxn)?;
-    /// assert_eq!(ret, 3);
+    /// let ret = db.get_lower_than(&wtxn, &27)?;
+    /// assert_eq!(ret, None);
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn len(&self, txn: &RoTxn) -> Result<u64> {
-        self.stat(txn).map(|stat| stat.entries as u64)
+    pub fn get_lower_than<'a, 'txn>(
+        &self,
+        txn: &'txn RoTxn,
+        key: &'a KC::EItem,
+    ) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesEncode<'a> + BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let mut cursor = RoCursor::new(txn, self.dbi)?;
+        let key_bytes: Cow<[u8]> =
+            KC::bytes_encode(key).map_err(self.operation(txn, "get_lower_than").encoding(None))?;
+        cursor.move_on_key_greater_than_or_equal_to(&key_bytes)?;
+
+        match cursor.move_on_prev(MoveOperation::NoDup) {
+            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                (Ok(key), Ok(data)) => Ok(Some((key, data))),
+                (Err(e), _) | (_, Err(e)) => {
+                    Err(self.operation(txn, "get_lower_than").error(Error::Decoding(e), Some(key)))
+                }
+            },
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
-    /// Returns `true` if and only if this database is empty.
+    /// Retrieves the key/value pair lower than or equal to the given one in this database.
+    ///
+    /// If the database if empty or there is no key lower than or equal to the given one,
+    /// then `None` is returned.
+    ///
+    /// Comparisons are made by using the bytes representation of the key.
     ///
     /// ```
     /// # use std::fs;
@@ -907,37 +1206,70 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEI32 = I32<BigEndian>;
+    /// type BEU32 = U32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    /// let db = env.create_database::<BEU32, Unit>(&mut wtxn, Some("get-lt-u32"))?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
-    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
-    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
-    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
+    /// db.put(&mut wtxn, &27, &())?;
+    /// db.put(&mut wtxn, &42, &())?;
+    /// db.put(&mut wtxn, &43, &())?;
     ///
-    /// let ret = db.is_empty(&wtxn)?;
-    /// assert_eq!(ret, false);
+    /// let ret = db.get_lower_than_or_equal_to(&wtxn, &4404)?;
+    /// assert_eq!(ret, Some((43, ())));
     ///
-    /// db.clear(&mut wtxn)?;
+    /// let ret = db.get_lower_than_or_equal_to(&wtxn, &43)?;
+    /// assert_eq!(ret, Some((43, ())));
     ///
-    /// let ret = db.is_empty(&wtxn)?;
-    /// assert_eq!(ret, true);
+    /// let ret = db.get_lower_than_or_equal_to(&wtxn, &26)?;
+    /// assert_eq!(ret, None);
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn is_empty(&self, txn: &RoTxn) -> Result<bool> {
-        self.len(txn).map(|l| l == 0)
-    }
-
-    /// Returns some statistics for this database.
-    ///
-    /// ```
-    /// # use std::fs;
-    /// # use std::path::Path;
+    pub fn get_lower_than_or_equal_to<'a, 'txn>(
+        &self,
+        txn: &'txn RoTxn,
+        key: &'a KC::EItem,
+    ) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesEncode<'a> + BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let mut cursor = RoCursor::new(txn, self.dbi)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(self.operation(txn, "get_lower_than_or_equal_to").encoding(None))?;
+        let result = match cursor.move_on_key_greater_than_or_equal_to(&key_bytes) {
+            Ok(Some((key, data))) if key == &key_bytes[..] => Ok(Some((key, data))),
+            Ok(_) => cursor.move_on_prev(MoveOperation::NoDup),
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                (Ok(key), Ok(data)) => Ok(Some((key, data))),
+                (Err(e), _) | (_, Err(e)) => Err(self
+                    .operation(txn, "get_lower_than_or_equal_to")
+                    .error(Error::Decoding(e), Some(key))),
+            },
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Retrieves the key/value pair greater than the given one in this database.
+    ///
+    /// If the database if empty or there is no key greater than the given one,
+    /// then `None` is returned.
+    ///
+    /// Comparisons are made by using the bytes representation of the key.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
     /// # use heed::EnvOpenOptions;
     /// use heed::Database;
     /// use heed::types::*;
@@ -950,56 +1282,65 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEI32 = I32<BigEndian>;
+    /// type BEU32 = U32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    /// let db = env.create_database::<BEU32, Unit>(&mut wtxn, Some("get-lt-u32"))?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
-    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
-    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
-    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
+    /// db.put(&mut wtxn, &27, &())?;
+    /// db.put(&mut wtxn, &42, &())?;
+    /// db.put(&mut wtxn, &43, &())?;
     ///
-    /// let stat = db.stat(&wtxn)?;
-    /// assert_eq!(stat.depth, 1);
-    /// assert_eq!(stat.branch_pages, 0);
-    /// assert_eq!(stat.leaf_pages, 1);
-    /// assert_eq!(stat.overflow_pages, 0);
-    /// assert_eq!(stat.entries, 4);
+    /// let ret = db.get_greater_than(&wtxn, &0)?;
+    /// assert_eq!(ret, Some((27, ())));
+    ///
+    /// let ret = db.get_greater_than(&wtxn, &42)?;
+    /// assert_eq!(ret, Some((43, ())));
+    ///
+    /// let ret = db.get_greater_than(&wtxn, &43)?;
+    /// assert_eq!(ret, None);
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn stat(&self, txn: &RoTxn) -> Result<DatabaseStat> {
+    pub fn get_greater_than<'a, 'txn>(
+        &self,
+        txn: &'txn RoTxn,
+        key: &'a KC::EItem,
+    ) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesEncode<'a> + BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
         assert_eq_env_db_txn!(self, txn);
 
-        let mut db_stat = mem::MaybeUninit::uninit();
-        let result = unsafe {
-            mdb_result(ffi::mdb_stat(txn.txn_ptr().as_mut(), self.dbi, db_stat.as_mut_ptr()))
+        let mut cursor = RoCursor::new(txn, self.dbi)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(self.operation(txn, "get_greater_than").encoding(None))?;
+        let entry = match cursor.move_on_key_greater_than_or_equal_to(&key_bytes)? {
+            Some((key, data)) if key > &key_bytes[..] => Some((key, data)),
+            Some((_key, _data)) => cursor.move_on_next(MoveOperation::NoDup)?,
+            None => None,
         };
 
-        match result {
-            Ok(()) => {
-                let stats = unsafe { db_stat.assume_init() };
-                Ok(DatabaseStat {
-                    page_size: stats.ms_psize,
-                    depth: stats.ms_depth,
-                    branch_pages: stats.ms_branch_pages,
-                    leaf_pages: stats.ms_leaf_pages,
-                    overflow_pages: stats.ms_overflow_pages,
-                    entries: stats.ms_entries,
-                })
-            }
-            Err(e) => Err(e.into()),
+        match entry {
+            Some((key, data)) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                (Ok(key), Ok(data)) => Ok(Some((key, data))),
+                (Err(e), _) | (_, Err(e)) => Err(self
+                    .operation(txn, "get_greater_than")
+                    .error(Error::Decoding(e), Some(key))),
+            },
+            None => Ok(None),
         }
     }
 
-    /// Return an ordered iterator of all key-value pairs in this database.
+    /// Retrieves the key/value pair greater than or equal to the given one in this database.
     ///
-    /// You can make this iterator `Send`able between threads by opening
-    /// the environment with the [`EnvOpenOptions::read_txn_without_tls`]
-    /// method.
+    /// If the database if empty or there is no key greater than or equal to the given one,
+    /// then `None` is returned.
+    ///
+    /// Comparisons are made by using the bytes representation of the key.
     ///
     /// ```
     /// # use std::fs;
@@ -1016,32 +1357,59 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEI32 = I32<BigEndian>;
+    /// type BEU32 = U32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    /// let db = env.create_database::<BEU32, Unit>(&mut wtxn, Some("get-lt-u32"))?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
-    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
-    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
+    /// db.put(&mut wtxn, &27, &())?;
+    /// db.put(&mut wtxn, &42, &())?;
+    /// db.put(&mut wtxn, &43, &())?;
     ///
-    /// let mut iter = db.iter(&wtxn)?;
-    /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
-    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-twenty-seven")));
-    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-forty-two")));
-    /// assert_eq!(iter.next().transpose()?, None);
+    /// let ret = db.get_greater_than_or_equal_to(&wtxn, &0)?;
+    /// assert_eq!(ret, Some((27, ())));
+    ///
+    /// let ret = db.get_greater_than_or_equal_to(&wtxn, &42)?;
+    /// assert_eq!(ret, Some((42, ())));
+    ///
+    /// let ret = db.get_greater_than_or_equal_to(&wtxn, &44)?;
+    /// assert_eq!(ret, None);
     ///
-    /// drop(iter);
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn iter<'txn>(&self, txn: &'txn RoTxn) -> Result<RoIter<'txn, KC, DC>> {
+    pub fn get_greater_than_or_equal_to<'a, 'txn>(
+        &self,
+        txn: &'txn RoTxn,
+        key: &'a KC::EItem,
+    ) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesEncode<'a> + BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
         assert_eq_env_db_txn!(self, txn);
-        RoCursor::new(txn, self.dbi).map(|cursor| RoIter::new(cursor))
+
+        let mut cursor = RoCursor::new(txn, self.dbi)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(self.operation(txn, "get_greater_than_or_equal_to").encoding(None))?;
+        match cursor.move_on_key_greater_than_or_equal_to(&key_bytes) {
+            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                (Ok(key), Ok(data)) => Ok(Some((key, data))),
+                (Err(e), _) | (_, Err(e)) => Err(self
+                    .operation(txn, "get_greater_than_or_equal_to")
+                    .error(Error::Decoding(e), Some(key))),
+            },
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
-    /// Return a mutable ordered iterator of all key-value pairs in this database.
+    /// Retrieves the first key/value pair of this database.
+    ///
+    /// If the database if empty, then `None` is returned.
+    ///
+    /// Comparisons are made by using the bytes representation of the key.
     ///
     /// ```
     /// # use std::fs;
@@ -1061,47 +1429,43 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// type BEI32 = I32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("first-i32"))?;
     ///
     /// # db.clear(&mut wtxn)?;
     /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
     /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
-    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
     ///
-    /// let mut iter = db.iter_mut(&mut wtxn)?;
-    /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
-    /// let ret = unsafe { iter.del_current()? };
-    /// assert!(ret);
-    ///
-    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-twenty-seven")));
-    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-forty-two")));
-    /// let ret = unsafe { iter.put_current(&42, "i-am-the-new-forty-two")? };
-    /// assert!(ret);
-    ///
-    /// assert_eq!(iter.next().transpose()?, None);
-    ///
-    /// drop(iter);
-    ///
-    /// let ret = db.get(&wtxn, &13)?;
-    /// assert_eq!(ret, None);
-    ///
-    /// let ret = db.get(&wtxn, &42)?;
-    /// assert_eq!(ret, Some("i-am-the-new-forty-two"));
+    /// let ret = db.first(&wtxn)?;
+    /// assert_eq!(ret, Some((27, "i-am-twenty-seven")));
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn iter_mut<'txn>(&self, txn: &'txn mut RwTxn) -> Result<RwIter<'txn, KC, DC>> {
+    pub fn first<'txn>(&self, txn: &'txn RoTxn) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
         assert_eq_env_db_txn!(self, txn);
 
-        RwCursor::new(txn, self.dbi).map(|cursor| RwIter::new(cursor))
+        let mut cursor = RoCursor::new(txn, self.dbi)?;
+        match cursor.move_on_first(MoveOperation::Any) {
+            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                (Ok(key), Ok(data)) => Ok(Some((key, data))),
+                (Err(e), _) | (_, Err(e)) => {
+                    Err(self.operation(txn, "first").error(Error::Decoding(e), Some(key)))
+                }
+            },
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
-    /// Return a reverse ordered iterator of all key-value pairs in this database.
+    /// Retrieves the last key/value pair of this database.
     ///
-    /// You can make this iterator `Send`able between threads by opening
-    /// the environment with the [`EnvOpenOptions::read_txn_without_tls`]
-    /// method.
+    /// If the database if empty, then `None` is returned.
+    ///
+    /// Comparisons are made by using the bytes representation of the key.
     ///
     /// ```
     /// # use std::fs;
@@ -1121,31 +1485,39 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// type BEI32 = I32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("last-i32"))?;
     ///
     /// # db.clear(&mut wtxn)?;
     /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
     /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
-    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
     ///
-    /// let mut iter = db.rev_iter(&wtxn)?;
-    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-forty-two")));
-    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-twenty-seven")));
-    /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
-    /// assert_eq!(iter.next().transpose()?, None);
+    /// let ret = db.last(&wtxn)?;
+    /// assert_eq!(ret, Some((42, "i-am-forty-two")));
     ///
-    /// drop(iter);
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn rev_iter<'txn>(&self, txn: &'txn RoTxn) -> Result<RoRevIter<'txn, KC, DC>> {
+    pub fn last<'txn>(&self, txn: &'txn RoTxn) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
         assert_eq_env_db_txn!(self, txn);
 
-        RoCursor::new(txn, self.dbi).map(|cursor| RoRevIter::new(cursor))
+        let mut cursor = RoCursor::new(txn, self.dbi)?;
+        match cursor.move_on_last(MoveOperation::Any) {
+            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                (Ok(key), Ok(data)) => Ok(Some((key, data))),
+                (Err(e), _) | (_, Err(e)) => {
+                    Err(self.operation(txn, "last").error(Error::Decoding(e), Some(key)))
+                }
+            },
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
-    /// Return a mutable reverse ordered iterator of all key-value\
-    /// pairs in this database.
+    /// Returns the number of elements in this database.
     ///
     /// ```
     /// # use std::fs;
@@ -1171,43 +1543,24 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
     /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
     /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
+    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
     ///
-    /// let mut iter = db.rev_iter_mut(&mut wtxn)?;
-    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-forty-two")));
-    /// let ret = unsafe { iter.del_current()? };
-    /// assert!(ret);
-    ///
-    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-twenty-seven")));
-    /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
-    /// let ret = unsafe { iter.put_current(&13, "i-am-the-new-thirteen")? };
-    /// assert!(ret);
-    ///
-    /// assert_eq!(iter.next().transpose()?, None);
+    /// let ret = db.len(&wtxn)?;
+    /// assert_eq!(ret, 4);
     ///
-    /// drop(iter);
+    /// db.delete(&mut wtxn, &27)?;
     ///
-    /// let ret = db.get(&wtxn, &42)?;
-    /// assert_eq!(ret, None);
-    ///
-    /// let ret = db.get(&wtxn, &13)?;
-    /// assert_eq!(ret, Some("i-am-the-new-thirteen"));
+    /// let ret = db.len(&wtxn)?;
+    /// assert_eq!(ret, 3);
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn rev_iter_mut<'txn>(&self, txn: &'txn mut RwTxn) -> Result<RwRevIter<'txn, KC, DC>> {
-        assert_eq_env_db_txn!(self, txn);
-
-        RwCursor::new(txn, self.dbi).map(|cursor| RwRevIter::new(cursor))
+    pub fn len(&self, txn: &RoTxn) -> Result<u64> {
+        self.stat(txn).map(|stat| stat.entries as u64)
     }
 
-    /// Return an ordered iterator of a range of key-value pairs in this database.
-    ///
-    /// Comparisons are made by using the comparator `C`.
-    ///
-    /// You can make this iterator `Send`able between threads by opening
-    /// the environment with the [`EnvOpenOptions::read_txn_without_tls`]
-    /// method.
+    /// Returns `true` if and only if this database is empty.
     ///
     /// ```
     /// # use std::fs;
@@ -1235,25 +1588,26 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
     /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
     ///
-    /// let range = 27..=42;
-    /// let mut iter = db.range(&wtxn, &range)?;
-    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-twenty-seven")));
-    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-forty-two")));
-    /// assert_eq!(iter.next().transpose()?, None);
+    /// let ret = db.is_empty(&wtxn)?;
+    /// assert_eq!(ret, false);
+    ///
+    /// db.clear(&mut wtxn)?;
+    ///
+    /// let ret = db.is_empty(&wtxn)?;
+    /// assert_eq!(ret, true);
     ///
-    /// drop(iter);
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    ///
-    /// It can be complex to work with ranges of slices and using
-    /// the `..` or `..=` range syntax is not the best way to deal
-    /// with that. We highly recommend using the [`Bound`](std::ops::Bound) enum for that.
+    pub fn is_empty(&self, txn: &RoTxn) -> Result<bool> {
+        self.len(txn).map(|l| l == 0)
+    }
+
+    /// Returns some statistics for this database.
     ///
     /// ```
     /// # use std::fs;
     /// # use std::path::Path;
-    /// use std::ops::Bound;
     /// # use heed::EnvOpenOptions;
     /// use heed::Database;
     /// use heed::types::*;
@@ -1263,72 +1617,64 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// # let dir = tempfile::tempdir()?;
     /// # let env = unsafe { EnvOpenOptions::new()
     /// #     .map_size(10 * 1024 * 1024) // 10MB
-    /// #     .max_dbs(30)
+    /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
+    /// type BEI32 = I32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<Bytes, Unit> = env.create_database(&mut wtxn, None)?;
-    ///
-    /// // make sure to create slices and not ref array
-    /// // by using the [..] syntax.
-    /// let start = &[0, 0, 0][..];
-    /// let end = &[9, 0, 0][..];
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
     ///
-    /// // equivalent to start..end
-    /// let range = (Bound::Included(start), Bound::Excluded(end));
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
+    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
+    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
     ///
-    /// let iter = db.range(&mut wtxn, &range)?;
+    /// let stat = db.stat(&wtxn)?;
+    /// assert_eq!(stat.depth, 1);
+    /// assert_eq!(stat.branch_pages, 0);
+    /// assert_eq!(stat.leaf_pages, 1);
+    /// assert_eq!(stat.overflow_pages, 0);
+    /// assert_eq!(stat.entries, 4);
     ///
-    /// drop(iter);
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn range<'a, 'txn, R>(
-        &self,
-        txn: &'txn RoTxn,
-        range: &'a R,
-    ) -> Result<RoRange<'txn, KC, DC, C>>
-    where
-        KC: BytesEncode<'a>,
-        R: RangeBounds<KC::EItem>,
-    {
+    pub fn stat(&self, txn: &RoTxn) -> Result<DatabaseStat> {
         assert_eq_env_db_txn!(self, txn);
 
-        let start_bound = match range.start_bound() {
-            Bound::Included(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Included(bytes.into_owned())
-            }
-            Bound::Excluded(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Excluded(bytes.into_owned())
-            }
-            Bound::Unbounded => Bound::Unbounded,
+        let mut db_stat = mem::MaybeUninit::uninit();
+        let result = unsafe {
+            mdb_result(ffi::mdb_stat(txn.txn_ptr().as_mut(), self.dbi, db_stat.as_mut_ptr()))
         };
 
-        let end_bound = match range.end_bound() {
-            Bound::Included(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Included(bytes.into_owned())
-            }
-            Bound::Excluded(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Excluded(bytes.into_owned())
+        match result {
+            Ok(()) => {
+                let stats = unsafe { db_stat.assume_init() };
+                Ok(DatabaseStat {
+                    page_size: stats.ms_psize,
+                    depth: stats.ms_depth,
+                    branch_pages: stats.ms_branch_pages,
+                    leaf_pages: stats.ms_leaf_pages,
+                    overflow_pages: stats.ms_overflow_pages,
+                    entries: stats.ms_entries,
+                })
             }
-            Bound::Unbounded => Bound::Unbounded,
-        };
-
-        RoCursor::new(txn, self.dbi).map(|cursor| RoRange::new(cursor, start_bound, end_bound))
+            Err(e) => Err(e.into()),
+        }
     }
 
-    /// Return a mutable ordered iterator of a range of key-value pairs in this database.
+    /// Splits the keys of this database into at most `n` contiguous ranges,
+    /// to be scanned independently, in parallel for example.
     ///
-    /// Comparisons are made by using the comparator `C`.
+    /// The ranges are ordered, don't overlap and cover every key. The split keys are sampled
+    /// by interpolating between the first and the last keys and seeking to the following key.
+    /// The ranges are therefore only balanced when the keys are evenly distributed over
+    /// the key space, under a lexicographic key comparator. There are fewer ranges than
+    /// requested when there are not enough keys to split on.
     ///
     /// ```
-    /// # use std::fs;
-    /// # use std::path::Path;
     /// # use heed::EnvOpenOptions;
     /// use heed::Database;
     /// use heed::types::*;
@@ -1341,89 +1687,78 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEI32 = I32<BigEndian>;
+    /// type BEU32 = U32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    /// let db: Database<BEU32, Unit> = env.create_database(&mut wtxn, Some("split-ranges"))?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
-    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
-    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
-    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
-    ///
-    /// let range = 27..=42;
-    /// let mut range = db.range_mut(&mut wtxn, &range)?;
-    /// assert_eq!(range.next().transpose()?, Some((27, "i-am-twenty-seven")));
-    /// let ret = unsafe { range.del_current()? };
-    /// assert!(ret);
-    /// assert_eq!(range.next().transpose()?, Some((42, "i-am-forty-two")));
-    /// let ret = unsafe { range.put_current(&42, "i-am-the-new-forty-two")? };
-    /// assert!(ret);
-    ///
-    /// assert_eq!(range.next().transpose()?, None);
-    /// drop(range);
+    /// for i in 0..1000 {
+    ///     db.put(&mut wtxn, &i, &())?;
+    /// }
     ///
+    /// let ranges = db.split_ranges(&wtxn, 4)?;
+    /// assert_eq!(ranges.len(), 4);
     ///
-    /// let mut iter = db.iter(&wtxn)?;
-    /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
-    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-the-new-forty-two")));
-    /// assert_eq!(iter.next().transpose()?, Some((521, "i-am-five-hundred-and-twenty-one")));
-    /// assert_eq!(iter.next().transpose()?, None);
+    /// let bytes_db = db.remap_key_type::<Bytes>();
+    /// let mut count = 0;
+    /// for range in &ranges {
+    ///     count += bytes_db.range(&wtxn, range)?.count();
+    /// }
+    /// assert_eq!(count, 1000);
     ///
-    /// drop(iter);
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn range_mut<'a, 'txn, R>(
-        &self,
-        txn: &'txn mut RwTxn,
-        range: &'a R,
-    ) -> Result<RwRange<'txn, KC, DC, C>>
+    pub fn split_ranges(&self, txn: &RoTxn, n: usize) -> Result<Vec<KeyRange>>
     where
-        KC: BytesEncode<'a>,
-        R: RangeBounds<KC::EItem>,
+        C: Comparator,
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let start_bound = match range.start_bound() {
-            Bound::Included(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Included(bytes.into_owned())
-            }
-            Bound::Excluded(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Excluded(bytes.into_owned())
-            }
-            Bound::Unbounded => Bound::Unbounded,
+        let db = self.remap_types::<Bytes, DecodeIgnore>();
+        let (first, last) = match (db.first(txn)?, db.last(txn)?) {
+            (Some((first, ())), Some((last, ()))) => (first, last),
+            _ => return Ok(vec![KeyRange::full()]),
         };
 
-        let end_bound = match range.end_bound() {
-            Bound::Included(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Included(bytes.into_owned())
-            }
-            Bound::Excluded(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Excluded(bytes.into_owned())
+        let n = n.min(self.stat(txn)?.entries);
+        let mut split_keys = Vec::with_capacity(n);
+        for key in interpolate_keys(first, last, n) {
+            if let Some((key, ())) = db.get_greater_than_or_equal_to(txn, &key)? {
+                split_keys.push(key.to_vec());
             }
-            Bound::Unbounded => Bound::Unbounded,
-        };
+        }
 
-        RwCursor::new(txn, self.dbi).map(|cursor| RwRange::new(cursor, start_bound, end_bound))
+        // Custom comparators may not order the sampled keys lexicographically.
+        split_keys.sort_by(|a, b| C::compare(a, b));
+        split_keys.dedup();
+        split_keys.retain(|key| key != first);
+
+        let mut ranges = Vec::with_capacity(split_keys.len() + 1);
+        let mut start = Bound::Unbounded;
+        for key in split_keys {
+            ranges.push(KeyRange { start, end: Bound::Excluded(key.clone()) });
+            start = Bound::Included(key);
+        }
+        ranges.push(KeyRange { start, end: Bound::Unbounded });
+
+        Ok(ranges)
     }
 
-    /// Return a reverse ordered iterator of a range of key-value pairs in this database.
+    /// Calls `f` on every key-value pair of this database, scanning
+    /// [at most `n` ranges](Self::split_ranges) in parallel on the rayon thread pool.
     ///
-    /// Comparisons are made by using the comparator `C`.
+    /// Every range is scanned in its own read transaction and all of them read
+    /// the same snapshot of the environment. The pairs of a range are visited in order
+    /// but the ranges are visited concurrently. The first error stops the scan and is returned.
     ///
-    /// You can make this iterator `Send`able between threads by opening
-    /// the environment with the [`EnvOpenOptions::read_txn_without_tls`]
-    /// method.
+    /// Fewer ranges are scanned when the environment runs out of reader slots, and a single
+    /// one when writes keep being committed while the read transactions are opened.
     ///
     /// ```
-    /// # use std::fs;
-    /// # use std::path::Path;
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    ///
     /// # use heed::EnvOpenOptions;
     /// use heed::Database;
     /// use heed::types::*;
@@ -1432,72 +1767,94 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// # let dir = tempfile::tempdir()?;
     /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .read_txn_without_tls()
     /// #     .map_size(10 * 1024 * 1024) // 10MB
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEI32 = I32<BigEndian>;
+    /// type BEU64 = U64<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    /// let db: Database<BEU64, BEU64> = env.create_database(&mut wtxn, Some("par-for-each"))?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
-    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
-    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
-    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
-    ///
-    /// let range = 27..=43;
-    /// let mut iter = db.rev_range(&wtxn, &range)?;
-    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-forty-two")));
-    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-twenty-seven")));
-    /// assert_eq!(iter.next().transpose()?, None);
-    ///
-    /// drop(iter);
+    /// for i in 0..1000 {
+    ///     db.put(&mut wtxn, &i, &(i * 2))?;
+    /// }
     /// wtxn.commit()?;
+    ///
+    /// let sum = AtomicU64::new(0);
+    /// db.par_for_each(&env, 8, |_key, value| -> heed::Result<()> {
+    ///     sum.fetch_add(value, Ordering::Relaxed);
+    ///     Ok(())
+    /// })?;
+    /// assert_eq!(sum.into_inner(), 999 * 1000);
     /// # Ok(()) }
     /// ```
-    pub fn rev_range<'a, 'txn, R>(
+    #[cfg(feature = "rayon")]
+    pub fn par_for_each<E, F>(
         &self,
-        txn: &'txn RoTxn,
-        range: &'a R,
-    ) -> Result<RoRevRange<'txn, KC, DC, C>>
+        env: &Env<WithoutTls>,
+        n: usize,
+        f: F,
+    ) -> std::result::Result<(), E>
     where
-        KC: BytesEncode<'a>,
-        R: RangeBounds<KC::EItem>,
+        KC: for<'txn> BytesDecode<'txn> + Sync,
+        DC: for<'txn> BytesDecode<'txn> + Sync,
+        C: Comparator + Sync,
+        CDUP: Sync,
+        E: From<Error> + Send,
+        F: for<'txn> Fn(
+                <KC as BytesDecode<'txn>>::DItem,
+                <DC as BytesDecode<'txn>>::DItem,
+            ) -> std::result::Result<(), E>
+            + Sync,
     {
-        assert_eq_env_db_txn!(self, txn);
-
-        let start_bound = match range.start_bound() {
-            Bound::Included(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Included(bytes.into_owned())
-            }
-            Bound::Excluded(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Excluded(bytes.into_owned())
+        use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+        /// The number of times the transactions are opened again when a write is committed
+        /// while they are opened, before falling back to a single transaction.
+        const ATTEMPTS: usize = 3;
+
+        let (txns, ranges) = 'open: {
+            for _ in 0..ATTEMPTS {
+                let txn = env.read_txn()?;
+                let mut ranges = self.split_ranges(&txn, n)?;
+                let mut txns = vec![txn];
+                while txns.len() < ranges.len() {
+                    match env.read_txn() {
+                        Ok(txn) => txns.push(txn),
+                        // Without reader slots left, the last transaction scans the remaining keys.
+                        Err(Error::Mdb(MdbError::ReadersFull)) => {
+                            ranges.truncate(txns.len());
+                            ranges.last_mut().unwrap().end = Bound::Unbounded;
+                        }
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+                // A write may have been committed while the transactions were opened.
+                if txns.iter().all(|txn| txn.id() == txns[0].id()) {
+                    break 'open (txns, ranges);
+                }
             }
-            Bound::Unbounded => Bound::Unbounded,
+            (vec![env.read_txn()?], vec![KeyRange::full()])
         };
 
-        let end_bound = match range.end_bound() {
-            Bound::Included(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Included(bytes.into_owned())
-            }
-            Bound::Excluded(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Excluded(bytes.into_owned())
+        let db = self.remap_key_type::<Bytes>();
+        txns.into_par_iter().zip(ranges).try_for_each(|(txn, range)| {
+            for result in db.range(&txn, &range)?.remap_key_type::<KC>() {
+                let (key, data) = result?;
+                f(key, data)?;
             }
-            Bound::Unbounded => Bound::Unbounded,
-        };
-
-        RoCursor::new(txn, self.dbi).map(|cursor| RoRevRange::new(cursor, start_bound, end_bound))
+            Ok(())
+        })
     }
 
-    /// Return a mutable reverse ordered iterator of a range of key-value pairs in this database.
+    /// Return an ordered iterator of all key-value pairs in this database.
     ///
-    /// Comparisons are made by using the comparator `C`.
+    /// You can make this iterator `Send`able between threads by opening
+    /// the environment with the [`EnvOpenOptions::read_txn_without_tls`]
+    /// method.
     ///
     /// ```
     /// # use std::fs;
@@ -1523,77 +1880,23 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
     /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
     /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
-    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
-    ///
-    /// let range = 27..=42;
-    /// let mut range = db.rev_range_mut(&mut wtxn, &range)?;
-    /// assert_eq!(range.next().transpose()?, Some((42, "i-am-forty-two")));
-    /// let ret = unsafe { range.del_current()? };
-    /// assert!(ret);
-    /// assert_eq!(range.next().transpose()?, Some((27, "i-am-twenty-seven")));
-    /// let ret = unsafe { range.put_current(&27, "i-am-the-new-twenty-seven")? };
-    /// assert!(ret);
-    ///
-    /// assert_eq!(range.next().transpose()?, None);
-    /// drop(range);
-    ///
     ///
     /// let mut iter = db.iter(&wtxn)?;
     /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
-    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-the-new-twenty-seven")));
-    /// assert_eq!(iter.next().transpose()?, Some((521, "i-am-five-hundred-and-twenty-one")));
+    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-twenty-seven")));
+    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-forty-two")));
     /// assert_eq!(iter.next().transpose()?, None);
     ///
     /// drop(iter);
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn rev_range_mut<'a, 'txn, R>(
-        &self,
-        txn: &'txn mut RwTxn,
-        range: &'a R,
-    ) -> Result<RwRevRange<'txn, KC, DC, C>>
-    where
-        KC: BytesEncode<'a>,
-        R: RangeBounds<KC::EItem>,
-    {
+    pub fn iter<'txn>(&self, txn: &'txn RoTxn) -> Result<RoIter<'txn, KC, DC>> {
         assert_eq_env_db_txn!(self, txn);
-
-        let start_bound = match range.start_bound() {
-            Bound::Included(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Included(bytes.into_owned())
-            }
-            Bound::Excluded(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Excluded(bytes.into_owned())
-            }
-            Bound::Unbounded => Bound::Unbounded,
-        };
-
-        let end_bound = match range.end_bound() {
-            Bound::Included(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Included(bytes.into_owned())
-            }
-            Bound::Excluded(bound) => {
-                let bytes = KC::bytes_encode(bound).map_err(Error::Encoding)?;
-                Bound::Excluded(bytes.into_owned())
-            }
-            Bound::Unbounded => Bound::Unbounded,
-        };
-
-        RwCursor::new(txn, self.dbi).map(|cursor| RwRevRange::new(cursor, start_bound, end_bound))
+        RoCursor::new(txn, self.dbi).map(|cursor| RoIter::new(cursor))
     }
 
-    /// Return a lexicographically ordered iterator of all key-value pairs
-    /// in this database that starts with the given prefix.
-    ///
-    /// Comparisons are made by using the bytes representation of the key.
-    ///
-    /// You can make this iterator `Send`able between threads by opening
-    /// the environment with the [`EnvOpenOptions::read_txn_without_tls`]
-    /// method.
+    /// Return a mutable ordered iterator of all key-value pairs in this database.
     ///
     /// ```
     /// # use std::fs;
@@ -1613,45 +1916,43 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// type BEI32 = I32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<Str, BEI32> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, "i-am-twenty-eight", &28)?;
-    /// db.put(&mut wtxn, "i-am-twenty-seven", &27)?;
-    /// db.put(&mut wtxn, "i-am-twenty-nine",  &29)?;
-    /// db.put(&mut wtxn, "i-am-forty-one",    &41)?;
-    /// db.put(&mut wtxn, "i-am-forty-two",    &42)?;
+    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
+    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
+    ///
+    /// let mut iter = db.iter_mut(&mut wtxn)?;
+    /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
+    /// let ret = unsafe { iter.del_current()? };
+    /// assert!(ret);
+    ///
+    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-twenty-seven")));
+    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-forty-two")));
+    /// let ret = unsafe { iter.put_current(&42, "i-am-the-new-forty-two")? };
+    /// assert!(ret);
     ///
-    /// let mut iter = db.prefix_iter(&mut wtxn, "i-am-twenty")?;
-    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-eight", 28)));
-    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-nine", 29)));
-    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-seven", 27)));
     /// assert_eq!(iter.next().transpose()?, None);
     ///
     /// drop(iter);
+    ///
+    /// let ret = db.get(&wtxn, &13)?;
+    /// assert_eq!(ret, None);
+    ///
+    /// let ret = db.get(&wtxn, &42)?;
+    /// assert_eq!(ret, Some("i-am-the-new-forty-two"));
+    ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn prefix_iter<'a, 'txn>(
-        &self,
-        txn: &'txn RoTxn,
-        prefix: &'a KC::EItem,
-    ) -> Result<RoPrefix<'txn, KC, DC, C>>
-    where
-        KC: BytesEncode<'a>,
-        C: LexicographicComparator,
-    {
+    pub fn iter_mut<'txn>(&self, txn: &'txn mut RwTxn) -> Result<RwIter<'txn, KC, DC>> {
         assert_eq_env_db_txn!(self, txn);
 
-        let prefix_bytes = KC::bytes_encode(prefix).map_err(Error::Encoding)?;
-        let prefix_bytes = prefix_bytes.into_owned();
-        RoCursor::new(txn, self.dbi).map(|cursor| RoPrefix::new(cursor, prefix_bytes))
+        RwCursor::new(txn, self.dbi).map(|cursor| RwIter::new(cursor))
     }
 
-    /// Return a mutable lexicographically ordered iterator of all key-value pairs
-    /// in this database that starts with the given prefix.
-    ///
-    /// Comparisons are made by using the bytes representation of the key.
+    /// Return a cursor to freely move in this database, see [`DatabaseCursor`].
     ///
     /// ```
     /// # use std::fs;
@@ -1671,58 +1972,75 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// type BEI32 = I32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<Str, BEI32> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("cursor-i32"))?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, "i-am-twenty-eight", &28)?;
-    /// db.put(&mut wtxn, "i-am-twenty-seven", &27)?;
-    /// db.put(&mut wtxn, "i-am-twenty-nine",  &29)?;
-    /// db.put(&mut wtxn, "i-am-forty-one",    &41)?;
-    /// db.put(&mut wtxn, "i-am-forty-two",    &42)?;
+    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
+    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
     ///
-    /// let mut iter = db.prefix_iter_mut(&mut wtxn, "i-am-twenty")?;
-    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-eight", 28)));
-    /// let ret = unsafe { iter.del_current()? };
-    /// assert!(ret);
+    /// let mut cursor = db.cursor(&wtxn)?;
+    /// assert_eq!(cursor.seek(&27)?, Some((27, "i-am-twenty-seven")));
+    /// assert_eq!(cursor.next()?, Some((42, "i-am-forty-two")));
+    /// assert_eq!(cursor.first()?, Some((13, "i-am-thirteen")));
     ///
-    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-nine", 29)));
-    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-seven", 27)));
-    /// let ret = unsafe { iter.put_current("i-am-twenty-seven", &27000)? };
-    /// assert!(ret);
+    /// drop(cursor);
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn cursor<'txn>(&self, txn: &'txn RoTxn) -> Result<DatabaseCursor<'txn, KC, DC, C>> {
+        assert_eq_env_db_txn!(self, txn);
+        RoCursor::new(txn, self.dbi).map(DatabaseCursor::new)
+    }
+
+    /// Return a cursor to freely move in and modify this database, see [`DatabaseCursorMut`].
     ///
-    /// assert_eq!(iter.next().transpose()?, None);
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
     ///
-    /// drop(iter);
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
     ///
-    /// let ret = db.get(&wtxn, "i-am-twenty-eight")?;
-    /// assert_eq!(ret, None);
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("cursor-mut-i32"))?;
     ///
-    /// let ret = db.get(&wtxn, "i-am-twenty-seven")?;
-    /// assert_eq!(ret, Some(27000));
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
+    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    ///
+    /// let mut cursor = db.cursor_mut(&mut wtxn)?;
+    /// assert_eq!(cursor.seek(&27)?, Some((27, "i-am-twenty-seven")));
+    /// assert!(cursor.delete()?);
+    /// cursor.put(&13, "i-am-thirteen")?;
+    /// assert_eq!(cursor.next()?, Some((42, "i-am-forty-two")));
+    ///
+    /// drop(cursor);
+    /// let ret: Vec<_> = db.iter(&wtxn)?.collect::<heed::Result<_>>()?;
+    /// assert_eq!(ret, [(13, "i-am-thirteen"), (42, "i-am-forty-two")]);
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn prefix_iter_mut<'a, 'txn>(
+    pub fn cursor_mut<'txn>(
         &self,
         txn: &'txn mut RwTxn,
-        prefix: &'a KC::EItem,
-    ) -> Result<RwPrefix<'txn, KC, DC, C>>
-    where
-        KC: BytesEncode<'a>,
-        C: LexicographicComparator,
-    {
+    ) -> Result<DatabaseCursorMut<'txn, KC, DC, C>> {
         assert_eq_env_db_txn!(self, txn);
-
-        let prefix_bytes = KC::bytes_encode(prefix).map_err(Error::Encoding)?;
-        let prefix_bytes = prefix_bytes.into_owned();
-        RwCursor::new(txn, self.dbi).map(|cursor| RwPrefix::new(cursor, prefix_bytes))
+        RwCursor::new(txn, self.dbi).map(DatabaseCursorMut::new)
     }
 
-    /// Return a reversed lexicographically ordered iterator of all key-value pairs
-    /// in this database that starts with the given prefix.
-    ///
-    /// Comparisons are made by using the bytes representation of the key.
+    /// Return a reverse ordered iterator of all key-value pairs in this database.
     ///
     /// You can make this iterator `Send`able between threads by opening
     /// the environment with the [`EnvOpenOptions::read_txn_without_tls`]
@@ -1746,45 +2064,31 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// type BEI32 = I32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<Str, BEI32> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, "i-am-twenty-eight", &28)?;
-    /// db.put(&mut wtxn, "i-am-twenty-seven", &27)?;
-    /// db.put(&mut wtxn, "i-am-twenty-nine",  &29)?;
-    /// db.put(&mut wtxn, "i-am-forty-one",    &41)?;
-    /// db.put(&mut wtxn, "i-am-forty-two",    &42)?;
+    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
+    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
     ///
-    /// let mut iter = db.rev_prefix_iter(&mut wtxn, "i-am-twenty")?;
-    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-seven", 27)));
-    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-nine", 29)));
-    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-eight", 28)));
+    /// let mut iter = db.rev_iter(&wtxn)?;
+    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-forty-two")));
+    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-twenty-seven")));
+    /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
     /// assert_eq!(iter.next().transpose()?, None);
     ///
     /// drop(iter);
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn rev_prefix_iter<'a, 'txn>(
-        &self,
-        txn: &'txn RoTxn,
-        prefix: &'a KC::EItem,
-    ) -> Result<RoRevPrefix<'txn, KC, DC, C>>
-    where
-        KC: BytesEncode<'a>,
-        C: LexicographicComparator,
-    {
+    pub fn rev_iter<'txn>(&self, txn: &'txn RoTxn) -> Result<RoRevIter<'txn, KC, DC>> {
         assert_eq_env_db_txn!(self, txn);
 
-        let prefix_bytes = KC::bytes_encode(prefix).map_err(Error::Encoding)?;
-        let prefix_bytes = prefix_bytes.into_owned();
-        RoCursor::new(txn, self.dbi).map(|cursor| RoRevPrefix::new(cursor, prefix_bytes))
+        RoCursor::new(txn, self.dbi).map(|cursor| RoRevIter::new(cursor))
     }
 
-    /// Return a mutable reversed lexicographically ordered iterator of all key-value pairs
-    /// in this database that starts with the given prefix.
-    ///
-    /// Comparisons are made by using the bytes representation of the key.
+    /// Return a mutable reverse ordered iterator of all key-value\
+    /// pairs in this database.
     ///
     /// ```
     /// # use std::fs;
@@ -1804,56 +2108,49 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// type BEI32 = I32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<Str, BEI32> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, "i-am-twenty-eight", &28)?;
-    /// db.put(&mut wtxn, "i-am-twenty-seven", &27)?;
-    /// db.put(&mut wtxn, "i-am-twenty-nine",  &29)?;
-    /// db.put(&mut wtxn, "i-am-forty-one",    &41)?;
-    /// db.put(&mut wtxn, "i-am-forty-two",    &42)?;
+    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
+    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
     ///
-    /// let mut iter = db.rev_prefix_iter_mut(&mut wtxn, "i-am-twenty")?;
-    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-seven", 27)));
+    /// let mut iter = db.rev_iter_mut(&mut wtxn)?;
+    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-forty-two")));
     /// let ret = unsafe { iter.del_current()? };
     /// assert!(ret);
     ///
-    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-nine", 29)));
-    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-eight", 28)));
-    /// let ret = unsafe { iter.put_current("i-am-twenty-eight", &28000)? };
+    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-twenty-seven")));
+    /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
+    /// let ret = unsafe { iter.put_current(&13, "i-am-the-new-thirteen")? };
     /// assert!(ret);
     ///
     /// assert_eq!(iter.next().transpose()?, None);
     ///
     /// drop(iter);
     ///
-    /// let ret = db.get(&wtxn, "i-am-twenty-seven")?;
+    /// let ret = db.get(&wtxn, &42)?;
     /// assert_eq!(ret, None);
     ///
-    /// let ret = db.get(&wtxn, "i-am-twenty-eight")?;
-    /// assert_eq!(ret, Some(28000));
+    /// let ret = db.get(&wtxn, &13)?;
+    /// assert_eq!(ret, Some("i-am-the-new-thirteen"));
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn rev_prefix_iter_mut<'a, 'txn>(
-        &self,
-        txn: &'txn mut RwTxn,
-        prefix: &'a KC::EItem,
-    ) -> Result<RwRevPrefix<'txn, KC, DC, C>>
-    where
-        KC: BytesEncode<'a>,
-        C: LexicographicComparator,
-    {
+    pub fn rev_iter_mut<'txn>(&self, txn: &'txn mut RwTxn) -> Result<RwRevIter<'txn, KC, DC>> {
         assert_eq_env_db_txn!(self, txn);
 
-        let prefix_bytes = KC::bytes_encode(prefix).map_err(Error::Encoding)?;
-        let prefix_bytes = prefix_bytes.into_owned();
-        RwCursor::new(txn, self.dbi).map(|cursor| RwRevPrefix::new(cursor, prefix_bytes))
+        RwCursor::new(txn, self.dbi).map(|cursor| RwRevIter::new(cursor))
     }
 
-    /// Insert a key-value pair in this database, replacing any previous value. The entry is
-    /// written with no specific flag.
+    /// Return an ordered iterator of a range of key-value pairs in this database.
+    ///
+    /// Comparisons are made by using the comparator `C`.
+    ///
+    /// You can make this iterator `Send`able between threads by opening
+    /// the environment with the [`EnvOpenOptions::read_txn_without_tls`]
+    /// method.
     ///
     /// ```
     /// # use std::fs;
@@ -1881,37 +2178,756 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
     /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
     ///
-    /// let ret = db.get(&mut wtxn, &27)?;
-    /// assert_eq!(ret, Some("i-am-twenty-seven"));
+    /// let range = 27..=42;
+    /// let mut iter = db.range(&wtxn, &range)?;
+    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-twenty-seven")));
+    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-forty-two")));
+    /// assert_eq!(iter.next().transpose()?, None);
     ///
+    /// drop(iter);
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn put<'a>(&self, txn: &mut RwTxn, key: &'a KC::EItem, data: &'a DC::EItem) -> Result<()>
-    where
-        KC: BytesEncode<'a>,
-        DC: BytesEncode<'a>,
-    {
-        assert_eq_env_db_txn!(self, txn);
-
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
-
-        let mut key_val = unsafe { crate::into_val(&key_bytes) };
-        let mut data_val = unsafe { crate::into_val(&data_bytes) };
-        let flags = 0;
-
-        unsafe {
-            mdb_result(ffi::mdb_put(
-                txn.txn_ptr().as_mut(),
-                self.dbi,
-                &mut key_val,
-                &mut data_val,
-                flags,
-            ))?
-        }
+    ///
+    /// It can be complex to work with ranges of slices and using
+    /// the `..` or `..=` range syntax is not the best way to deal
+    /// with that. We highly recommend using the [`Bound`](std::ops::Bound) enum for that.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// use std::ops::Bound;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(30)
+    /// #     .open(dir.path())?
+    /// # };
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<Bytes, Unit> = env.create_database(&mut wtxn, None)?;
+    ///
+    /// // make sure to create slices and not ref array
+    /// // by using the [..] syntax.
+    /// let start = &[0, 0, 0][..];
+    /// let end = &[9, 0, 0][..];
+    ///
+    /// // equivalent to start..end
+    /// let range = (Bound::Included(start), Bound::Excluded(end));
+    ///
+    /// let iter = db.range(&mut wtxn, &range)?;
+    ///
+    /// drop(iter);
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn range<'a, 'txn, R>(
+        &self,
+        txn: &'txn RoTxn,
+        range: &'a R,
+    ) -> Result<RoRange<'txn, KC, DC, C>>
+    where
+        KC: BytesEncode<'a>,
+        R: RangeBounds<KC::EItem>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let start_bound = match range.start_bound() {
+            Bound::Included(bound) => {
+                let bytes =
+                    KC::bytes_encode(bound).map_err(self.operation(txn, "range").encoding(None))?;
+                Bound::Included(bytes.into_owned())
+            }
+            Bound::Excluded(bound) => {
+                let bytes =
+                    KC::bytes_encode(bound).map_err(self.operation(txn, "range").encoding(None))?;
+                Bound::Excluded(bytes.into_owned())
+            }
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        let end_bound = match range.end_bound() {
+            Bound::Included(bound) => {
+                let bytes =
+                    KC::bytes_encode(bound).map_err(self.operation(txn, "range").encoding(None))?;
+                Bound::Included(bytes.into_owned())
+            }
+            Bound::Excluded(bound) => {
+                let bytes =
+                    KC::bytes_encode(bound).map_err(self.operation(txn, "range").encoding(None))?;
+                Bound::Excluded(bytes.into_owned())
+            }
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        RoCursor::new(txn, self.dbi).map(|cursor| RoRange::new(cursor, start_bound, end_bound))
+    }
+
+    /// Return a mutable ordered iterator of a range of key-value pairs in this database.
+    ///
+    /// Comparisons are made by using the comparator `C`.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
+    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
+    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
+    ///
+    /// let range = 27..=42;
+    /// let mut range = db.range_mut(&mut wtxn, &range)?;
+    /// assert_eq!(range.next().transpose()?, Some((27, "i-am-twenty-seven")));
+    /// let ret = unsafe { range.del_current()? };
+    /// assert!(ret);
+    /// assert_eq!(range.next().transpose()?, Some((42, "i-am-forty-two")));
+    /// let ret = unsafe { range.put_current(&42, "i-am-the-new-forty-two")? };
+    /// assert!(ret);
+    ///
+    /// assert_eq!(range.next().transpose()?, None);
+    /// drop(range);
+    ///
+    ///
+    /// let mut iter = db.iter(&wtxn)?;
+    /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
+    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-the-new-forty-two")));
+    /// assert_eq!(iter.next().transpose()?, Some((521, "i-am-five-hundred-and-twenty-one")));
+    /// assert_eq!(iter.next().transpose()?, None);
+    ///
+    /// drop(iter);
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn range_mut<'a, 'txn, R>(
+        &self,
+        txn: &'txn mut RwTxn,
+        range: &'a R,
+    ) -> Result<RwRange<'txn, KC, DC, C>>
+    where
+        KC: BytesEncode<'a>,
+        R: RangeBounds<KC::EItem>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let start_bound = match range.start_bound() {
+            Bound::Included(bound) => {
+                let bytes = KC::bytes_encode(bound)
+                    .map_err(self.operation(txn, "range_mut").encoding(None))?;
+                Bound::Included(bytes.into_owned())
+            }
+            Bound::Excluded(bound) => {
+                let bytes = KC::bytes_encode(bound)
+                    .map_err(self.operation(txn, "range_mut").encoding(None))?;
+                Bound::Excluded(bytes.into_owned())
+            }
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        let end_bound = match range.end_bound() {
+            Bound::Included(bound) => {
+                let bytes = KC::bytes_encode(bound)
+                    .map_err(self.operation(txn, "range_mut").encoding(None))?;
+                Bound::Included(bytes.into_owned())
+            }
+            Bound::Excluded(bound) => {
+                let bytes = KC::bytes_encode(bound)
+                    .map_err(self.operation(txn, "range_mut").encoding(None))?;
+                Bound::Excluded(bytes.into_owned())
+            }
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        RwCursor::new(txn, self.dbi).map(|cursor| RwRange::new(cursor, start_bound, end_bound))
+    }
+
+    /// Return a reverse ordered iterator of a range of key-value pairs in this database.
+    ///
+    /// Comparisons are made by using the comparator `C`.
+    ///
+    /// You can make this iterator `Send`able between threads by opening
+    /// the environment with the [`EnvOpenOptions::read_txn_without_tls`]
+    /// method.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
+    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
+    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
+    ///
+    /// let range = 27..=43;
+    /// let mut iter = db.rev_range(&wtxn, &range)?;
+    /// assert_eq!(iter.next().transpose()?, Some((42, "i-am-forty-two")));
+    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-twenty-seven")));
+    /// assert_eq!(iter.next().transpose()?, None);
+    ///
+    /// drop(iter);
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn rev_range<'a, 'txn, R>(
+        &self,
+        txn: &'txn RoTxn,
+        range: &'a R,
+    ) -> Result<RoRevRange<'txn, KC, DC, C>>
+    where
+        KC: BytesEncode<'a>,
+        R: RangeBounds<KC::EItem>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let start_bound = match range.start_bound() {
+            Bound::Included(bound) => {
+                let bytes = KC::bytes_encode(bound)
+                    .map_err(self.operation(txn, "rev_range").encoding(None))?;
+                Bound::Included(bytes.into_owned())
+            }
+            Bound::Excluded(bound) => {
+                let bytes = KC::bytes_encode(bound)
+                    .map_err(self.operation(txn, "rev_range").encoding(None))?;
+                Bound::Excluded(bytes.into_owned())
+            }
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        let end_bound = match range.end_bound() {
+            Bound::Included(bound) => {
+                let bytes = KC::bytes_encode(bound)
+                    .map_err(self.operation(txn, "rev_range").encoding(None))?;
+                Bound::Included(bytes.into_owned())
+            }
+            Bound::Excluded(bound) => {
+                let bytes = KC::bytes_encode(bound)
+                    .map_err(self.operation(txn, "rev_range").encoding(None))?;
+                Bound::Excluded(bytes.into_owned())
+            }
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        RoCursor::new(txn, self.dbi).map(|cursor| RoRevRange::new(cursor, start_bound, end_bound))
+    }
+
+    /// Return a mutable reverse ordered iterator of a range of key-value pairs in this database.
+    ///
+    /// Comparisons are made by using the comparator `C`.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
+    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
+    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
+    ///
+    /// let range = 27..=42;
+    /// let mut range = db.rev_range_mut(&mut wtxn, &range)?;
+    /// assert_eq!(range.next().transpose()?, Some((42, "i-am-forty-two")));
+    /// let ret = unsafe { range.del_current()? };
+    /// assert!(ret);
+    /// assert_eq!(range.next().transpose()?, Some((27, "i-am-twenty-seven")));
+    /// let ret = unsafe { range.put_current(&27, "i-am-the-new-twenty-seven")? };
+    /// assert!(ret);
+    ///
+    /// assert_eq!(range.next().transpose()?, None);
+    /// drop(range);
+    ///
+    ///
+    /// let mut iter = db.iter(&wtxn)?;
+    /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
+    /// assert_eq!(iter.next().transpose()?, Some((27, "i-am-the-new-twenty-seven")));
+    /// assert_eq!(iter.next().transpose()?, Some((521, "i-am-five-hundred-and-twenty-one")));
+    /// assert_eq!(iter.next().transpose()?, None);
+    ///
+    /// drop(iter);
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn rev_range_mut<'a, 'txn, R>(
+        &self,
+        txn: &'txn mut RwTxn,
+        range: &'a R,
+    ) -> Result<RwRevRange<'txn, KC, DC, C>>
+    where
+        KC: BytesEncode<'a>,
+        R: RangeBounds<KC::EItem>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let start_bound = match range.start_bound() {
+            Bound::Included(bound) => {
+                let bytes = KC::bytes_encode(bound)
+                    .map_err(self.operation(txn, "rev_range_mut").encoding(None))?;
+                Bound::Included(bytes.into_owned())
+            }
+            Bound::Excluded(bound) => {
+                let bytes = KC::bytes_encode(bound)
+                    .map_err(self.operation(txn, "rev_range_mut").encoding(None))?;
+                Bound::Excluded(bytes.into_owned())
+            }
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        let end_bound = match range.end_bound() {
+            Bound::Included(bound) => {
+                let bytes = KC::bytes_encode(bound)
+                    .map_err(self.operation(txn, "rev_range_mut").encoding(None))?;
+                Bound::Included(bytes.into_owned())
+            }
+            Bound::Excluded(bound) => {
+                let bytes = KC::bytes_encode(bound)
+                    .map_err(self.operation(txn, "rev_range_mut").encoding(None))?;
+                Bound::Excluded(bytes.into_owned())
+            }
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        RwCursor::new(txn, self.dbi).map(|cursor| RwRevRange::new(cursor, start_bound, end_bound))
+    }
+
+    /// Return a lexicographically ordered iterator of all key-value pairs
+    /// in this database that starts with the given prefix.
+    ///
+    /// Comparisons are made by using the bytes representation of the key.
+    ///
+    /// You can make this iterator `Send`able between threads by opening
+    /// the environment with the [`EnvOpenOptions::read_txn_without_tls`]
+    /// method.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<Str, BEI32> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, "i-am-twenty-eight", &28)?;
+    /// db.put(&mut wtxn, "i-am-twenty-seven", &27)?;
+    /// db.put(&mut wtxn, "i-am-twenty-nine",  &29)?;
+    /// db.put(&mut wtxn, "i-am-forty-one",    &41)?;
+    /// db.put(&mut wtxn, "i-am-forty-two",    &42)?;
+    ///
+    /// let mut iter = db.prefix_iter(&mut wtxn, "i-am-twenty")?;
+    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-eight", 28)));
+    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-nine", 29)));
+    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-seven", 27)));
+    /// assert_eq!(iter.next().transpose()?, None);
+    ///
+    /// drop(iter);
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn prefix_iter<'a, 'txn>(
+        &self,
+        txn: &'txn RoTxn,
+        prefix: &'a KC::EItem,
+    ) -> Result<RoPrefix<'txn, KC, DC, C>>
+    where
+        KC: BytesEncode<'a>,
+        C: LexicographicComparator,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let prefix_bytes =
+            KC::bytes_encode(prefix).map_err(self.operation(txn, "prefix_iter").encoding(None))?;
+        let prefix_bytes = prefix_bytes.into_owned();
+        RoCursor::new(txn, self.dbi).map(|cursor| RoPrefix::new(cursor, prefix_bytes))
+    }
+
+    /// Return a mutable lexicographically ordered iterator of all key-value pairs
+    /// in this database that starts with the given prefix.
+    ///
+    /// Comparisons are made by using the bytes representation of the key.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<Str, BEI32> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, "i-am-twenty-eight", &28)?;
+    /// db.put(&mut wtxn, "i-am-twenty-seven", &27)?;
+    /// db.put(&mut wtxn, "i-am-twenty-nine",  &29)?;
+    /// db.put(&mut wtxn, "i-am-forty-one",    &41)?;
+    /// db.put(&mut wtxn, "i-am-forty-two",    &42)?;
+    ///
+    /// let mut iter = db.prefix_iter_mut(&mut wtxn, "i-am-twenty")?;
+    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-eight", 28)));
+    /// let ret = unsafe { iter.del_current()? };
+    /// assert!(ret);
+    ///
+    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-nine", 29)));
+    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-seven", 27)));
+    /// let ret = unsafe { iter.put_current("i-am-twenty-seven", &27000)? };
+    /// assert!(ret);
+    ///
+    /// assert_eq!(iter.next().transpose()?, None);
+    ///
+    /// drop(iter);
+    ///
+    /// let ret = db.get(&wtxn, "i-am-twenty-eight")?;
+    /// assert_eq!(ret, None);
+    ///
+    /// let ret = db.get(&wtxn, "i-am-twenty-seven")?;
+    /// assert_eq!(ret, Some(27000));
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn prefix_iter_mut<'a, 'txn>(
+        &self,
+        txn: &'txn mut RwTxn,
+        prefix: &'a KC::EItem,
+    ) -> Result<RwPrefix<'txn, KC, DC, C>>
+    where
+        KC: BytesEncode<'a>,
+        C: LexicographicComparator,
+    {
+        assert_eq_env_db_txn!(self, txn);
 
-        Ok(())
+        let prefix_bytes = KC::bytes_encode(prefix)
+            .map_err(self.operation(txn, "prefix_iter_mut").encoding(None))?;
+        let prefix_bytes = prefix_bytes.into_owned();
+        RwCursor::new(txn, self.dbi).map(|cursor| RwPrefix::new(cursor, prefix_bytes))
+    }
+
+    /// Return a reversed lexicographically ordered iterator of all key-value pairs
+    /// in this database that starts with the given prefix.
+    ///
+    /// Comparisons are made by using the bytes representation of the key.
+    ///
+    /// You can make this iterator `Send`able between threads by opening
+    /// the environment with the [`EnvOpenOptions::read_txn_without_tls`]
+    /// method.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<Str, BEI32> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, "i-am-twenty-eight", &28)?;
+    /// db.put(&mut wtxn, "i-am-twenty-seven", &27)?;
+    /// db.put(&mut wtxn, "i-am-twenty-nine",  &29)?;
+    /// db.put(&mut wtxn, "i-am-forty-one",    &41)?;
+    /// db.put(&mut wtxn, "i-am-forty-two",    &42)?;
+    ///
+    /// let mut iter = db.rev_prefix_iter(&mut wtxn, "i-am-twenty")?;
+    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-seven", 27)));
+    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-nine", 29)));
+    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-eight", 28)));
+    /// assert_eq!(iter.next().transpose()?, None);
+    ///
+    /// drop(iter);
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn rev_prefix_iter<'a, 'txn>(
+        &self,
+        txn: &'txn RoTxn,
+        prefix: &'a KC::EItem,
+    ) -> Result<RoRevPrefix<'txn, KC, DC, C>>
+    where
+        KC: BytesEncode<'a>,
+        C: LexicographicComparator,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let prefix_bytes = KC::bytes_encode(prefix)
+            .map_err(self.operation(txn, "rev_prefix_iter").encoding(None))?;
+        let prefix_bytes = prefix_bytes.into_owned();
+        RoCursor::new(txn, self.dbi).map(|cursor| RoRevPrefix::new(cursor, prefix_bytes))
+    }
+
+    /// Return a mutable reversed lexicographically ordered iterator of all key-value pairs
+    /// in this database that starts with the given prefix.
+    ///
+    /// Comparisons are made by using the bytes representation of the key.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<Str, BEI32> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, "i-am-twenty-eight", &28)?;
+    /// db.put(&mut wtxn, "i-am-twenty-seven", &27)?;
+    /// db.put(&mut wtxn, "i-am-twenty-nine",  &29)?;
+    /// db.put(&mut wtxn, "i-am-forty-one",    &41)?;
+    /// db.put(&mut wtxn, "i-am-forty-two",    &42)?;
+    ///
+    /// let mut iter = db.rev_prefix_iter_mut(&mut wtxn, "i-am-twenty")?;
+    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-seven", 27)));
+    /// let ret = unsafe { iter.del_current()? };
+    /// assert!(ret);
+    ///
+    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-nine", 29)));
+    /// assert_eq!(iter.next().transpose()?, Some(("i-am-twenty-eight", 28)));
+    /// let ret = unsafe { iter.put_current("i-am-twenty-eight", &28000)? };
+    /// assert!(ret);
+    ///
+    /// assert_eq!(iter.next().transpose()?, None);
+    ///
+    /// drop(iter);
+    ///
+    /// let ret = db.get(&wtxn, "i-am-twenty-seven")?;
+    /// assert_eq!(ret, None);
+    ///
+    /// let ret = db.get(&wtxn, "i-am-twenty-eight")?;
+    /// assert_eq!(ret, Some(28000));
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn rev_prefix_iter_mut<'a, 'txn>(
+        &self,
+        txn: &'txn mut RwTxn,
+        prefix: &'a KC::EItem,
+    ) -> Result<RwRevPrefix<'txn, KC, DC, C>>
+    where
+        KC: BytesEncode<'a>,
+        C: LexicographicComparator,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let prefix_bytes = KC::bytes_encode(prefix)
+            .map_err(self.operation(txn, "rev_prefix_iter_mut").encoding(None))?;
+        let prefix_bytes = prefix_bytes.into_owned();
+        RwCursor::new(txn, self.dbi).map(|cursor| RwRevPrefix::new(cursor, prefix_bytes))
+    }
+
+    /// Insert a key-value pair in this database, replacing any previous value. The entry is
+    /// written with no specific flag.
+    ///
+    /// See [`Database::put_into`] to encode the key and the value without allocating.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
+    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
+    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
+    ///
+    /// let ret = db.get(&mut wtxn, &27)?;
+    /// assert_eq!(ret, Some("i-am-twenty-seven"));
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn put<'a>(&self, txn: &mut RwTxn, key: &'a KC::EItem, data: &'a DC::EItem) -> Result<()>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let context = self.operation(txn, "put");
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(context.encoding(None))?;
+        let data_bytes: Cow<[u8]> =
+            DC::bytes_encode(data).map_err(context.encoding(Some(&key_bytes)))?;
+
+        self.put_bytes(txn, "put", 0, &key_bytes, &data_bytes)
+    }
+
+    /// Insert a key-value pair in this database, replacing any previous value, like
+    /// [`Database::put`] but encoding the key and the value into buffers reused by the
+    /// write transaction instead of allocating them.
+    ///
+    /// ```
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEU64 = U64<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<BEU64, Str> = env.create_database(&mut wtxn, Some("put-into"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// for i in 0..100 {
+    ///     db.put_into(&mut wtxn, &i, "no-allocation-here")?;
+    /// }
+    /// assert_eq!(db.get(&wtxn, &42)?, Some("no-allocation-here"));
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn put_into<'a>(
+        &self,
+        txn: &mut RwTxn,
+        key: &'a KC::EItem,
+        data: &'a DC::EItem,
+    ) -> Result<()>
+    where
+        KC: BytesEncodeInto<'a>,
+        DC: BytesEncodeInto<'a>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let mut scratch = mem::take(&mut txn.scratch);
+        scratch.recycle();
+
+        let result = (|| {
+            let context = self.operation(txn, "put_into");
+
+            KC::bytes_encode_into(key, &mut scratch.key).map_err(context.encoding(None))?;
+            if let Some(size) = DC::size_hint(data) {
+                scratch.data.reserve(size);
+            }
+            DC::bytes_encode_into(data, &mut scratch.data)
+                .map_err(context.encoding(Some(&scratch.key)))?;
+
+            self.put_bytes(txn, "put_into", 0, &scratch.key, &scratch.data)
+        })();
+
+        txn.scratch = scratch;
+        result
     }
 
     /// Insert a key-value pair where the value is written directly into the space reserved
@@ -1967,7 +2983,8 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> =
+            KC::bytes_encode(key).map_err(self.operation(txn, "put_reserved").encoding(None))?;
         let mut key_val = unsafe { crate::into_val(&key_bytes) };
         let mut reserved = ffi::reserve_size_val(data_size);
         let flags = ffi::MDB_RESERVE;
@@ -2063,24 +3080,56 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
+        let context = self.operation(txn, "put_with_flags");
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(context.encoding(None))?;
+        let data_bytes: Cow<[u8]> =
+            DC::bytes_encode(data).map_err(context.encoding(Some(&key_bytes)))?;
 
-        let mut key_val = unsafe { crate::into_val(&key_bytes) };
-        let mut data_val = unsafe { crate::into_val(&data_bytes) };
-        let flags = flags.bits();
+        self.put_bytes(txn, "put_with_flags", flags.bits(), &key_bytes, &data_bytes)
+    }
+
+    /// Inserts the encoded key and value with the given flags.
+    fn put_bytes(
+        &self,
+        txn: &mut RwTxn,
+        operation: &'static str,
+        flags: u32,
+        key: &[u8],
+        data: &[u8],
+    ) -> Result<()> {
+        let mut key_val = unsafe { crate::into_val(key) };
+        let mut data_val = unsafe { crate::into_val(data) };
 
         unsafe {
             mdb_result(ffi::mdb_put(
-                txn.txn_ptr().as_mut(),
+                txn.txn.txn_ptr().as_ptr(),
                 self.dbi,
                 &mut key_val,
                 &mut data_val,
                 flags,
-            ))?
+            ))
+            .map_err(|e| self.operation(txn, operation).error(e, Some(key)))
         }
+    }
 
-        Ok(())
+    /// Returns the operation on this database whose context is attached to the errors,
+    /// if the environment records the context of the errors.
+    fn operation<'t>(&self, txn: &'t RoTxn, name: &'static str) -> Operation<'t> {
+        Operation::new(txn.env_inner(), self.dbi, name)
+    }
+
+    /// Returns whether this database was opened with the `DUP_SORT` flag.
+    pub(crate) fn is_dup_sort(&self, txn: &RoTxn) -> Result<bool> {
+        let mut flags = 0;
+        unsafe { mdb_result(ffi::mdb_dbi_flags(txn.txn_ptr().as_ptr(), self.dbi, &mut flags))? };
+        Ok(flags & DatabaseFlags::DUP_SORT.bits() != 0)
+    }
+
+    /// Returns whether this database was opened with the `DUP_FIXED` flag.
+    fn is_dup_fixed(&self, txn: &RoTxn) -> Result<bool> {
+        let mut flags = 0;
+        unsafe { mdb_result(ffi::mdb_dbi_flags(txn.txn_ptr().as_ptr(), self.dbi, &mut flags))? };
+        Ok(flags & DatabaseFlags::DUP_FIXED.bits() != 0)
     }
 
     /// Insert a key-value pair where the value is written directly into the space reserved
@@ -2127,7 +3176,8 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(self.operation(txn, "put_reserved_with_flags").encoding(None))?;
         let mut key_val = unsafe { crate::into_val(&key_bytes) };
         let mut reserved = ffi::reserve_size_val(data_size);
         let flags = flags.bits() | ffi::MDB_RESERVE;
@@ -2244,8 +3294,10 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(self.operation(txn, "get_or_put_with_flags").encoding(None))?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data)
+            .map_err(self.operation(txn, "get_or_put_with_flags").encoding(Some(&key_bytes)))?;
 
         let mut key_val = unsafe { crate::into_val(&key_bytes) };
         let mut data_val = unsafe { crate::into_val(&data_bytes) };
@@ -2267,7 +3319,9 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
             // the key already exists: the previous value is stored in the data parameter
             Err(MdbError::KeyExist) => {
                 let bytes = unsafe { crate::from_val(data_val) };
-                let data = DC::bytes_decode(bytes).map_err(Error::Decoding)?;
+                let data = DC::bytes_decode(bytes).map_err(
+                    self.operation(txn, "get_or_put_with_flags").decoding(Some(&key_bytes)),
+                )?;
                 Ok(Some(data))
             }
             Err(error) => Err(error.into()),
@@ -2342,22 +3396,415 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
         self.get_or_put_reserved_with_flags(txn, PutFlags::empty(), key, data_size, write_func)
     }
 
-    /// Attempt to insert a key-value pair in this database, where the value is written
-    /// directly into the space reserved by LMDB, or if a value already exists for the key,
-    /// returns the previous value.
+    /// Attempt to insert a key-value pair in this database, where the value is written
+    /// directly into the space reserved by LMDB, or if a value already exists for the key,
+    /// returns the previous value.
+    ///
+    /// This avoids an extra copy of the value. The reserved space points into the memory
+    /// map only when the environment was opened with `MDB_WRITEMAP`; otherwise it points to
+    /// an in-memory page that LMDB later flushes to disk.
+    ///
+    /// The entry is written with the specified flags, in addition to
+    /// [`NO_OVERWRITE`](PutFlags::NO_OVERWRITE) and [`MDB_RESERVE`](ffi::MDB_RESERVE)
+    /// which are always used.
+    ///
+    /// ```
+    /// # use heed::EnvOpenOptions;
+    /// use std::io::Write;
+    /// use heed::{Database, PutFlags};
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db = env.create_database::<BEI32, Str>(&mut wtxn, Some("number-string"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// let long = "I am a long long long value";
+    /// assert_eq!(
+    ///     db.get_or_put_reserved_with_flags(&mut wtxn, PutFlags::empty(), &42, long.len(), |reserved| {
+    ///         reserved.write_all(long.as_bytes())
+    ///     })?,
+    ///     None
+    /// );
+    ///
+    /// let longer = "I am an even longer long long long value";
+    /// assert_eq!(
+    ///     db.get_or_put_reserved_with_flags(&mut wtxn, PutFlags::empty(), &42, longer.len(), |reserved| {
+    ///         unreachable!()
+    ///     })?,
+    ///     Some(long)
+    /// );
+    ///
+    /// let ret = db.get(&mut wtxn, &42)?;
+    /// assert_eq!(ret, Some(long));
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn get_or_put_reserved_with_flags<'a, 'txn, F>(
+        &'txn self,
+        txn: &mut RwTxn,
+        flags: PutFlags,
+        key: &'a KC::EItem,
+        data_size: usize,
+        write_func: F,
+    ) -> Result<Option<DC::DItem>>
+    where
+        KC: BytesEncode<'a>,
+        F: FnOnce(&mut ReservedSpace) -> io::Result<()>,
+        DC: BytesDecode<'a>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(self.operation(txn, "get_or_put_reserved_with_flags").encoding(None))?;
+
+        let mut key_val = unsafe { crate::into_val(&key_bytes) };
+        let mut reserved = ffi::reserve_size_val(data_size);
+        let flags = (flags | PutFlags::NO_OVERWRITE).bits() | ffi::MDB_RESERVE;
+
+        let result = unsafe {
+            mdb_result(ffi::mdb_put(
+                txn.txn.txn_ptr().as_mut(),
+                self.dbi,
+                &mut key_val,
+                &mut reserved,
+                flags,
+            ))
+        };
+
+        match result {
+            // value was inserted: fill the reserved space
+            Ok(()) => {
+                let mut reserved = unsafe { ReservedSpace::from_val(reserved) };
+                write_func(&mut reserved)?;
+                if reserved.remaining() == 0 {
+                    Ok(None)
+                } else {
+                    Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
+                }
+            }
+            // the key already exists: the previous value is stored in the data parameter
+            Err(MdbError::KeyExist) => {
+                let bytes = unsafe { crate::from_val(reserved) };
+                let data = DC::bytes_decode(bytes).map_err(
+                    self.operation(txn, "get_or_put_reserved_with_flags")
+                        .decoding(Some(&key_bytes)),
+                )?;
+                Ok(Some(data))
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Merges the given operand into the value of a key with the merge function `M`, without
+    /// decoding nor encoding the value, and writes the merged value back in place.
+    ///
+    /// The merge function receives the existing raw value, or `None` if the key doesn't exist,
+    /// and the encoded operand. The merged value is written into the space reserved by LMDB
+    /// where the previous value was, an existing key is looked up only once.
+    ///
+    /// The errors of the merge function are returned as [`Error::Encoding`] errors, the
+    /// merged value being the encoded one. Returns an [`MdbError::Incompatible`] error if
+    /// the database supports duplicate data items.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEU64 = U64<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<Str, BEU64> = env.create_database(&mut wtxn, Some("merge"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.merge::<IntegerAdd<BEU64>>(&mut wtxn, "visits", &1)?;
+    /// db.merge::<IntegerAdd<BEU64>>(&mut wtxn, "visits", &41)?;
+    ///
+    /// assert_eq!(db.get(&wtxn, "visits")?, Some(42));
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn merge<'a, M>(
+        &self,
+        txn: &mut RwTxn,
+        key: &'a KC::EItem,
+        operand: &'a DC::EItem,
+    ) -> Result<()>
+    where
+        M: MergeFunction,
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        if self.is_dup_sort(txn)? {
+            return Err(Error::Mdb(MdbError::Incompatible));
+        }
+
+        let mut scratch = mem::take(&mut txn.scratch);
+        scratch.recycle();
+
+        let result = (|| {
+            let context = self.operation(txn, "merge");
+
+            encode_into::<KC>(key, &mut scratch.key).map_err(context.encoding(None))?;
+            encode_into::<DC>(operand, &mut scratch.data)
+                .map_err(context.encoding(Some(&scratch.key)))?;
+
+            let mut cursor = RwCursor::new(txn, self.dbi)?;
+            let mut output = Vec::new();
+            merge_on_key::<M>(&mut cursor, "merge", &scratch.key, &[&scratch.data], &mut output)
+        })();
+
+        txn.scratch = scratch;
+        result
+    }
+
+    /// Merges many operands into the values of their keys with the merge function `M`,
+    /// using a single cursor.
+    ///
+    /// Consecutive entries with the same key are merged together in a single call to the
+    /// merge function, and the value is written back only once. The entries should be sorted
+    /// by key according to the comparator `C` so that all the operands of a key are merged at
+    /// once; entries out of order are still merged correctly but their key is written again.
+    ///
+    /// The errors of the merge function are returned as [`Error::Encoding`] errors, the
+    /// merged value being the encoded one. Returns an [`MdbError::Incompatible`] error if
+    /// the database supports duplicate data items.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEU64 = U64<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<Str, BEU64> = env.create_database(&mut wtxn, Some("merge-sorted"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, "apples", &10)?;
+    ///
+    /// let entries = [("apples", 1), ("apples", 2), ("oranges", 5), ("pears", 7)];
+    /// db.merge_sorted::<IntegerAdd<BEU64>, _>(&mut wtxn, entries.iter().map(|(k, v)| (*k, v)))?;
+    ///
+    /// let counts: Vec<_> = db.iter(&wtxn)?.collect::<heed::Result<_>>()?;
+    /// assert_eq!(counts, [("apples", 13), ("oranges", 5), ("pears", 7)]);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn merge_sorted<'a, M, I>(&self, txn: &mut RwTxn, entries: I) -> Result<()>
+    where
+        M: MergeFunction,
+        I: IntoIterator<Item = (&'a KC::EItem, &'a DC::EItem)>,
+        KC: BytesEncode<'a> + 'a,
+        DC: BytesEncode<'a> + 'a,
+        C: Comparator,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        if self.is_dup_sort(txn)? {
+            return Err(Error::Mdb(MdbError::Incompatible));
+        }
+
+        let mut scratch = mem::take(&mut txn.scratch);
+        scratch.recycle();
+
+        let result = (|| {
+            let context = self.operation(txn, "merge_sorted");
+
+            let mut cursor = RwCursor::new(txn, self.dbi)?;
+            let mut next_key = Vec::new();
+            let mut operand_ends = Vec::new();
+            let mut output = Vec::new();
+
+            // The key of the current group is in the key scratch buffer and
+            // its operands are concatenated in the data scratch buffer.
+            let mut merge_group = |key: &[u8], operands: &[u8], ends: &[usize]| {
+                let mut start = 0;
+                let mut slices = Vec::with_capacity(ends.len());
+                for &end in ends {
+                    slices.push(&operands[start..end]);
+                    start = end;
+                }
+                merge_on_key::<M>(&mut cursor, "merge_sorted", key, &slices, &mut output)
+            };
+
+            for (key, operand) in entries {
+                next_key.clear();
+                encode_into::<KC>(key, &mut next_key).map_err(context.encoding(None))?;
+
+                if !operand_ends.is_empty() && C::compare(&next_key, &scratch.key).is_ne() {
+                    merge_group(&scratch.key, &scratch.data, &operand_ends)?;
+                    operand_ends.clear();
+                    scratch.data.clear();
+                }
+
+                if operand_ends.is_empty() {
+                    mem::swap(&mut scratch.key, &mut next_key);
+                }
+
+                encode_into::<DC>(operand, &mut scratch.data)
+                    .map_err(context.encoding(Some(&scratch.key)))?;
+                operand_ends.push(scratch.data.len());
+            }
+
+            if !operand_ends.is_empty() {
+                merge_group(&scratch.key, &scratch.data, &operand_ends)?;
+            }
+
+            Ok(())
+        })();
+
+        txn.scratch = scratch;
+        result
+    }
+
+    /// Reads the value of a key and replaces it with the one returned by the given closure,
+    /// positioning the cursor on the key only once.
+    ///
+    /// The closure receives the current value, or `None` if the key doesn't exist, and returns
+    /// the new value to write or `None` to delete the entry. The new value is written in place
+    /// when it has the same size as the previous one and the entry is left untouched when the
+    /// encoded value doesn't change.
+    ///
+    /// Returns `true` if the entry was written or deleted.
+    /// Returns an [`MdbError::Incompatible`] error if the database supports duplicate data items.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<Str, BEI32> = env.create_database(&mut wtxn, Some("update"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, "stock", &3)?;
+    ///
+    /// // Take one item out of the stock and remove the entry once it is empty.
+    /// let take_one = |stock: Option<i32>| stock.map(|n| n - 1).filter(|&n| n > 0);
+    /// assert!(db.update(&mut wtxn, "stock", take_one)?);
+    /// assert_eq!(db.get(&wtxn, "stock")?, Some(2));
+    ///
+    /// db.update(&mut wtxn, "stock", take_one)?;
+    /// db.update(&mut wtxn, "stock", take_one)?;
+    /// assert_eq!(db.get(&wtxn, "stock")?, None);
+    ///
+    /// // Nothing is written when the key doesn't exist and the closure returns `None`.
+    /// assert!(!db.update(&mut wtxn, "stock", take_one)?);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn update<'a, DE, V, F>(&self, txn: &mut RwTxn, key: &'a KC::EItem, f: F) -> Result<bool>
+    where
+        KC: BytesEncode<'a>,
+        DC: for<'b> BytesDecode<'b> + for<'b> BytesEncode<'b, EItem = DE>,
+        DE: ?Sized,
+        V: Borrow<DE>,
+        F: for<'b> FnOnce(Option<<DC as BytesDecode<'b>>::DItem>) -> Option<V>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        if self.is_dup_sort(txn)? {
+            return Err(Error::Mdb(MdbError::Incompatible));
+        }
+
+        let mut scratch = mem::take(&mut txn.scratch);
+        scratch.recycle();
+
+        let result = (|| {
+            let context = self.operation(txn, "update");
+
+            encode_into::<KC>(key, &mut scratch.key).map_err(context.encoding(None))?;
+
+            let mut cursor = RwCursor::new(txn, self.dbi)?;
+            let existing = match cursor.move_on_key(&scratch.key)? {
+                true => cursor.current()?.map(|(_, data)| data),
+                false => None,
+            };
+
+            let current = match existing.map(DC::bytes_decode).transpose() {
+                Ok(current) => current,
+                Err(e) => return Err(context.error(Error::Decoding(e), Some(&scratch.key))),
+            };
+
+            let new = match f(current) {
+                Some(value) => {
+                    encode_into::<DC>(value.borrow(), &mut scratch.data)
+                        .map_err(context.encoding(Some(&scratch.key)))?;
+                    Some(&scratch.data[..])
+                }
+                None => None,
+            };
+
+            replace_current(&mut cursor, &scratch.key, existing, new)
+        })();
+
+        txn.scratch = scratch;
+        result
+    }
+
+    /// Replaces the value of a key with `new` only if its current value is `expected`,
+    /// positioning the cursor on the key only once.
     ///
-    /// This avoids an extra copy of the value. The reserved space points into the memory
-    /// map only when the environment was opened with `MDB_WRITEMAP`; otherwise it points to
-    /// an in-memory page that LMDB later flushes to disk.
+    /// An `expected` value of `None` means that the key must not exist and a `new` value
+    /// of `None` deletes the entry. The values are compared on their encoded bytes.
     ///
-    /// The entry is written with the specified flags, in addition to
-    /// [`NO_OVERWRITE`](PutFlags::NO_OVERWRITE) and [`MDB_RESERVE`](ffi::MDB_RESERVE)
-    /// which are always used.
+    /// Returns `true` if the current value was the expected one.
+    /// Returns an [`MdbError::Incompatible`] error if the database supports duplicate data items.
     ///
     /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
     /// # use heed::EnvOpenOptions;
-    /// use std::io::Write;
-    /// use heed::{Database, PutFlags};
+    /// use heed::Database;
     /// use heed::types::*;
     /// use heed::byteorder::BigEndian;
     ///
@@ -2368,84 +3815,76 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEI32 = I32<BigEndian>;
-    ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db = env.create_database::<BEI32, Str>(&mut wtxn, Some("number-string"))?;
+    /// let db: Database<Str, Str> = env.create_database(&mut wtxn, Some("compare-and-swap"))?;
     ///
     /// # db.clear(&mut wtxn)?;
-    /// let long = "I am a long long long value";
-    /// assert_eq!(
-    ///     db.get_or_put_reserved_with_flags(&mut wtxn, PutFlags::empty(), &42, long.len(), |reserved| {
-    ///         reserved.write_all(long.as_bytes())
-    ///     })?,
-    ///     None
-    /// );
+    /// assert!(db.compare_and_swap(&mut wtxn, "status", None, Some("pending"))?);
+    /// assert!(!db.compare_and_swap(&mut wtxn, "status", None, Some("pending"))?);
     ///
-    /// let longer = "I am an even longer long long long value";
-    /// assert_eq!(
-    ///     db.get_or_put_reserved_with_flags(&mut wtxn, PutFlags::empty(), &42, longer.len(), |reserved| {
-    ///         unreachable!()
-    ///     })?,
-    ///     Some(long)
-    /// );
+    /// assert!(db.compare_and_swap(&mut wtxn, "status", Some("pending"), Some("done"))?);
+    /// assert!(!db.compare_and_swap(&mut wtxn, "status", Some("pending"), None)?);
+    /// assert_eq!(db.get(&wtxn, "status")?, Some("done"));
     ///
-    /// let ret = db.get(&mut wtxn, &42)?;
-    /// assert_eq!(ret, Some(long));
+    /// assert!(db.compare_and_swap(&mut wtxn, "status", Some("done"), None)?);
+    /// assert_eq!(db.get(&wtxn, "status")?, None);
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn get_or_put_reserved_with_flags<'a, 'txn, F>(
-        &'txn self,
+    pub fn compare_and_swap<'a>(
+        &self,
         txn: &mut RwTxn,
-        flags: PutFlags,
         key: &'a KC::EItem,
-        data_size: usize,
-        write_func: F,
-    ) -> Result<Option<DC::DItem>>
+        expected: Option<&'a DC::EItem>,
+        new: Option<&'a DC::EItem>,
+    ) -> Result<bool>
     where
         KC: BytesEncode<'a>,
-        F: FnOnce(&mut ReservedSpace) -> io::Result<()>,
-        DC: BytesDecode<'a>,
+        DC: BytesEncode<'a>,
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        if self.is_dup_sort(txn)? {
+            return Err(Error::Mdb(MdbError::Incompatible));
+        }
 
-        let mut key_val = unsafe { crate::into_val(&key_bytes) };
-        let mut reserved = ffi::reserve_size_val(data_size);
-        let flags = (flags | PutFlags::NO_OVERWRITE).bits() | ffi::MDB_RESERVE;
+        let mut scratch = mem::take(&mut txn.scratch);
+        scratch.recycle();
+
+        let result = (|| {
+            let context = self.operation(txn, "compare_and_swap");
+
+            encode_into::<KC>(key, &mut scratch.key).map_err(context.encoding(None))?;
+            let expected = match expected.map(DC::bytes_encode).transpose() {
+                Ok(expected) => expected,
+                Err(e) => return Err(context.error(Error::Encoding(e), Some(&scratch.key))),
+            };
+            let new = match new {
+                Some(new) => {
+                    encode_into::<DC>(new, &mut scratch.data)
+                        .map_err(context.encoding(Some(&scratch.key)))?;
+                    Some(&scratch.data[..])
+                }
+                None => None,
+            };
 
-        let result = unsafe {
-            mdb_result(ffi::mdb_put(
-                txn.txn.txn_ptr().as_mut(),
-                self.dbi,
-                &mut key_val,
-                &mut reserved,
-                flags,
-            ))
-        };
+            let mut cursor = RwCursor::new(txn, self.dbi)?;
+            let existing = match cursor.move_on_key(&scratch.key)? {
+                true => cursor.current()?.map(|(_, data)| data),
+                false => None,
+            };
 
-        match result {
-            // value was inserted: fill the reserved space
-            Ok(()) => {
-                let mut reserved = unsafe { ReservedSpace::from_val(reserved) };
-                write_func(&mut reserved)?;
-                if reserved.remaining() == 0 {
-                    Ok(None)
-                } else {
-                    Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
-                }
-            }
-            // the key already exists: the previous value is stored in the data parameter
-            Err(MdbError::KeyExist) => {
-                let bytes = unsafe { crate::from_val(reserved) };
-                let data = DC::bytes_decode(bytes).map_err(Error::Decoding)?;
-                Ok(Some(data))
+            if existing != expected.as_deref() {
+                return Ok(false);
             }
-            Err(error) => Err(error.into()),
-        }
+
+            replace_current(&mut cursor, &scratch.key, existing, new)?;
+            Ok(true)
+        })();
+
+        txn.scratch = scratch;
+        result
     }
 
     /// Deletes an entry or every duplicate data items of a key
@@ -2497,7 +3936,8 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> =
+            KC::bytes_encode(key).map_err(self.operation(txn, "delete").encoding(None))?;
         let mut key_val = unsafe { crate::into_val(&key_bytes) };
 
         let result = unsafe {
@@ -2512,7 +3952,7 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
         match result {
             Ok(()) => Ok(true),
             Err(e) if e.not_found() => Ok(false),
-            Err(e) => Err(e.into()),
+            Err(e) => Err(self.operation(txn, "delete").error(e, Some(&key_bytes))),
         }
     }
 
@@ -2554,74 +3994,277 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// db.put(&mut wtxn, &0, &120)?;
     /// db.put(&mut wtxn, &42, &120)?;
     ///
-    /// let mut iter = db.get_duplicates(&wtxn, &68)?.expect("the key exists");
-    /// assert_eq!(iter.next().transpose()?, Some((68, 120)));
-    /// assert_eq!(iter.next().transpose()?, Some((68, 121)));
-    /// assert_eq!(iter.next().transpose()?, Some((68, 122)));
-    /// assert_eq!(iter.next().transpose()?, Some((68, 123)));
-    /// assert_eq!(iter.next().transpose()?, None);
-    /// drop(iter);
+    /// let mut iter = db.get_duplicates(&wtxn, &68)?.expect("the key exists");
+    /// assert_eq!(iter.next().transpose()?, Some((68, 120)));
+    /// assert_eq!(iter.next().transpose()?, Some((68, 121)));
+    /// assert_eq!(iter.next().transpose()?, Some((68, 122)));
+    /// assert_eq!(iter.next().transpose()?, Some((68, 123)));
+    /// assert_eq!(iter.next().transpose()?, None);
+    /// drop(iter);
+    ///
+    /// assert!(db.delete_one_duplicate(&mut wtxn, &68, &121)?, "The entry must exist");
+    ///
+    /// let mut iter = db.get_duplicates(&wtxn, &68)?.expect("the key exists");
+    /// assert_eq!(iter.next().transpose()?, Some((68, 120)));
+    /// // No more (68, 121) returned here!
+    /// assert_eq!(iter.next().transpose()?, Some((68, 122)));
+    /// assert_eq!(iter.next().transpose()?, Some((68, 123)));
+    /// assert_eq!(iter.next().transpose()?, None);
+    /// drop(iter);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn delete_one_duplicate<'a>(
+        &self,
+        txn: &mut RwTxn,
+        key: &'a KC::EItem,
+        data: &'a DC::EItem,
+    ) -> Result<bool>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(self.operation(txn, "delete_one_duplicate").encoding(None))?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data)
+            .map_err(self.operation(txn, "delete_one_duplicate").encoding(Some(&key_bytes)))?;
+        let mut key_val = unsafe { crate::into_val(&key_bytes) };
+        let mut data_val = unsafe { crate::into_val(&data_bytes) };
+
+        let result = unsafe {
+            mdb_result(ffi::mdb_del(
+                txn.txn.txn_ptr().as_mut(),
+                self.dbi,
+                &mut key_val,
+                &mut data_val,
+            ))
+        };
+
+        match result {
+            Ok(()) => Ok(true),
+            Err(e) if e.not_found() => Ok(false),
+            Err(e) => Err(self.operation(txn, "delete_one_duplicate").error(e, Some(&key_bytes))),
+        }
+    }
+
+    /// Deletes a range of key-value pairs in this database.
+    ///
+    /// Prefer using [`clear`] instead of a call to this method with a full range ([`..`]).
+    ///
+    /// Comparisons are made by using the comparator `C`.
+    ///
+    /// [`clear`]: crate::Database::clear
+    /// [`..`]: std::ops::RangeFull
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
+    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
+    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
+    ///
+    /// let range = 27..=42;
+    /// let ret = db.delete_range(&mut wtxn, &range)?;
+    /// assert_eq!(ret, 2);
+    ///
+    ///
+    /// let mut iter = db.iter(&wtxn)?;
+    /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
+    /// assert_eq!(iter.next().transpose()?, Some((521, "i-am-five-hundred-and-twenty-one")));
+    /// assert_eq!(iter.next().transpose()?, None);
+    ///
+    /// drop(iter);
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn delete_range<'a, 'txn, R>(&self, txn: &'txn mut RwTxn, range: &'a R) -> Result<usize>
+    where
+        KC: BytesEncode<'a> + BytesDecode<'txn>,
+        C: Comparator,
+        R: RangeBounds<KC::EItem>,
+    {
+        assert_eq_env_db_txn!(self, txn);
+
+        let mut count = 0;
+        let mut iter = self.remap_data_type::<DecodeIgnore>().range_mut(txn, range)?;
+
+        while iter.next().is_some() {
+            // safety: We do not keep any reference from the database while using `del_current`.
+            //         The user can't keep any reference inside of the database as we ask for a
+            //         mutable reference to the `txn`.
+            unsafe { iter.del_current()? };
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Loads many entries into this database, in any order, using fast appends.
+    ///
+    /// The entries are encoded and sorted with the comparators of the database, using
+    /// temporary files when they don't fit in memory, then written with
+    /// [`PutFlags::APPEND`] and [`PutFlags::APPEND_DUP`]. A key given many times keeps its
+    /// last value, unless the database is [`DUP_SORT`](DatabaseFlags::DUP_SORT) in which
+    /// case all of its distinct values are kept.
+    ///
+    /// Appending requires the loaded keys to be greater than the ones already in the database,
+    /// it is therefore meant to fill an empty database. An [`MdbError::KeyExist`] error is
+    /// returned otherwise, before any entry is written.
+    ///
+    /// See [`Database::bulk_load_with_options`] to configure the memory used to sort the
+    /// entries, and [`Database::bulk_load_in_chunks`] to load more entries than a single
+    /// transaction can hold.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEU32 = U32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<BEU32, Str> = env.create_database(&mut wtxn, Some("bulk-load"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// let entries = (0..1000u32).rev().map(|i| (i, i.to_string()));
+    /// db.bulk_load(&mut wtxn, entries)?;
+    ///
+    /// assert_eq!(db.len(&wtxn)?, 1000);
+    /// assert_eq!(db.first(&wtxn)?, Some((0, "0")));
+    /// assert_eq!(db.last(&wtxn)?, Some((999, "999")));
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn bulk_load<I, K, V, KE, DE>(&self, txn: &mut RwTxn, entries: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Borrow<KE>,
+        V: Borrow<DE>,
+        KE: ?Sized,
+        DE: ?Sized,
+        KC: for<'a> BytesEncode<'a, EItem = KE>,
+        DC: for<'a> BytesEncode<'a, EItem = DE>,
+        C: Comparator,
+        CDUP: Comparator,
+    {
+        self.bulk_load_with_options(txn, &BulkLoadOptions::default(), entries)
+    }
+
+    /// Loads many entries into this database, in any order, using fast appends.
+    ///
+    /// For more info, see [`Database::bulk_load`].
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::{BulkLoadOptions, Database};
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEU32 = U32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<BEU32, BEU32> = env.create_database(&mut wtxn, Some("bulk-load"))?;
     ///
-    /// assert!(db.delete_one_duplicate(&mut wtxn, &68, &121)?, "The entry must exist");
+    /// # db.clear(&mut wtxn)?;
+    /// // Only keep around 4KiB of entries in memory
+    /// let options = BulkLoadOptions::new().max_memory(4096);
+    /// let entries = (0..10_000u32).map(|i| (i.wrapping_mul(7919) % 10_000, i));
+    /// db.bulk_load_with_options(&mut wtxn, &options, entries)?;
     ///
-    /// let mut iter = db.get_duplicates(&wtxn, &68)?.expect("the key exists");
-    /// assert_eq!(iter.next().transpose()?, Some((68, 120)));
-    /// // No more (68, 121) returned here!
-    /// assert_eq!(iter.next().transpose()?, Some((68, 122)));
-    /// assert_eq!(iter.next().transpose()?, Some((68, 123)));
-    /// assert_eq!(iter.next().transpose()?, None);
-    /// drop(iter);
+    /// assert_eq!(db.len(&wtxn)?, 10_000);
+    /// assert_eq!(db.first(&wtxn)?.map(|(key, _)| key), Some(0));
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn delete_one_duplicate<'a>(
+    pub fn bulk_load_with_options<I, K, V, KE, DE>(
         &self,
         txn: &mut RwTxn,
-        key: &'a KC::EItem,
-        data: &'a DC::EItem,
-    ) -> Result<bool>
+        options: &BulkLoadOptions,
+        entries: I,
+    ) -> Result<()>
     where
-        KC: BytesEncode<'a>,
-        DC: BytesEncode<'a>,
+        I: IntoIterator<Item = (K, V)>,
+        K: Borrow<KE>,
+        V: Borrow<DE>,
+        KE: ?Sized,
+        DE: ?Sized,
+        KC: for<'a> BytesEncode<'a, EItem = KE>,
+        DC: for<'a> BytesEncode<'a, EItem = DE>,
+        C: Comparator,
+        CDUP: Comparator,
     {
         assert_eq_env_db_txn!(self, txn);
 
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
-        let mut key_val = unsafe { crate::into_val(&key_bytes) };
-        let mut data_val = unsafe { crate::into_val(&data_bytes) };
-
-        let result = unsafe {
-            mdb_result(ffi::mdb_del(
-                txn.txn.txn_ptr().as_mut(),
-                self.dbi,
-                &mut key_val,
-                &mut data_val,
-            ))
-        };
+        let mut sorted = self.sort_bulk_entries(txn, options, entries)?;
+        self.check_bulk_append(txn, &sorted)?;
+        let mut cursor = RwCursor::new(txn, self.dbi)?;
+        sorted.append_to(&mut cursor, usize::MAX)?;
 
-        match result {
-            Ok(()) => Ok(true),
-            Err(e) if e.not_found() => Ok(false),
-            Err(e) => Err(e.into()),
-        }
+        Ok(())
     }
 
-    /// Deletes a range of key-value pairs in this database.
+    /// Loads many entries into this database, committing a write transaction
+    /// every `entries_per_txn` entries.
     ///
-    /// Prefer using [`clear`] instead of a call to this method with a full range ([`..`]).
+    /// This avoids [`MdbError::TxnFull`] errors when loading a huge number of entries.
+    /// The entries are consumed and sorted in a read transaction, the first write transaction
+    /// is only opened to append them, and the entries committed before an error are kept in
+    /// the database.
     ///
-    /// Comparisons are made by using the comparator `C`.
+    /// For more info, see [`Database::bulk_load`].
     ///
-    /// [`clear`]: crate::Database::clear
-    /// [`..`]: std::ops::RangeFull
+    /// # Panics
+    ///
+    /// Panics if `entries_per_txn` is zero.
     ///
     /// ```
     /// # use std::fs;
     /// # use std::path::Path;
     /// # use heed::EnvOpenOptions;
-    /// use heed::Database;
+    /// use heed::{BulkLoadOptions, Database};
     /// use heed::types::*;
     /// use heed::byteorder::BigEndian;
     ///
@@ -2632,51 +4275,111 @@ impl<KC, DC, C, CDUP> Database<KC, DC, C, CDUP> {
     /// #     .max_dbs(3000)
     /// #     .open(dir.path())?
     /// # };
-    /// type BEI32 = I32<BigEndian>;
+    /// type BEU32 = U32<BigEndian>;
     ///
     /// let mut wtxn = env.write_txn()?;
-    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("iter-i32"))?;
-    ///
+    /// let db: Database<BEU32, Unit> = env.create_database(&mut wtxn, Some("bulk-load"))?;
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &42, "i-am-forty-two")?;
-    /// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
-    /// db.put(&mut wtxn, &13, "i-am-thirteen")?;
-    /// db.put(&mut wtxn, &521, "i-am-five-hundred-and-twenty-one")?;
-    ///
-    /// let range = 27..=42;
-    /// let ret = db.delete_range(&mut wtxn, &range)?;
-    /// assert_eq!(ret, 2);
-    ///
+    /// wtxn.commit()?;
     ///
-    /// let mut iter = db.iter(&wtxn)?;
-    /// assert_eq!(iter.next().transpose()?, Some((13, "i-am-thirteen")));
-    /// assert_eq!(iter.next().transpose()?, Some((521, "i-am-five-hundred-and-twenty-one")));
-    /// assert_eq!(iter.next().transpose()?, None);
+    /// let entries = (0..10_000u32).rev().map(|i| (i, ()));
+    /// db.bulk_load_in_chunks(&env, &BulkLoadOptions::new(), 1000, entries)?;
     ///
-    /// drop(iter);
-    /// wtxn.commit()?;
+    /// let rtxn = env.read_txn()?;
+    /// assert_eq!(db.len(&rtxn)?, 10_000);
     /// # Ok(()) }
     /// ```
-    pub fn delete_range<'a, 'txn, R>(&self, txn: &'txn mut RwTxn, range: &'a R) -> Result<usize>
+    pub fn bulk_load_in_chunks<T, I, K, V, KE, DE>(
+        &self,
+        env: &Env<T>,
+        options: &BulkLoadOptions,
+        entries_per_txn: usize,
+        entries: I,
+    ) -> Result<()>
     where
-        KC: BytesEncode<'a> + BytesDecode<'txn>,
+        I: IntoIterator<Item = (K, V)>,
+        K: Borrow<KE>,
+        V: Borrow<DE>,
+        KE: ?Sized,
+        DE: ?Sized,
+        KC: for<'a> BytesEncode<'a, EItem = KE>,
+        DC: for<'a> BytesEncode<'a, EItem = DE>,
         C: Comparator,
-        R: RangeBounds<KC::EItem>,
+        CDUP: Comparator,
     {
-        assert_eq_env_db_txn!(self, txn);
+        assert!(entries_per_txn > 0, "The number of entries per transaction must not be zero");
+
+        // Sorting can take a while, the other writers must not wait for it.
+        let mut sorted = {
+            let rtxn = env.read_txn()?;
+            let rtxn = rtxn.as_any_tls();
+            assert_eq_env_db_txn!(self, rtxn);
+            self.sort_bulk_entries(rtxn, options, entries)?
+        };
 
-        let mut count = 0;
-        let mut iter = self.remap_data_type::<DecodeIgnore>().range_mut(txn, range)?;
+        let mut wtxn = env.write_txn()?;
+        self.check_bulk_append(&wtxn, &sorted)?;
+        loop {
+            let mut cursor = RwCursor::new(&wtxn, self.dbi)?;
+            let written = sorted.append_to(&mut cursor, entries_per_txn)?;
+            drop(cursor);
+            wtxn.commit()?;
 
-        while iter.next().is_some() {
-            // safety: We do not keep any reference from the database while using `del_current`.
-            //         The user can't keep any reference inside of the database as we ask for a
-            //         mutable reference to the `txn`.
-            unsafe { iter.del_current()? };
-            count += 1;
+            if written < entries_per_txn {
+                return Ok(());
+            }
+            wtxn = env.write_txn()?;
         }
+    }
 
-        Ok(count)
+    /// Encodes and sorts the entries to bulk load, according to the comparators of the database.
+    fn sort_bulk_entries<I, K, V, KE, DE>(
+        &self,
+        txn: &RoTxn,
+        options: &BulkLoadOptions,
+        entries: I,
+    ) -> Result<SortedEntries<C, CDUP>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Borrow<KE>,
+        V: Borrow<DE>,
+        KE: ?Sized,
+        DE: ?Sized,
+        KC: for<'a> BytesEncode<'a, EItem = KE>,
+        DC: for<'a> BytesEncode<'a, EItem = DE>,
+        C: Comparator,
+        CDUP: Comparator,
+    {
+        let mut sorter = Sorter::<C, CDUP>::new(options, self.is_dup_sort(txn)?);
+        let mut key_buffer = Vec::new();
+        let mut data_buffer = Vec::new();
+
+        for (key, data) in entries {
+            key_buffer.clear();
+            encode_into::<KC>(key.borrow(), &mut key_buffer)
+                .map_err(self.operation(txn, "bulk_load").encoding(None))?;
+            data_buffer.clear();
+            encode_into::<DC>(data.borrow(), &mut data_buffer)
+                .map_err(self.operation(txn, "bulk_load").encoding(Some(&key_buffer)))?;
+            sorter.insert(&key_buffer, &data_buffer)?;
+        }
+
+        Ok(sorter.into_sorted_entries()?)
+    }
+
+    /// Makes sure the sorted entries can be appended after the last key of the database.
+    fn check_bulk_append(&self, txn: &RoTxn, sorted: &SortedEntries<C, CDUP>) -> Result<()>
+    where
+        C: Comparator,
+        CDUP: Comparator,
+    {
+        let Some(first_key) = sorted.peek_key() else { return Ok(()) };
+        match self.remap_types::<Bytes, DecodeIgnore>().last(txn)? {
+            Some((last_key, ())) if C::compare(first_key, last_key).is_le() => {
+                Err(self.operation(txn, "bulk_load").error(MdbError::KeyExist, Some(first_key)))
+            }
+            _ => Ok(()),
+        }
     }
 
     /// Deletes all key/value pairs in this database.
@@ -2884,6 +4587,65 @@ impl<KC, DC, C, CDUP> fmt::Debug for Database<KC, DC, C, CDUP> {
     }
 }
 
+/// Merges the operands into the value of the given key with the merge function `M`
+/// and writes the merged value in place of the previous one.
+fn merge_on_key<M: MergeFunction>(
+    cursor: &mut RwCursor,
+    operation: &'static str,
+    key: &[u8],
+    operands: &[&[u8]],
+    output: &mut Vec<u8>,
+) -> Result<()> {
+    let existing = match cursor.move_on_key(key)? {
+        true => cursor.current()?.map(|(_, data)| data),
+        false => None,
+    };
+
+    output.clear();
+    M::merge(key, existing, operands, output)
+        .map_err(|e| cursor.error_context(Error::Encoding(e), operation, Some(key)))?;
+
+    // The existing value is not used anymore, it can be overwritten.
+    let write_func = |reserved: &mut ReservedSpace| reserved.write_all(output);
+    unsafe {
+        match existing {
+            Some(_) => cursor.put_current_reserved(key, output.len(), write_func)?,
+            None => cursor.put_current_reserved_with_flags(
+                PutFlags::empty(),
+                key,
+                output.len(),
+                write_func,
+            )?,
+        };
+    }
+
+    Ok(())
+}
+
+/// Replaces the `existing` value of the key the cursor is positioned on by the `new` one,
+/// deletes the entry when there is no new value or leaves it untouched when nothing changes.
+///
+/// Returns `true` if the entry was written or deleted.
+fn replace_current(
+    cursor: &mut RwCursor,
+    key: &[u8],
+    existing: Option<&[u8]>,
+    new: Option<&[u8]>,
+) -> Result<bool> {
+    // The existing value is not used after the write, it can be overwritten.
+    unsafe {
+        match (existing, new) {
+            (Some(existing), Some(new)) if existing == new => Ok(false),
+            (Some(_), Some(new)) => cursor.put_current(key, new),
+            (None, Some(new)) => {
+                cursor.put_current_with_flags(PutFlags::empty(), key, new).map(|()| true)
+            }
+            (Some(_), None) => cursor.del_current(),
+            (None, None) => Ok(false),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use byteorder::*;
@@ -2960,4 +4722,425 @@ mod tests {
         assert_eq!(i, range.end - range.start);
         Ok(())
     }
+
+    #[test]
+    fn get_many_with_integer_keys() -> Result<()> {
+        type NEU32 = U32<NativeEndian>;
+
+        let dir = tempfile::tempdir()?;
+        let env = unsafe { EnvOpenOptions::new().open(dir.path())? };
+        let mut txn = env.write_txn()?;
+        let db = env
+            .database_options()
+            .types::<NEU32, NEU32>()
+            .key_comparator::<IntegerComparator>()
+            .create(&mut txn)?;
+
+        for i in (0..2000).step_by(2) {
+            db.put(&mut txn, &i, &(i * 10))?;
+        }
+
+        // Keys in a scrambled order, with duplicates and missing ones.
+        let keys: Vec<u32> = (0..3000).map(|i| (i * 7919) % 2100).chain([4, 4, 5]).collect();
+        let expected = keys.iter().map(|key| db.get(&txn, key)).collect::<Result<Vec<_>>>()?;
+        assert!(expected.iter().any(Option::is_none));
+        assert_eq!(db.get_many(&txn, &keys)?, expected);
+
+        let values = db.get_many_sorted(&txn, &keys)?.collect::<Result<Vec<_>>>()?;
+        assert_eq!(values, expected);
+
+        let mut sorted = keys.clone();
+        sorted.sort_unstable();
+        let expected = sorted.iter().map(|key| db.get(&txn, key)).collect::<Result<Vec<_>>>()?;
+        let values = db.get_many_sorted(&txn, &sorted)?.collect::<Result<Vec<_>>>()?;
+        assert_eq!(values, expected);
+
+        assert!(db.get_many(&txn, &[])?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn bulk_load() -> Result<()> {
+        type BEU32 = U32<BigEndian>;
+
+        let dir = tempfile::tempdir()?;
+        let temp_dir = tempfile::tempdir()?;
+        let env = unsafe { EnvOpenOptions::new().max_dbs(3).open(dir.path())? };
+        let options = BulkLoadOptions::new().max_memory(1024).temp_dir(temp_dir.path());
+        let entries: Vec<(u32, u32)> = (0..5000).map(|i| ((i * 7919) % 1000, i % 7)).collect();
+
+        let mut txn = env.write_txn()?;
+        let db = env.create_database::<BEU32, BEU32>(&mut txn, Some("last-wins"))?;
+        db.bulk_load_with_options(&mut txn, &options, entries.iter().copied())?;
+        let expected: std::collections::BTreeMap<_, _> = entries.iter().copied().collect();
+        let loaded = db.iter(&txn)?.collect::<Result<Vec<_>>>()?;
+        assert_eq!(loaded, expected.into_iter().collect::<Vec<_>>());
+
+        let db = env
+            .database_options()
+            .types::<BEU32, BEU32>()
+            .flags(DatabaseFlags::DUP_SORT)
+            .name("dup-sort")
+            .create(&mut txn)?;
+        txn.commit()?;
+
+        db.bulk_load_in_chunks(&env, &options, 100, entries.iter().copied())?;
+        let expected: std::collections::BTreeSet<_> = entries.iter().copied().collect();
+        let txn = env.read_txn()?;
+        let loaded = db.iter(&txn)?.collect::<Result<Vec<_>>>()?;
+        assert_eq!(loaded, expected.into_iter().collect::<Vec<_>>());
+        drop(txn);
+
+        // The temporary files are all removed.
+        assert_eq!(std::fs::read_dir(temp_dir.path())?.count(), 0);
+
+        // Loading keys that don't come after the last one fails before writing anything.
+        let mut txn = env.write_txn()?;
+        let len = db.len(&txn)?;
+        let error = db.bulk_load(&mut txn, [(999, 7), (1000, 0), (0, 0)]).unwrap_err();
+        assert!(matches!(error, Error::Mdb(MdbError::KeyExist)));
+        assert_eq!(db.len(&txn)?, len);
+        db.bulk_load(&mut txn, [(1000, 0), (1001, 0)])?;
+        assert_eq!(db.len(&txn)?, len + 2);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn par_for_each_without_reader_slots() -> Result<()> {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        type BEU64 = U64<BigEndian>;
+
+        let dir = tempfile::tempdir()?;
+        let env = unsafe {
+            EnvOpenOptions::new().read_txn_without_tls().max_readers(2).open(dir.path())?
+        };
+        let mut txn = env.write_txn()?;
+        let db = env.create_database::<BEU64, BEU64>(&mut txn, None)?;
+        for i in 0..1000 {
+            db.put(&mut txn, &i, &i)?;
+        }
+        txn.commit()?;
+
+        // Only two of the eight ranges get a read transaction, they scan every key.
+        let sum = AtomicU64::new(0);
+        db.par_for_each(&env, 8, |_key, value| -> Result<()> {
+            sum.fetch_add(value, Ordering::Relaxed);
+            Ok(())
+        })?;
+        assert_eq!(sum.into_inner(), 999 * 1000 / 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn merge() -> Result<()> {
+        type BEU32 = U32<BigEndian>;
+
+        /// Appends the operands to the existing value.
+        enum Concat {}
+
+        impl MergeFunction for Concat {
+            fn merge(
+                _key: &[u8],
+                existing: Option<&[u8]>,
+                operands: &[&[u8]],
+                output: &mut Vec<u8>,
+            ) -> std::result::Result<(), BoxedError> {
+                output.extend_from_slice(existing.unwrap_or_default());
+                operands.iter().for_each(|operand| output.extend_from_slice(operand));
+                Ok(())
+            }
+        }
+
+        let dir = tempfile::tempdir()?;
+        let env = unsafe { EnvOpenOptions::new().max_dbs(3).open(dir.path())? };
+        let mut txn = env.write_txn()?;
+
+        let db = env.create_database::<BEU32, BEU32>(&mut txn, Some("counters"))?;
+        db.merge::<IntegerAdd<BEU32>>(&mut txn, &1, &5)?;
+        db.merge::<IntegerAdd<BEU32>>(&mut txn, &1, &6)?;
+        db.merge::<IntegerAdd<BEU32>>(&mut txn, &2, &u32::MAX)?;
+        assert_eq!(db.get(&txn, &1)?, Some(11));
+        assert!(matches!(db.merge::<IntegerAdd<BEU32>>(&mut txn, &2, &1), Err(Error::Encoding(_))));
+        assert_eq!(db.get(&txn, &2)?, Some(u32::MAX));
+
+        let db = env.create_database::<BEU32, Str>(&mut txn, Some("strings"))?;
+        db.put(&mut txn, &2, "b")?;
+        let entries = [(1, "a"), (2, "c"), (2, "d"), (3, "e"), (1, "f"), (1, "g")];
+        db.merge_sorted::<Concat, _>(&mut txn, entries.iter().map(|(k, v)| (k, *v)))?;
+        let merged = db.iter(&txn)?.collect::<Result<Vec<_>>>()?;
+        assert_eq!(merged, [(1, "afg"), (2, "bcd"), (3, "e")]);
+
+        let db = env
+            .database_options()
+            .types::<BEU32, BEU32>()
+            .flags(DatabaseFlags::DUP_SORT)
+            .name("dup-sort")
+            .create(&mut txn)?;
+        let error = db.merge::<IntegerAdd<BEU32>>(&mut txn, &1, &1).unwrap_err();
+        assert!(matches!(error, Error::Mdb(MdbError::Incompatible)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn update_and_compare_and_swap() -> Result<()> {
+        type BEU32 = U32<BigEndian>;
+
+        let dir = tempfile::tempdir()?;
+        let env = unsafe { EnvOpenOptions::new().max_dbs(2).open(dir.path())? };
+        let mut txn = env.write_txn()?;
+
+        let db = env.create_database::<BEU32, Str>(&mut txn, Some("strings"))?;
+        assert!(db.update(&mut txn, &1, |value| value.map(str::to_uppercase).or(Some("a".into())))?);
+        assert!(db.update(&mut txn, &1, |value| value.map(str::to_uppercase))?);
+        assert!(!db.update(&mut txn, &1, |value| value.map(str::to_uppercase))?);
+        assert_eq!(db.get(&txn, &1)?, Some("A"));
+        assert!(db.update(&mut txn, &1, |value| value.map(|v| v.repeat(3)))?);
+        assert_eq!(db.get(&txn, &1)?, Some("AAA"));
+        assert!(db.update(&mut txn, &1, |_| None::<&str>)?);
+        assert!(!db.update(&mut txn, &1, |_| None::<&str>)?);
+        assert_eq!(db.get(&txn, &1)?, None);
+
+        assert!(db.compare_and_swap(&mut txn, &2, None, Some("b"))?);
+        assert!(!db.compare_and_swap(&mut txn, &2, Some("c"), Some("d"))?);
+        assert!(db.compare_and_swap(&mut txn, &2, Some("b"), Some("bb"))?);
+        assert_eq!(db.get(&txn, &2)?, Some("bb"));
+        assert!(db.compare_and_swap(&mut txn, &2, Some("bb"), None)?);
+        assert_eq!(db.get(&txn, &2)?, None);
+
+        let db = env
+            .database_options()
+            .types::<BEU32, BEU32>()
+            .flags(DatabaseFlags::DUP_SORT)
+            .name("dup-sort")
+            .create(&mut txn)?;
+        let error = db.compare_and_swap(&mut txn, &1, None, Some(&1)).unwrap_err();
+        assert!(matches!(error, Error::Mdb(MdbError::Incompatible)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn error_context() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let env = unsafe { EnvOpenOptions::new().max_dbs(1).open(dir.path())? };
+        let mut txn = env.write_txn()?;
+        let db = env.create_database::<Bytes, Bytes>(&mut txn, Some("strings"))?;
+        let long_key = [b'a'; 100];
+        db.put(&mut txn, &long_key, &[0xff])?;
+
+        let db = db.remap_data_type::<Str>();
+        let error = db.get(&txn, &long_key).unwrap_err();
+        assert!(matches!(error, Error::Decoding(_)));
+        assert!(error.context().is_none());
+
+        env.enable_error_context();
+        let error = db.iter(&txn)?.next().unwrap().unwrap_err();
+        assert!(matches!(error, Error::Decoding(_)));
+        let context = error.context().unwrap();
+        assert_eq!(context.database_name(), Some("strings"));
+        assert_eq!(context.dbi(), db.dbi);
+        assert_eq!(context.operation(), "iter");
+        assert_eq!(context.key(), Some(&long_key[..64]));
+        assert!(context.is_key_truncated());
+        assert!(error.to_string().ends_with(&format!(
+            "(iter on database \"strings\" with key 0x{}... (100 bytes))",
+            "61".repeat(64)
+        )));
+
+        // The LMDB errors are left untouched, the I/O errors keep their kind.
+        let error = db.put_with_flags(&mut txn, PutFlags::NO_OVERWRITE, &long_key, "").unwrap_err();
+        assert!(matches!(error, Error::Mdb(MdbError::KeyExist)));
+        assert!(error.context().is_none());
+
+        let error = txn.error_context(MdbError::Other(5).into(), db.dbi, "get", None);
+        let expected = io::Error::from_raw_os_error(5);
+        match &error {
+            Error::Io(error) => assert_eq!(error.kind(), expected.kind()),
+            error => panic!("unexpected error {error:?}"),
+        }
+        assert_eq!(error.context().unwrap().operation(), "get");
+
+        Ok(())
+    }
+
+    #[test]
+    fn duplicates_range() -> Result<()> {
+        type BEU32 = U32<BigEndian>;
+
+        let dir = tempfile::tempdir()?;
+        let env = unsafe { EnvOpenOptions::new().max_dbs(2).open(dir.path())? };
+        let mut txn = env.write_txn()?;
+        let db = env
+            .database_options()
+            .types::<BEU32, BEU32>()
+            .flags(DatabaseFlags::DUP_SORT)
+            .name("dup-sort")
+            .create(&mut txn)?;
+
+        for data in [1, 3, 5, 7] {
+            db.put(&mut txn, &10, &data)?;
+        }
+        db.put(&mut txn, &11, &0)?;
+        db.put(&mut txn, &9, &100)?;
+
+        let values = |range: (Bound<u32>, Bound<u32>)| -> Result<Vec<u32>> {
+            let iter = db.duplicates_range(&txn, &10, &range)?;
+            iter.map(|entry| entry.map(|(_, data)| data)).collect()
+        };
+        let rev_values = |range: (Bound<u32>, Bound<u32>)| -> Result<Vec<u32>> {
+            let iter = db.rev_duplicates_range(&txn, &10, &range)?;
+            iter.map(|entry| entry.map(|(_, data)| data)).collect()
+        };
+
+        use Bound::{Excluded, Included, Unbounded};
+        assert_eq!(values((Unbounded, Unbounded))?, [1, 3, 5, 7]);
+        assert_eq!(values((Included(3), Included(5)))?, [3, 5]);
+        assert_eq!(values((Excluded(3), Excluded(7)))?, [5]);
+        assert_eq!(values((Included(4), Unbounded))?, [5, 7]);
+        assert!(values((Excluded(7), Unbounded))?.is_empty());
+        assert_eq!(rev_values((Unbounded, Unbounded))?, [7, 5, 3, 1]);
+        assert_eq!(rev_values((Included(3), Included(5)))?, [5, 3]);
+        assert_eq!(rev_values((Excluded(1), Excluded(7)))?, [5, 3]);
+        assert_eq!(rev_values((Unbounded, Included(100)))?, [7, 5, 3, 1]);
+        assert!(rev_values((Unbounded, Excluded(1)))?.is_empty());
+
+        let iter = db.duplicates_range(&txn, &12, &(..))?;
+        assert_eq!(iter.count(), 0);
+        let iter = db.rev_duplicates_range(&txn, &12, &(..))?;
+        assert_eq!(iter.count(), 0);
+
+        assert_eq!(db.count_duplicates(&txn, &10)?, 4);
+        assert_eq!(db.count_duplicates(&txn, &11)?, 1);
+        assert_eq!(db.count_duplicates(&txn, &12)?, 0);
+
+        let mut iter = db.iter(&txn)?.move_between_keys();
+        let mut counts = Vec::new();
+        while let Some((key, _)) = iter.next().transpose()? {
+            counts.push((key, iter.dup_count()?));
+        }
+        drop(iter);
+        assert_eq!(counts, [(9, 1), (10, 4), (11, 1)]);
+
+        assert!(db.contains_duplicate(&txn, &10, &5)?);
+        assert!(!db.contains_duplicate(&txn, &10, &6)?);
+        assert!(!db.contains_duplicate(&txn, &12, &5)?);
+
+        let db = env.create_database::<BEU32, BEU32>(&mut txn, Some("no-dup-sort"))?;
+        db.put(&mut txn, &10, &5)?;
+        assert!(matches!(
+            db.contains_duplicate(&txn, &10, &5),
+            Err(Error::Mdb(MdbError::Incompatible))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn duplicates_fixed() -> Result<()> {
+        type BEU32 = U32<BigEndian>;
+
+        let dir = tempfile::tempdir()?;
+        let env = unsafe { EnvOpenOptions::new().max_dbs(2).open(dir.path())? };
+        let mut txn = env.write_txn()?;
+        let db = env
+            .database_options()
+            .types::<BEU32, BEU32>()
+            .flags(DatabaseFlags::DUP_SORT | DatabaseFlags::DUP_FIXED)
+            .name("dup-fixed")
+            .create(&mut txn)?;
+
+        // Enough values to span many pages.
+        let values: Vec<u32> = (0..10_000).rev().collect();
+        db.put_duplicates_fixed(&mut txn, &10, &values)?;
+        db.put_duplicates_fixed(&mut txn, &10, &[])?;
+        db.put(&mut txn, &11, &42)?;
+        assert_eq!(db.len(&txn)?, 10_001);
+
+        let mut pages = 0;
+        let mut bytes = Vec::new();
+        for page in db.get_duplicates_fixed(&txn, &10)?.unwrap() {
+            bytes.extend_from_slice(page?);
+            pages += 1;
+        }
+        assert!(pages > 1);
+        let expected: Vec<u8> = (0..10_000u32).flat_map(u32::to_be_bytes).collect();
+        assert_eq!(bytes, expected);
+
+        let pages: Vec<_> = db.get_duplicates_fixed(&txn, &11)?.unwrap().collect::<Result<_>>()?;
+        assert_eq!(pages, [&42u32.to_be_bytes()[..]]);
+        assert!(db.get_duplicates_fixed(&txn, &12)?.is_none());
+
+        let db = env.create_database::<BEU32, BEU32>(&mut txn, Some("no-dup-fixed"))?;
+        assert!(matches!(
+            db.put_duplicates_fixed(&mut txn, &10, &[1, 2]),
+            Err(Error::Mdb(MdbError::Incompatible))
+        ));
+        assert!(matches!(
+            db.get_duplicates_fixed(&txn, &10),
+            Err(Error::Mdb(MdbError::Incompatible))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn put_into_reused_buffers() -> Result<()> {
+        type BEU64 = U64<BigEndian>;
+
+        /// Claims a size and fails after writing part of the value.
+        enum Failing {}
+
+        impl BytesEncode<'_> for Failing {
+            type EItem = ();
+
+            fn bytes_encode(_item: &()) -> std::result::Result<Cow<'_, [u8]>, BoxedError> {
+                Err("failing codec".into())
+            }
+        }
+
+        impl BytesEncodeInto<'_> for Failing {
+            fn size_hint(_item: &()) -> Option<usize> {
+                Some(8)
+            }
+
+            fn bytes_encode_into<W: Write>(
+                _item: &(),
+                writer: &mut W,
+            ) -> std::result::Result<(), BoxedError> {
+                writer.write_all(&[1, 2])?;
+                Err("failing codec".into())
+            }
+        }
+
+        let dir = tempfile::tempdir()?;
+        let env = unsafe { EnvOpenOptions::new().max_dbs(2).open(dir.path())? };
+        let mut txn = env.write_txn()?;
+        let db = env.create_database::<Str, BEU64>(&mut txn, Some("sized"))?;
+        let dup_db = env
+            .database_options()
+            .types::<Str, BEU64>()
+            .name("sized-dup")
+            .flags(DatabaseFlags::DUP_SORT)
+            .create(&mut txn)?;
+
+        for i in 0..100 {
+            db.put_into(&mut txn, "hello", &i)?;
+            dup_db.put_into(&mut txn, "hello", &i)?;
+        }
+
+        assert_eq!(db.get(&txn, "hello")?, Some(99));
+        assert_eq!(dup_db.get_duplicates(&txn, "hello")?.unwrap().count(), 100);
+
+        // A value that fails to encode leaves the previous one untouched.
+        let failing = db.remap_data_type::<Failing>();
+        assert!(matches!(failing.put_into(&mut txn, "hello", &()), Err(Error::Encoding(_))));
+        assert_eq!(db.get(&txn, "hello")?, Some(99));
+        Ok(())
+    }
 }
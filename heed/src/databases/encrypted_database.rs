@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::ops::RangeBounds;
 use std::{any, fmt};
 
@@ -1962,6 +1963,121 @@ impl<KC, DC, C, CDUP> EncryptedDatabase<KC, DC, C, CDUP> {
         self.inner.get_or_put_reserved_with_flags(txn, flags, key, data_size, write_func)
     }
 
+    /// Reads the value of a key and replaces it with the one returned by the given closure,
+    /// positioning the cursor on the key only once.
+    ///
+    /// The closure receives the current value, or `None` if the key doesn't exist, and returns
+    /// the new value to write or `None` to delete the entry. The new value is written in place
+    /// when it has the same size as the previous one and the entry is left untouched when the
+    /// encoded value doesn't change.
+    ///
+    /// Returns `true` if the entry was written or deleted.
+    /// Returns an [`MdbError::Incompatible`] error if the database supports duplicate data items.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<Str, BEI32> = env.create_database(&mut wtxn, Some("update"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, "stock", &3)?;
+    ///
+    /// // Take one item out of the stock and remove the entry once it is empty.
+    /// let take_one = |stock: Option<i32>| stock.map(|n| n - 1).filter(|&n| n > 0);
+    /// assert!(db.update(&mut wtxn, "stock", take_one)?);
+    /// assert_eq!(db.get(&mut wtxn, "stock")?, Some(2));
+    ///
+    /// db.update(&mut wtxn, "stock", take_one)?;
+    /// db.update(&mut wtxn, "stock", take_one)?;
+    /// assert_eq!(db.get(&mut wtxn, "stock")?, None);
+    ///
+    /// // Nothing is written when the key doesn't exist and the closure returns `None`.
+    /// assert!(!db.update(&mut wtxn, "stock", take_one)?);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn update<'a, DE, V, F>(&self, txn: &mut RwTxn, key: &'a KC::EItem, f: F) -> Result<bool>
+    where
+        KC: BytesEncode<'a>,
+        DC: for<'b> BytesDecode<'b> + for<'b> BytesEncode<'b, EItem = DE>,
+        DE: ?Sized,
+        V: Borrow<DE>,
+        F: for<'b> FnOnce(Option<<DC as BytesDecode<'b>>::DItem>) -> Option<V>,
+    {
+        self.inner.update(txn, key, f)
+    }
+
+    /// Replaces the value of a key with `new` only if its current value is `expected`,
+    /// positioning the cursor on the key only once.
+    ///
+    /// An `expected` value of `None` means that the key must not exist and a `new` value
+    /// of `None` deletes the entry. The values are compared on their encoded bytes.
+    ///
+    /// Returns `true` if the current value was the expected one.
+    /// Returns an [`MdbError::Incompatible`] error if the database supports duplicate data items.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<Str, Str> = env.create_database(&mut wtxn, Some("compare-and-swap"))?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// assert!(db.compare_and_swap(&mut wtxn, "status", None, Some("pending"))?);
+    /// assert!(!db.compare_and_swap(&mut wtxn, "status", None, Some("pending"))?);
+    ///
+    /// assert!(db.compare_and_swap(&mut wtxn, "status", Some("pending"), Some("done"))?);
+    /// assert!(!db.compare_and_swap(&mut wtxn, "status", Some("pending"), None)?);
+    /// assert_eq!(db.get(&mut wtxn, "status")?, Some("done"));
+    ///
+    /// assert!(db.compare_and_swap(&mut wtxn, "status", Some("done"), None)?);
+    /// assert_eq!(db.get(&mut wtxn, "status")?, None);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn compare_and_swap<'a>(
+        &self,
+        txn: &mut RwTxn,
+        key: &'a KC::EItem,
+        expected: Option<&'a DC::EItem>,
+        new: Option<&'a DC::EItem>,
+    ) -> Result<bool>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+    {
+        self.inner.compare_and_swap(txn, key, expected, new)
+    }
+
     /// Deletes an entry or every duplicate data items of a key
     /// if the database supports duplicate data items.
     ///
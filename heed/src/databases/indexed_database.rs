@@ -0,0 +1,419 @@
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::{any, fmt};
+
+use types::{Bytes, DecodeIgnore};
+
+use super::encode_into;
+use crate::bulk_load::Sorter;
+use crate::envs::DefaultComparator;
+use crate::*;
+
+/// Computes the encoded index keys of an encoded primary value.
+type IndexKeysFn = dyn Fn(&[u8], &mut Vec<Vec<u8>>) -> Result<()> + Send + Sync;
+
+/// The encoded index keys to remove and to add, for every index.
+type IndexChanges = Vec<(Vec<Vec<u8>>, Vec<Vec<u8>>)>;
+
+/// A primary [`Database`] whose secondary indexes are kept up to date by its write methods.
+///
+/// Every secondary index is a `DUP_SORT` database mapping the index keys computed
+/// from a value to the keys of the primary entries with this value. The indexes are
+/// added with [`Self::add_index`] and written in the same transaction as the primary
+/// entries by [`Self::put`] and [`Self::delete`].
+///
+/// Writing to the primary database or to an index without going through this
+/// handle desynchronizes them, [`Self::rebuild_index`] can fix an index afterward.
+///
+/// ```
+/// # use heed::EnvOpenOptions;
+/// use heed::{Database, DatabaseFlags, IndexedDatabase};
+/// use heed::types::*;
+/// use heed::byteorder::BigEndian;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let dir = tempfile::tempdir()?;
+/// # let env = unsafe { EnvOpenOptions::new()
+/// #     .map_size(10 * 1024 * 1024) // 10MB
+/// #     .max_dbs(3000)
+/// #     .open(dir.path())?
+/// # };
+/// type BEU32 = U32<BigEndian>;
+///
+/// let mut wtxn = env.write_txn()?;
+/// let primary: Database<BEU32, Str> = env.create_database(&mut wtxn, Some("words"))?;
+/// let by_length = env
+///     .database_options()
+///     .types::<BEU32, Bytes>()
+///     .flags(DatabaseFlags::DUP_SORT)
+///     .name("words-by-length")
+///     .create(&mut wtxn)?;
+///
+/// let mut words = IndexedDatabase::new(primary);
+/// let by_length = words.add_index(by_length, |word: &&str| [word.len() as u32]);
+///
+/// # primary.clear(&mut wtxn)?;
+/// words.put(&mut wtxn, &1, "hello")?;
+/// words.put(&mut wtxn, &2, "world")?;
+/// words.put(&mut wtxn, &3, "hi")?;
+/// assert_eq!(words.lookup_by(&wtxn, &by_length, &5)?, [(1, "hello"), (2, "world")]);
+///
+/// words.put(&mut wtxn, &2, "you")?;
+/// words.delete(&mut wtxn, &1)?;
+/// assert_eq!(words.lookup_by(&wtxn, &by_length, &5)?, []);
+/// assert_eq!(words.lookup_by(&wtxn, &by_length, &3)?, [(2, "you")]);
+///
+/// wtxn.commit()?;
+/// # Ok(()) }
+/// ```
+pub struct IndexedDatabase<KC, DC> {
+    primary: Database<KC, DC>,
+    indexes: Vec<Index>,
+}
+
+/// A secondary index of an [`IndexedDatabase`], created with [`IndexedDatabase::add_index`].
+pub struct SecondaryIndex<IK> {
+    position: usize,
+    db: Database<IK, Bytes>,
+}
+
+struct Index {
+    db: Database<Bytes, Bytes>,
+    keys: Arc<IndexKeysFn>,
+}
+
+impl<KC, DC> IndexedDatabase<KC, DC> {
+    /// Wraps a primary database that doesn't have any secondary index yet.
+    pub fn new(primary: Database<KC, DC>) -> IndexedDatabase<KC, DC> {
+        IndexedDatabase { primary, indexes: Vec::new() }
+    }
+
+    /// Returns the primary database, to read from it.
+    ///
+    /// Writing to it directly doesn't update the secondary indexes.
+    pub fn primary(&self) -> &Database<KC, DC> {
+        &self.primary
+    }
+
+    /// Adds a secondary index, the given database must be opened with the
+    /// [`DUP_SORT`](DatabaseFlags::DUP_SORT) flag.
+    ///
+    /// The function returns the keys under which a decoded value is indexed, there can be
+    /// none or many of them and they can't borrow from the value. The index is only written
+    /// by the next calls to the write methods, entries that are already in the primary
+    /// database are indexed by [`Self::rebuild_index`].
+    pub fn add_index<IK, IE, V, I, F>(
+        &mut self,
+        index: Database<IK, Bytes>,
+        keys: F,
+    ) -> SecondaryIndex<IK>
+    where
+        DC: for<'a> BytesDecode<'a> + 'static,
+        IK: for<'a> BytesEncode<'a, EItem = IE> + 'static,
+        IE: ?Sized,
+        V: Borrow<IE>,
+        I: IntoIterator<Item = V>,
+        F: for<'a> Fn(&<DC as BytesDecode<'a>>::DItem) -> I + Send + Sync + 'static,
+    {
+        let keys = move |data: &[u8], output: &mut Vec<Vec<u8>>| {
+            let data = DC::bytes_decode(data).map_err(Error::Decoding)?;
+            for key in keys(&data) {
+                let mut buffer = Vec::new();
+                encode_into::<IK>(key.borrow(), &mut buffer).map_err(Error::Encoding)?;
+                output.push(buffer);
+            }
+            Ok(())
+        };
+
+        let position = self.indexes.len();
+        self.indexes.push(Index { db: index.remap_key_type(), keys: Arc::new(keys) });
+        SecondaryIndex { position, db: index }
+    }
+
+    /// Retrieves the value associated with a key in the primary database.
+    pub fn get<'a, 'txn>(&self, txn: &'txn RoTxn, key: &'a KC::EItem) -> Result<Option<DC::DItem>>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesDecode<'txn>,
+    {
+        self.primary.get(txn, key)
+    }
+
+    /// Inserts a key-value pair in the primary database, replacing any previous value,
+    /// and updates the secondary indexes accordingly.
+    pub fn put<'a>(&self, txn: &mut RwTxn, key: &'a KC::EItem, data: &'a DC::EItem) -> Result<()>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+    {
+        let dbi = self.primary.dbi;
+        let key = KC::bytes_encode(key)
+            .map_err(|e| txn.error_context(Error::Encoding(e), dbi, "put", None))?;
+        let data = DC::bytes_encode(data)
+            .map_err(|e| txn.error_context(Error::Encoding(e), dbi, "put", Some(&key)))?;
+
+        let (_, changes) = self.index_changes(txn, &key, Some(&data), "put")?;
+        self.apply_index_changes(txn, &key, changes)?;
+        self.primary.remap_types::<Bytes, Bytes>().put(txn, &key, &data)
+    }
+
+    /// Deletes an entry of the primary database and its secondary index keys.
+    ///
+    /// If the entry does not exist, then `false` is returned.
+    pub fn delete<'a>(&self, txn: &mut RwTxn, key: &'a KC::EItem) -> Result<bool>
+    where
+        KC: BytesEncode<'a>,
+    {
+        let key = KC::bytes_encode(key)
+            .map_err(|e| txn.error_context(Error::Encoding(e), self.primary.dbi, "delete", None))?;
+
+        let (found, changes) = self.index_changes(txn, &key, None, "delete")?;
+        if !found {
+            return Ok(false);
+        }
+
+        self.apply_index_changes(txn, &key, changes)?;
+        self.primary.remap_types::<Bytes, Bytes>().delete(txn, &key)
+    }
+
+    /// Returns the entries of the primary database indexed under the given key
+    /// by a secondary index, ordered by primary key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index was not added to this database.
+    pub fn lookup_by<'a, 'txn, IK>(
+        &self,
+        txn: &'txn RoTxn,
+        index: &SecondaryIndex<IK>,
+        key: &'a IK::EItem,
+    ) -> Result<Vec<(KC::DItem, DC::DItem)>>
+    where
+        IK: BytesEncode<'a>,
+        KC: BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
+        self.index(index);
+
+        let Some(primary_keys) = index.db.get_duplicates(txn, key)? else { return Ok(Vec::new()) };
+        let primary_keys = primary_keys
+            .remap_key_type::<DecodeIgnore>()
+            .map(|result| result.map(|((), primary_key)| primary_key))
+            .collect::<Result<Vec<_>>>()?;
+
+        // The primary keys of an index key are sorted, they are all looked up with one cursor.
+        let primary = self.primary.remap_types::<Bytes, Bytes>();
+        let values = primary.get_many_sorted(txn, primary_keys.iter().copied())?;
+
+        let mut entries = Vec::with_capacity(primary_keys.len());
+        for (primary_key, value) in primary_keys.iter().zip(values) {
+            let context = |error| txn.error_context(error, self.primary.dbi, "lookup_by", None);
+            // An index entry without primary entry means the index must be rebuilt.
+            let value = value?.ok_or_else(|| context(Error::Mdb(MdbError::NotFound)))?;
+            let key = KC::bytes_decode(primary_key).map_err(|e| context(Error::Decoding(e)))?;
+            let data = DC::bytes_decode(value).map_err(|e| context(Error::Decoding(e)))?;
+            entries.push((key, data));
+        }
+
+        Ok(entries)
+    }
+
+    /// Clears a secondary index and indexes all the entries of the primary database again,
+    /// e.g. to backfill an index added to a database that already has entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index was not added to this database.
+    pub fn rebuild_index<IK>(&self, txn: &mut RwTxn, index: &SecondaryIndex<IK>) -> Result<()> {
+        let index = self.index(index);
+        if !index.db.is_dup_sort(txn)? {
+            return Err(Error::Mdb(MdbError::Incompatible));
+        }
+
+        // The index keys are sorted beforehand to append them to the cleared index.
+        let options = BulkLoadOptions::default();
+        let mut sorter = Sorter::<DefaultComparator, DefaultComparator>::new(&options, true);
+        let mut index_keys = Vec::new();
+        for result in self.primary.remap_types::<Bytes, Bytes>().iter(txn)? {
+            let (key, data) = result?;
+            index_keys.clear();
+            (index.keys)(data, &mut index_keys)
+                .map_err(|e| txn.error_context(e, self.primary.dbi, "rebuild_index", Some(key)))?;
+            for index_key in &index_keys {
+                sorter.insert(index_key, key)?;
+            }
+        }
+        let mut sorted = sorter.into_sorted_entries()?;
+
+        index.db.clear(txn)?;
+        let mut cursor = RwCursor::new(txn, index.db.dbi)?;
+        sorted.append_to(&mut cursor, usize::MAX)?;
+
+        Ok(())
+    }
+
+    fn index<IK>(&self, index: &SecondaryIndex<IK>) -> &Index {
+        match self.indexes.get(index.position) {
+            Some(found) if found.db.dbi == index.db.dbi => found,
+            _ => panic!("the secondary index was not added to this database"),
+        }
+    }
+
+    /// Returns whether the key exists and, for every index, the index keys
+    /// to remove and to add to replace the current value by the new one.
+    fn index_changes(
+        &self,
+        txn: &RoTxn,
+        key: &[u8],
+        new: Option<&[u8]>,
+        operation: &'static str,
+    ) -> Result<(bool, IndexChanges)> {
+        let current = self.primary.remap_types::<Bytes, Bytes>().get(txn, key)?;
+        let context = |error| txn.error_context(error, self.primary.dbi, operation, Some(key));
+
+        let mut changes = Vec::with_capacity(self.indexes.len());
+        let mut index_keys = Vec::new();
+        for index in &self.indexes {
+            if !index.db.is_dup_sort(txn)? {
+                return Err(Error::Mdb(MdbError::Incompatible));
+            }
+
+            let mut keys_of = |data: Option<&[u8]>| -> Result<BTreeSet<Vec<u8>>> {
+                index_keys.clear();
+                if let Some(data) = data {
+                    (index.keys)(data, &mut index_keys).map_err(context)?;
+                }
+                Ok(index_keys.drain(..).collect())
+            };
+
+            let current_keys = keys_of(current)?;
+            let new_keys = keys_of(new)?;
+            let removed = current_keys.difference(&new_keys).cloned().collect();
+            let added = new_keys.difference(&current_keys).cloned().collect();
+            changes.push((removed, added));
+        }
+
+        Ok((current.is_some(), changes))
+    }
+
+    fn apply_index_changes(
+        &self,
+        txn: &mut RwTxn,
+        key: &[u8],
+        changes: IndexChanges,
+    ) -> Result<()> {
+        for (index, (removed, added)) in self.indexes.iter().zip(changes) {
+            for index_key in removed {
+                index.db.delete_one_duplicate(txn, &index_key, key)?;
+            }
+            for index_key in added {
+                index.db.put(txn, &index_key, key)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<KC, DC> Clone for IndexedDatabase<KC, DC> {
+    fn clone(&self) -> Self {
+        let indexes = self
+            .indexes
+            .iter()
+            .map(|index| Index { db: index.db, keys: index.keys.clone() })
+            .collect();
+        IndexedDatabase { primary: self.primary, indexes }
+    }
+}
+
+impl<KC, DC> fmt::Debug for IndexedDatabase<KC, DC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IndexedDatabase")
+            .field("key_codec", &any::type_name::<KC>())
+            .field("data_codec", &any::type_name::<DC>())
+            .field("indexes", &self.indexes.len())
+            .finish()
+    }
+}
+
+impl<IK> Clone for SecondaryIndex<IK> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<IK> Copy for SecondaryIndex<IK> {}
+
+impl<IK> fmt::Debug for SecondaryIndex<IK> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SecondaryIndex")
+            .field("position", &self.position)
+            .field("key_codec", &any::type_name::<IK>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::BigEndian;
+    use heed_types::*;
+
+    use super::*;
+
+    #[test]
+    fn maintain_and_rebuild_indexes() -> Result<()> {
+        type BEU32 = U32<BigEndian>;
+
+        let dir = tempfile::tempdir()?;
+        let env = unsafe { EnvOpenOptions::new().max_dbs(4).open(dir.path())? };
+        let mut txn = env.write_txn()?;
+
+        let primary: Database<BEU32, Str> = env.create_database(&mut txn, Some("tags"))?;
+        let index_options = |name| {
+            let mut options = env.database_options().types::<Str, Bytes>();
+            options.flags(DatabaseFlags::DUP_SORT).name(name);
+            options
+        };
+        let by_tag_db = index_options("by-tag").create(&mut txn)?;
+        let by_first_db = index_options("by-first").create(&mut txn)?;
+
+        // The entries inserted before the second index is added are missing from it.
+        let mut db = IndexedDatabase::new(primary);
+        let by_tag = db.add_index(by_tag_db, |tags: &&str| {
+            tags.split(',').map(String::from).collect::<Vec<_>>()
+        });
+        db.put(&mut txn, &1, "red,blue")?;
+        db.put(&mut txn, &2, "blue,blue")?;
+
+        let by_first =
+            db.add_index(by_first_db, |tags: &&str| tags.split(',').next().map(String::from));
+        db.put(&mut txn, &3, "green,red")?;
+        assert_eq!(db.lookup_by(&txn, &by_tag, "blue")?, [(1, "red,blue"), (2, "blue,blue")]);
+        assert_eq!(db.lookup_by(&txn, &by_first, "red")?, []);
+        assert_eq!(by_tag_db.len(&txn)?, 5);
+
+        db.rebuild_index(&mut txn, &by_first)?;
+        assert_eq!(db.lookup_by(&txn, &by_first, "red")?, [(1, "red,blue")]);
+        assert_eq!(by_first_db.len(&txn)?, 3);
+
+        // Replacing and deleting entries only leaves the index keys of the remaining values.
+        db.put(&mut txn, &1, "yellow")?;
+        assert!(db.delete(&mut txn, &3)?);
+        assert!(!db.delete(&mut txn, &3)?);
+        assert_eq!(db.lookup_by(&txn, &by_tag, "red")?, []);
+        assert_eq!(db.lookup_by(&txn, &by_tag, "blue")?, [(2, "blue,blue")]);
+        assert_eq!(db.lookup_by(&txn, &by_first, "yellow")?, [(1, "yellow")]);
+        assert_eq!(by_tag_db.len(&txn)?, 2);
+        assert_eq!(by_first_db.len(&txn)?, 2);
+
+        // An index whose database doesn't support duplicates is refused.
+        let unique = env.create_database::<Str, Bytes>(&mut txn, Some("unique"))?;
+        let unique = db.add_index(unique, |tags: &&str| [tags.len().to_string()]);
+        let error = db.put(&mut txn, &4, "white").unwrap_err();
+        assert!(matches!(error, Error::Mdb(MdbError::Incompatible)));
+        let error = db.rebuild_index(&mut txn, &unique).unwrap_err();
+        assert!(matches!(error, Error::Mdb(MdbError::Incompatible)));
+
+        Ok(())
+    }
+}
@@ -1,10 +1,40 @@
 pub use database::{Database, DatabaseOpenOptions};
 #[cfg(master3)]
 pub use encrypted_database::{EncryptedDatabase, EncryptedDatabaseOpenOptions};
+pub use indexed_database::{IndexedDatabase, SecondaryIndex};
+pub use multimap_database::{MultimapDatabase, RoValues};
+pub use ttl_database::{Clock, ManualClock, SystemClock, TtlDatabase, TtlIter, TtlSweeper};
 
 mod database;
 #[cfg(master3)]
 mod encrypted_database;
+mod indexed_database;
+mod multimap_database;
+mod ttl_database;
+
+use crate::{BoxedError, BytesEncode, Env, Result, RoTxn, RwTxn};
+#[allow(unused)] // for cargo auto doc links
+use crate::{DatabaseFlags, EnvOpenOptions};
+
+/// A set of databases that are opened or created together.
+///
+/// It is generally implemented with the `Databases` derive macro, available
+/// with the `derive` feature, on a struct whose fields are [`Database`]s.
+/// Each field is a database named after the field by default.
+pub trait Databases: Sized {
+    /// The number of named databases in this set.
+    ///
+    /// It can be given to [`EnvOpenOptions::max_dbs`].
+    const COUNT: u32;
+
+    /// Opens all the databases of this set.
+    ///
+    /// Returns `None` if any of the databases doesn't exist.
+    fn open<T>(env: &Env<T>, rtxn: &RoTxn) -> Result<Option<Self>>;
+
+    /// Creates all the databases of this set, they can already exist.
+    fn create<T>(env: &Env<T>, wtxn: &mut RwTxn) -> Result<Self>;
+}
 
 /// Statistics for a database in the environment.
 #[derive(Debug, Clone, Copy)]
@@ -23,3 +53,12 @@ pub struct DatabaseStat {
     /// Number of data items.
     pub entries: usize,
 }
+
+/// Appends the item encoded with the codec `C` to the buffer.
+pub(crate) fn encode_into<'a, C: BytesEncode<'a>>(
+    item: &'a C::EItem,
+    buffer: &mut Vec<u8>,
+) -> std::result::Result<(), BoxedError> {
+    buffer.extend_from_slice(&C::bytes_encode(item)?);
+    Ok(())
+}
@@ -0,0 +1,263 @@
+use std::{any, fmt};
+
+use types::DecodeIgnore;
+
+use crate::envs::DefaultComparator;
+use crate::iteration_method::MoveOnCurrentKeyDuplicates;
+use crate::*;
+
+/// A typed database that maps every key to a sorted set of values.
+///
+/// It is a [`DUP_SORT`](DatabaseFlags::DUP_SORT) database created or opened with
+/// [`DatabaseOpenOptions::create_multimap`] and [`DatabaseOpenOptions::open_multimap`].
+/// Unlike a [`Database`], it only exposes methods that make sense when a key has
+/// many values, the values are sorted by the `CDUP` comparator.
+///
+/// The multimap API is opt-in: a database opened with [`DatabaseOpenOptions::open`] or
+/// [`DatabaseOpenOptions::create`] is a [`Database`], whether or not its flags contain
+/// `DUP_SORT`, and keeps the duplicate methods that aren't checked at compile time.
+///
+/// ```
+/// # use heed::EnvOpenOptions;
+/// use heed::types::*;
+/// use heed::byteorder::BigEndian;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let dir = tempfile::tempdir()?;
+/// # let env = unsafe { EnvOpenOptions::new()
+/// #     .map_size(10 * 1024 * 1024) // 10MB
+/// #     .max_dbs(3000)
+/// #     .open(dir.path())?
+/// # };
+/// type BEU32 = U32<BigEndian>;
+///
+/// let mut wtxn = env.write_txn()?;
+/// let followers = env
+///     .database_options()
+///     .types::<Str, BEU32>()
+///     .name("followers")
+///     .create_multimap(&mut wtxn)?;
+///
+/// # followers.clear(&mut wtxn)?;
+/// assert!(followers.insert(&mut wtxn, "alice", &3)?);
+/// assert!(followers.insert(&mut wtxn, "alice", &1)?);
+/// assert!(!followers.insert(&mut wtxn, "alice", &3)?);
+/// assert!(followers.insert(&mut wtxn, "bob", &1)?);
+///
+/// let values: Vec<_> = followers.values(&wtxn, "alice")?.collect::<heed::Result<_>>()?;
+/// assert_eq!(values, [1, 3]);
+/// assert_eq!(followers.count(&wtxn, "alice")?, 2);
+///
+/// assert!(followers.remove(&mut wtxn, "alice", &1)?);
+/// assert!(followers.contains(&wtxn, "alice", &3)?);
+/// assert!(!followers.contains(&wtxn, "alice", &1)?);
+///
+/// wtxn.commit()?;
+/// # Ok(()) }
+/// ```
+pub struct MultimapDatabase<KC, DC, C = DefaultComparator, CDUP = DefaultComparator> {
+    inner: Database<KC, DC, C, CDUP>,
+}
+
+impl<KC, DC, C, CDUP> MultimapDatabase<KC, DC, C, CDUP> {
+    /// Wraps a database, making sure it is [`DUP_SORT`](DatabaseFlags::DUP_SORT).
+    ///
+    /// LMDB ignores the flags given when opening an existing database, they must be checked.
+    pub(crate) fn new(
+        txn: &RoTxn,
+        inner: Database<KC, DC, C, CDUP>,
+    ) -> Result<MultimapDatabase<KC, DC, C, CDUP>> {
+        if inner.is_dup_sort(txn)? {
+            Ok(MultimapDatabase { inner })
+        } else {
+            Err(Error::Mdb(MdbError::Incompatible))
+        }
+    }
+
+    /// Inserts a value in the set of values of a key.
+    ///
+    /// Returns `false` if the value was already associated with the key.
+    pub fn insert<'a>(
+        &self,
+        txn: &mut RwTxn,
+        key: &'a KC::EItem,
+        data: &'a DC::EItem,
+    ) -> Result<bool>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+    {
+        match self.inner.put_with_flags(txn, PutFlags::NO_DUP_DATA, key, data) {
+            Ok(()) => Ok(true),
+            Err(Error::Mdb(MdbError::KeyExist)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Removes a value from the set of values of a key.
+    ///
+    /// Returns `false` if the value wasn't associated with the key.
+    pub fn remove<'a>(
+        &self,
+        txn: &mut RwTxn,
+        key: &'a KC::EItem,
+        data: &'a DC::EItem,
+    ) -> Result<bool>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+    {
+        self.inner.delete_one_duplicate(txn, key, data)
+    }
+
+    /// Removes a key and all of its values.
+    ///
+    /// Returns `false` if the key doesn't exist.
+    pub fn remove_all<'a>(&self, txn: &mut RwTxn, key: &'a KC::EItem) -> Result<bool>
+    where
+        KC: BytesEncode<'a>,
+    {
+        self.inner.delete(txn, key)
+    }
+
+    /// Returns an iterator over the values of a key, in order.
+    ///
+    /// The iterator is empty if the key doesn't exist.
+    pub fn values<'a, 'txn>(
+        &self,
+        txn: &'txn RoTxn,
+        key: &'a KC::EItem,
+    ) -> Result<RoValues<'txn, DC>>
+    where
+        KC: BytesEncode<'a>,
+    {
+        let iter = self.inner.get_duplicates(txn, key)?;
+        Ok(RoValues { iter: iter.map(RoIter::remap_key_type) })
+    }
+
+    /// Returns whether a value is associated with a key.
+    pub fn contains<'a>(&self, txn: &RoTxn, key: &'a KC::EItem, data: &'a DC::EItem) -> Result<bool>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+    {
+        self.inner.contains_duplicate(txn, key, data)
+    }
+
+    /// Returns the number of values of a key, zero if the key doesn't exist.
+    ///
+    /// The values are counted by LMDB without being read.
+    pub fn count<'a>(&self, txn: &RoTxn, key: &'a KC::EItem) -> Result<usize>
+    where
+        KC: BytesEncode<'a>,
+    {
+        self.inner.count_duplicates(txn, key)
+    }
+
+    /// Returns the number of key-value pairs in this database.
+    pub fn len(&self, txn: &RoTxn) -> Result<u64> {
+        self.inner.len(txn)
+    }
+
+    /// Returns `true` if this database is empty.
+    pub fn is_empty(&self, txn: &RoTxn) -> Result<bool> {
+        self.inner.is_empty(txn)
+    }
+
+    /// Returns an iterator over all the key-value pairs of this database,
+    /// ordered by key and then by value.
+    pub fn iter<'txn>(&self, txn: &'txn RoTxn) -> Result<RoIter<'txn, KC, DC>> {
+        self.inner.iter(txn)
+    }
+
+    /// Removes all the keys and values of this database.
+    pub fn clear(&self, txn: &mut RwTxn) -> Result<()> {
+        self.inner.clear(txn)
+    }
+}
+
+impl<KC, DC, C, CDUP> Clone for MultimapDatabase<KC, DC, C, CDUP> {
+    fn clone(&self) -> MultimapDatabase<KC, DC, C, CDUP> {
+        *self
+    }
+}
+
+impl<KC, DC, C, CDUP> Copy for MultimapDatabase<KC, DC, C, CDUP> {}
+
+impl<KC, DC, C, CDUP> fmt::Debug for MultimapDatabase<KC, DC, C, CDUP> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MultimapDatabase")
+            .field("key_codec", &any::type_name::<KC>())
+            .field("data_codec", &any::type_name::<DC>())
+            .field("key_comparator", &any::type_name::<C>())
+            .field("dup_sort_comparator", &any::type_name::<CDUP>())
+            .finish()
+    }
+}
+
+/// A read-only iterator over the values of a key of a [`MultimapDatabase`],
+/// created with [`MultimapDatabase::values`].
+pub struct RoValues<'txn, DC> {
+    iter: Option<RoIter<'txn, DecodeIgnore, DC, MoveOnCurrentKeyDuplicates>>,
+}
+
+impl<'txn, DC: BytesDecode<'txn>> Iterator for RoValues<'txn, DC> {
+    type Item = Result<DC::DItem>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.iter.as_mut()?.next()?;
+        Some(result.map(|((), data)| data))
+    }
+}
+
+impl<DC> fmt::Debug for RoValues<'_, DC> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoValues").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::BigEndian;
+    use heed_types::*;
+
+    use super::*;
+
+    #[test]
+    fn insert_remove_and_count_values() -> Result<()> {
+        type BEU32 = U32<BigEndian>;
+
+        let dir = tempfile::tempdir()?;
+        let env = unsafe { EnvOpenOptions::new().max_dbs(2).open(dir.path())? };
+        let mut txn = env.write_txn()?;
+
+        let db =
+            env.database_options().types::<Str, BEU32>().name("tags").create_multimap(&mut txn)?;
+        assert!(db.insert(&mut txn, "rust", &2)?);
+        assert!(db.insert(&mut txn, "rust", &1)?);
+        assert!(!db.insert(&mut txn, "rust", &2)?);
+        assert!(db.insert(&mut txn, "lmdb", &1)?);
+
+        assert_eq!(db.len(&txn)?, 3);
+        assert_eq!(db.count(&txn, "rust")?, 2);
+        assert_eq!(db.count(&txn, "missing")?, 0);
+        assert_eq!(db.values(&txn, "rust")?.collect::<Result<Vec<_>>>()?, [1, 2]);
+        assert_eq!(db.values(&txn, "missing")?.count(), 0);
+
+        assert!(db.remove(&mut txn, "rust", &1)?);
+        assert!(!db.remove(&mut txn, "rust", &1)?);
+        assert!(!db.contains(&txn, "rust", &1)?);
+        assert!(db.remove_all(&mut txn, "rust")?);
+        assert!(!db.remove_all(&mut txn, "rust")?);
+
+        let entries: Vec<_> = db.iter(&txn)?.collect::<Result<_>>()?;
+        assert_eq!(entries, [("lmdb", 1)]);
+
+        // A database created without DUP_SORT can't be opened as a multimap.
+        env.database_options().types::<Str, BEU32>().name("plain").create(&mut txn)?;
+        let result = env.database_options().types::<Str, BEU32>().name("plain").open_multimap(&txn);
+        assert!(matches!(result, Err(Error::Mdb(MdbError::Incompatible))));
+
+        Ok(())
+    }
+}
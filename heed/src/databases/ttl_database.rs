@@ -0,0 +1,560 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{any, fmt, io, marker, mem, panic};
+
+use types::{Bytes, Unit};
+
+use super::encode_into;
+use crate::*;
+
+/// The size of the expiry timestamp stored in front of the values and of the index keys.
+const EXPIRY_SIZE: usize = mem::size_of::<u64>();
+
+/// A source of the current time, used by a [`TtlDatabase`] to hide the expired entries.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+}
+
+/// The [`Clock`] of the system, the default one of a [`TtlDatabase`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A [`Clock`] that only moves when told to, e.g. to test the expiration of entries.
+///
+/// The clones of a manual clock share the same time. It starts at the [`UNIX_EPOCH`]
+/// by default and has a millisecond precision.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    millis: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    /// Creates a clock stopped at the given time.
+    pub fn new(now: SystemTime) -> ManualClock {
+        ManualClock { millis: Arc::new(AtomicU64::new(to_millis(now))) }
+    }
+
+    /// Moves the clock to the given time.
+    pub fn set(&self, now: SystemTime) {
+        self.millis.store(to_millis(now), Ordering::SeqCst);
+    }
+
+    /// Moves the clock forward by the given duration.
+    pub fn advance(&self, duration: Duration) {
+        self.millis.fetch_add(duration_millis(duration), Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.millis.load(Ordering::SeqCst))
+    }
+}
+
+/// A typed database whose entries expire after a given time-to-live.
+///
+/// The expiry time of every entry is stored in front of its value and in a companion
+/// index database ordered by time. Expired entries are hidden from [`Self::get`] and
+/// [`Self::iter`] right away but stay on disk until they are deleted by [`Self::sweep`],
+/// or by a background thread spawned with [`Self::spawn_sweeper`].
+///
+/// A TTL database named `name` uses two named databases, `name` and `name-expiries`,
+/// which count towards the [maximum number of databases](EnvOpenOptions::max_dbs).
+///
+/// ```
+/// # use heed::EnvOpenOptions;
+/// use std::time::{Duration, SystemTime};
+///
+/// use heed::{Clock, ManualClock, TtlDatabase};
+/// use heed::types::*;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let dir = tempfile::tempdir()?;
+/// # let env = unsafe { EnvOpenOptions::new()
+/// #     .map_size(10 * 1024 * 1024) // 10MB
+/// #     .max_dbs(3000)
+/// #     .open(dir.path())?
+/// # };
+/// let clock = ManualClock::new(SystemTime::now());
+///
+/// let mut wtxn = env.write_txn()?;
+/// let cache = TtlDatabase::<Str, Str>::create(&env, &mut wtxn, "cache")?;
+/// let cache = cache.with_clock(clock.clone());
+///
+/// cache.put(&mut wtxn, "session", "alice", Duration::from_secs(60))?;
+/// assert_eq!(cache.get(&wtxn, "session")?, Some("alice"));
+///
+/// clock.advance(Duration::from_secs(61));
+/// assert_eq!(cache.get(&wtxn, "session")?, None);
+///
+/// assert_eq!(cache.sweep(&mut wtxn, clock.now(), 100)?, 1);
+///
+/// wtxn.commit()?;
+/// # Ok(()) }
+/// ```
+pub struct TtlDatabase<KC, DC, CL = SystemClock> {
+    entries: Database<KC, Bytes>,
+    expiries: Database<Bytes, Unit>,
+    clock: CL,
+    marker: marker::PhantomData<DC>,
+}
+
+impl<KC: 'static, DC> TtlDatabase<KC, DC> {
+    /// Opens a TTL database that already exists in this environment.
+    ///
+    /// Returns `None` if any of its two databases doesn't exist.
+    pub fn open<T>(env: &Env<T>, rtxn: &RoTxn, name: &str) -> Result<Option<Self>> {
+        let Some(entries) = env.open_database(rtxn, Some(name))? else { return Ok(None) };
+        let Some(expiries) = env.open_database(rtxn, Some(&expiries_name(name)))? else {
+            return Ok(None);
+        };
+        Ok(Some(TtlDatabase::new(entries, expiries)))
+    }
+
+    /// Creates a TTL database that can already exist in this environment.
+    pub fn create<T>(env: &Env<T>, wtxn: &mut RwTxn, name: &str) -> Result<Self> {
+        let entries = env.create_database(wtxn, Some(name))?;
+        let expiries = env.create_database(wtxn, Some(&expiries_name(name)))?;
+        Ok(TtlDatabase::new(entries, expiries))
+    }
+
+    fn new(entries: Database<KC, Bytes>, expiries: Database<Bytes, Unit>) -> Self {
+        TtlDatabase { entries, expiries, clock: SystemClock, marker: marker::PhantomData }
+    }
+}
+
+impl<KC, DC, CL> TtlDatabase<KC, DC, CL> {
+    /// Uses the given clock instead of the current one to hide expired entries
+    /// and to compute the expiry time of the new ones.
+    pub fn with_clock<CL2: Clock>(self, clock: CL2) -> TtlDatabase<KC, DC, CL2> {
+        TtlDatabase {
+            entries: self.entries,
+            expiries: self.expiries,
+            clock,
+            marker: marker::PhantomData,
+        }
+    }
+
+    /// Returns the clock used by this database.
+    pub fn clock(&self) -> &CL {
+        &self.clock
+    }
+
+    /// Inserts a key-value pair that expires at the given time, replacing any previous
+    /// value and expiry time of the key.
+    pub fn put_with_expiry<'a>(
+        &self,
+        txn: &mut RwTxn,
+        key: &'a KC::EItem,
+        data: &'a DC::EItem,
+        expires_at: SystemTime,
+    ) -> Result<()>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+    {
+        self.put_expiring(txn, key, data, to_millis(expires_at))
+    }
+
+    fn put_expiring<'a>(
+        &self,
+        txn: &mut RwTxn,
+        key: &'a KC::EItem,
+        data: &'a DC::EItem,
+        expiry: u64,
+    ) -> Result<()>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+    {
+        let dbi = self.entries.dbi;
+        let key = KC::bytes_encode(key)
+            .map_err(|e| txn.error_context(Error::Encoding(e), dbi, "put", None))?;
+
+        let mut value = expiry.to_be_bytes().to_vec();
+        encode_into::<DC>(data, &mut value)
+            .map_err(|e| txn.error_context(Error::Encoding(e), dbi, "put", Some(&key)))?;
+
+        let mut previous_expiry = None;
+        self.entries.remap_key_type::<Bytes>().update(txn, &key, |previous: Option<&[u8]>| {
+            previous_expiry = previous.and_then(|bytes| split_expiry(bytes).ok()).map(|(e, _)| e);
+            Some(&value[..])
+        })?;
+
+        if let Some(previous_expiry) = previous_expiry.filter(|&e| e != expiry) {
+            self.expiries.delete(txn, &index_key(previous_expiry, &key))?;
+        }
+        self.expiries.put(txn, &index_key(expiry, &key), &())
+    }
+
+    /// Retrieves the value associated with a key and its expiry time,
+    /// even if the entry expired but wasn't swept yet.
+    pub fn get_with_expiry<'a, 'txn>(
+        &self,
+        txn: &'txn RoTxn,
+        key: &'a KC::EItem,
+    ) -> Result<Option<(DC::DItem, SystemTime)>>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesDecode<'txn>,
+    {
+        let Some(bytes) = self.entries.get(txn, key)? else { return Ok(None) };
+        let (expiry, data) = split_expiry(bytes)?;
+        let data = DC::bytes_decode(data)
+            .map_err(|e| txn.error_context(Error::Decoding(e), self.entries.dbi, "get", None))?;
+        Ok(Some((data, UNIX_EPOCH + Duration::from_millis(expiry))))
+    }
+
+    /// Deletes an entry and its expiry time.
+    ///
+    /// If the entry does not exist, then `false` is returned.
+    pub fn delete<'a>(&self, txn: &mut RwTxn, key: &'a KC::EItem) -> Result<bool>
+    where
+        KC: BytesEncode<'a>,
+    {
+        let key = KC::bytes_encode(key)
+            .map_err(|e| txn.error_context(Error::Encoding(e), self.entries.dbi, "delete", None))?;
+
+        let entries = self.entries.remap_key_type::<Bytes>();
+        let Some(bytes) = entries.get(txn, &key)? else { return Ok(false) };
+        let index_key = split_expiry(bytes).map(|(expiry, _)| index_key(expiry, &key));
+
+        entries.delete(txn, &key)?;
+        if let Ok(index_key) = index_key {
+            self.expiries.delete(txn, &index_key)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Deletes up to `limit` entries that expired at the given time, the oldest first.
+    ///
+    /// Returns the number of deleted entries, when it is equal to `limit`
+    /// there may be more expired entries to delete.
+    pub fn sweep(&self, txn: &mut RwTxn, now: SystemTime, limit: usize) -> Result<usize> {
+        let now = to_millis(now);
+
+        let mut expired = Vec::new();
+        for result in self.expiries.iter(txn)?.take(limit) {
+            let (index_key, ()) = result?;
+            match split_expiry(index_key)? {
+                (expiry, _) if expiry <= now => expired.push(index_key.to_vec()),
+                _ => break,
+            }
+        }
+
+        let entries = self.entries.remap_key_type::<Bytes>();
+        for index_key in &expired {
+            entries.delete(txn, &index_key[EXPIRY_SIZE..])?;
+            self.expiries.delete(txn, index_key)?;
+        }
+
+        Ok(expired.len())
+    }
+
+    /// Deletes all the entries of this database and their expiry times.
+    pub fn clear(&self, txn: &mut RwTxn) -> Result<()> {
+        self.entries.clear(txn)?;
+        self.expiries.clear(txn)
+    }
+
+    /// Spawns a thread that sweeps the expired entries of this database every `interval`,
+    /// in write transactions deleting at most `limit` entries each.
+    ///
+    /// The thread runs until the returned [`TtlSweeper`] is stopped or dropped,
+    /// or until a transaction fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    pub fn spawn_sweeper<T: 'static>(
+        &self,
+        env: Env<T>,
+        interval: Duration,
+        limit: usize,
+    ) -> Result<TtlSweeper>
+    where
+        CL: Clock + Clone + Send + 'static,
+    {
+        assert!(limit > 0, "the sweeper must delete at least one entry per transaction");
+
+        let db: TtlDatabase<Bytes, Bytes, CL> = TtlDatabase {
+            entries: self.entries.remap_key_type(),
+            expiries: self.expiries,
+            clock: self.clock.clone(),
+            marker: marker::PhantomData,
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let handle =
+            thread::Builder::new().name(String::from("heed-ttl-sweeper")).spawn(move || loop {
+                match receiver.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                }
+
+                loop {
+                    let mut wtxn = env.write_txn()?;
+                    let swept = db.sweep(&mut wtxn, db.clock.now(), limit)?;
+                    wtxn.commit()?;
+                    if swept < limit {
+                        break;
+                    }
+                }
+            })?;
+
+        Ok(TtlSweeper { sender, handle })
+    }
+}
+
+impl<KC, DC, CL: Clock> TtlDatabase<KC, DC, CL> {
+    /// Inserts a key-value pair that expires after the given time-to-live,
+    /// replacing any previous value and expiry time of the key.
+    pub fn put<'a>(
+        &self,
+        txn: &mut RwTxn,
+        key: &'a KC::EItem,
+        data: &'a DC::EItem,
+        ttl: Duration,
+    ) -> Result<()>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+    {
+        let expiry = to_millis(self.clock.now()).saturating_add(duration_millis(ttl));
+        self.put_expiring(txn, key, data, expiry)
+    }
+
+    /// Retrieves the value associated with a key.
+    ///
+    /// If the key does not exist or its entry expired, then `None` is returned.
+    pub fn get<'a, 'txn>(&self, txn: &'txn RoTxn, key: &'a KC::EItem) -> Result<Option<DC::DItem>>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesDecode<'txn>,
+    {
+        let now = self.clock.now();
+        match self.get_with_expiry(txn, key)? {
+            Some((data, expires_at)) if expires_at > now => Ok(Some(data)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns an iterator over the entries of this database that didn't expire,
+    /// ordered by key.
+    pub fn iter<'txn>(&self, txn: &'txn RoTxn) -> Result<TtlIter<'txn, KC, DC>> {
+        let iter = self.entries.remap_key_type::<Bytes>().iter(txn)?;
+        Ok(TtlIter { iter, now: to_millis(self.clock.now()), marker: marker::PhantomData })
+    }
+}
+
+impl<KC, DC, CL: Clone> Clone for TtlDatabase<KC, DC, CL> {
+    fn clone(&self) -> Self {
+        TtlDatabase {
+            entries: self.entries,
+            expiries: self.expiries,
+            clock: self.clock.clone(),
+            marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<KC, DC, CL> fmt::Debug for TtlDatabase<KC, DC, CL> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TtlDatabase")
+            .field("key_codec", &any::type_name::<KC>())
+            .field("data_codec", &any::type_name::<DC>())
+            .field("clock", &any::type_name::<CL>())
+            .finish()
+    }
+}
+
+/// An iterator over the entries of a [`TtlDatabase`] that didn't expire,
+/// created with [`TtlDatabase::iter`].
+pub struct TtlIter<'txn, KC, DC> {
+    iter: RoIter<'txn, Bytes, Bytes>,
+    now: u64,
+    marker: marker::PhantomData<(KC, DC)>,
+}
+
+impl<'txn, KC, DC> Iterator for TtlIter<'txn, KC, DC>
+where
+    KC: BytesDecode<'txn>,
+    DC: BytesDecode<'txn>,
+{
+    type Item = Result<(KC::DItem, DC::DItem)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (key, bytes) = match self.iter.next()? {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e)),
+            };
+
+            let data = match split_expiry(bytes) {
+                Ok((expiry, _)) if expiry <= self.now => continue,
+                Ok((_, data)) => data,
+                Err(e) => return Some(Err(e)),
+            };
+
+            return Some(match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                (Ok(key), Ok(data)) => Ok((key, data)),
+                (Err(e), _) | (_, Err(e)) => Err(Error::Decoding(e)),
+            });
+        }
+    }
+}
+
+impl<KC, DC> fmt::Debug for TtlIter<'_, KC, DC> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TtlIter").finish()
+    }
+}
+
+/// A handle on the thread sweeping the expired entries of a [`TtlDatabase`],
+/// created with [`TtlDatabase::spawn_sweeper`].
+///
+/// Dropping it stops the thread without waiting for it.
+#[derive(Debug)]
+pub struct TtlSweeper {
+    sender: mpsc::Sender<()>,
+    handle: JoinHandle<Result<()>>,
+}
+
+impl TtlSweeper {
+    /// Returns whether the thread is still running, it stops early when a transaction fails.
+    pub fn is_running(&self) -> bool {
+        !self.handle.is_finished()
+    }
+
+    /// Stops the thread, waiting for its current transaction to be committed,
+    /// and returns the error that stopped it early if any.
+    pub fn stop(self) -> Result<()> {
+        let TtlSweeper { sender, handle } = self;
+        drop(sender);
+        handle.join().unwrap_or_else(|payload| panic::resume_unwind(payload))
+    }
+}
+
+fn expiries_name(name: &str) -> String {
+    format!("{name}-expiries")
+}
+
+/// Returns the key of the index database, the expiry time followed by the key.
+fn index_key(expiry: u64, key: &[u8]) -> Vec<u8> {
+    let mut index_key = Vec::with_capacity(EXPIRY_SIZE + key.len());
+    index_key.extend_from_slice(&expiry.to_be_bytes());
+    index_key.extend_from_slice(key);
+    index_key
+}
+
+/// Splits the expiry time from the rest of the bytes.
+fn split_expiry(bytes: &[u8]) -> Result<(u64, &[u8])> {
+    match bytes.split_first_chunk() {
+        Some((expiry, rest)) => Ok((u64::from_be_bytes(*expiry), rest)),
+        None => Err(Error::Decoding(io::Error::from(io::ErrorKind::UnexpectedEof).into())),
+    }
+}
+
+fn to_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, duration_millis)
+}
+
+fn duration_millis(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use byteorder::BigEndian;
+    use heed_types::*;
+
+    use super::*;
+
+    #[test]
+    fn expire_and_sweep() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let env = unsafe { EnvOpenOptions::new().max_dbs(2).open(dir.path())? };
+        let clock = ManualClock::new(UNIX_EPOCH + Duration::from_secs(1000));
+        let second = Duration::from_secs(1);
+
+        let mut txn = env.write_txn()?;
+        let db = TtlDatabase::<U32<BigEndian>, Str>::create(&env, &mut txn, "ttl")?;
+        let db = db.with_clock(clock.clone());
+        for i in 0..10 {
+            db.put(&mut txn, &i, "value", second * (i + 1))?;
+        }
+        // Extending the time-to-live of an entry moves it in the index.
+        db.put(&mut txn, &0, "extended", second * 100)?;
+        assert!(db.delete(&mut txn, &9)?);
+        assert!(!db.delete(&mut txn, &9)?);
+
+        clock.advance(second * 5);
+        assert_eq!(db.get(&txn, &0)?, Some("extended"));
+        assert_eq!(db.get(&txn, &4)?, None);
+        assert_eq!(db.get(&txn, &5)?, Some("value"));
+        let (_, expires_at) = db.get_with_expiry(&txn, &4)?.unwrap();
+        assert_eq!(expires_at, clock.now());
+
+        let keys: Vec<_> = db.iter(&txn)?.map(|e| e.map(|(k, _)| k)).collect::<Result<_>>()?;
+        assert_eq!(keys, [0, 5, 6, 7, 8]);
+
+        assert_eq!(db.sweep(&mut txn, clock.now(), 3)?, 3);
+        assert_eq!(db.sweep(&mut txn, clock.now(), 3)?, 1);
+        assert_eq!(db.sweep(&mut txn, clock.now(), 3)?, 0);
+        assert_eq!(db.entries.len(&txn)?, 5);
+        assert_eq!(db.expiries.len(&txn)?, 5);
+
+        assert_eq!(db.sweep(&mut txn, clock.now() + second * 1000, 100)?, 5);
+        assert!(db.entries.is_empty(&txn)?);
+        assert!(db.expiries.is_empty(&txn)?);
+        txn.commit()?;
+
+        let rtxn = env.read_txn()?;
+        assert!(TtlDatabase::<U32<BigEndian>, Str>::open(&env, &rtxn, "ttl")?.is_some());
+        assert!(TtlDatabase::<U32<BigEndian>, Str>::open(&env, &rtxn, "missing")?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn background_sweeper() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let env =
+            unsafe { EnvOpenOptions::new().read_txn_without_tls().max_dbs(2).open(dir.path())? };
+        let clock = ManualClock::default();
+
+        let db = env.write(|wtxn| TtlDatabase::<Str, Unit>::create(&env, wtxn, "ttl"))?;
+        let db = db.with_clock(clock.clone());
+        env.write(|wtxn| {
+            for key in ["a", "b", "c", "d", "e"] {
+                db.put(wtxn, key, &(), Duration::from_secs(1))?;
+            }
+            Ok(())
+        })?;
+
+        let sweeper = db.spawn_sweeper(env.clone(), Duration::from_millis(1), 2)?;
+        clock.advance(Duration::from_secs(1));
+
+        let start = Instant::now();
+        while !env.read(|rtxn| db.expiries.is_empty(rtxn))? {
+            assert!(start.elapsed() < Duration::from_secs(10), "the sweeper didn't sweep");
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        assert!(sweeper.is_running());
+        sweeper.stop()
+    }
+}
@@ -6,7 +6,9 @@ use std::path::Path;
 use aead::generic_array::typenum::Unsigned;
 use aead::{AeadMutInPlace, Key, KeyInit, Nonce, Tag};
 
-use super::{Env, EnvClosingEvent, EnvInfo, FlagSetMode};
+use super::{
+    retry, Env, EnvClosingEvent, EnvInfo, FlagSetMode, ReadTxnReport, ReadTxnWatchdog, RetryPolicy,
+};
 use crate::databases::{EncryptedDatabase, EncryptedDatabaseOpenOptions};
 use crate::envs::EnvStat;
 use crate::mdb::ffi::{self};
@@ -94,6 +96,42 @@ impl<T> EncryptedEnv<T> {
         self.inner.info()
     }
 
+    /// Enable the watchdog that tracks the read transactions opened from now on in this process.
+    ///
+    /// See [`Env::enable_read_txn_watchdog`] for more details.
+    pub fn enable_read_txn_watchdog(&self, watchdog: ReadTxnWatchdog) {
+        self.inner.enable_read_txn_watchdog(watchdog)
+    }
+
+    /// Disable the read transactions watchdog.
+    pub fn disable_read_txn_watchdog(&self) {
+        self.inner.disable_read_txn_watchdog()
+    }
+
+    /// Returns the reports of all the read transactions tracked by the watchdog, oldest first.
+    pub fn active_read_txns(&self) -> Vec<ReadTxnReport> {
+        self.inner.active_read_txns()
+    }
+
+    /// Returns the reports of the read transactions opened for longer than the watchdog
+    /// threshold, oldest first, and calls the watchdog callback for each of them.
+    pub fn check_read_txns(&self) -> Vec<ReadTxnReport> {
+        self.inner.check_read_txns()
+    }
+
+    /// Record the database, the operation and the key involved in the
+    /// errors returned from now on.
+    ///
+    /// See [`Env::enable_error_context`] for more details.
+    pub fn enable_error_context(&self) {
+        self.inner.enable_error_context()
+    }
+
+    /// Stop recording the context of the errors.
+    pub fn disable_error_context(&self) {
+        self.inner.disable_error_context()
+    }
+
     /// Returns some statistics about this environment.
     pub fn stat(&self) -> EnvStat {
         self.inner.stat()
@@ -257,6 +295,48 @@ impl<T> EncryptedEnv<T> {
         self.inner.static_read_txn()
     }
 
+    /// Run the given closure in a new write transaction that is committed if the
+    /// closure returns `Ok` and aborted otherwise.
+    ///
+    /// See [`Env::write`] for more details.
+    pub fn write<R, F>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut RwTxn) -> Result<R>,
+    {
+        self.inner.write(f)
+    }
+
+    /// Run the given closure in a new write transaction like [`Self::write`] and
+    /// run it again, in a brand new transaction, as long as the policy asks for it.
+    pub fn write_with_retry<R, P, F>(&self, policy: &P, f: F) -> Result<R>
+    where
+        P: RetryPolicy + ?Sized,
+        F: FnMut(&mut RwTxn) -> Result<R>,
+    {
+        self.inner.write_with_retry(policy, f)
+    }
+
+    /// Run the given closure in a new read transaction.
+    ///
+    /// The transaction is given mutably as it is required to read from an [`EncryptedDatabase`].
+    pub fn read<R, F>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut RoTxn<T>) -> Result<R>,
+    {
+        let mut rtxn = self.read_txn()?;
+        f(&mut rtxn)
+    }
+
+    /// Run the given closure in a new read transaction like [`Self::read`] and
+    /// run it again, in a brand new transaction, as long as the policy asks for it.
+    pub fn read_with_retry<R, P, F>(&self, policy: &P, mut f: F) -> Result<R>
+    where
+        P: RetryPolicy + ?Sized,
+        F: FnMut(&mut RoTxn<T>) -> Result<R>,
+    {
+        retry(policy, || self.read(&mut f))
+    }
+
     /// Copy an LMDB environment to the specified path, with options.
     ///
     /// This function may be used to make a backup of an existing environment.
@@ -1,30 +1,34 @@
 use std::any::TypeId;
+use std::collections::HashMap;
 use std::ffi::CString;
 use std::fs::{self, File};
 use std::io::Seek;
 use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
 use std::ptr::{self, NonNull};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
 use std::{fmt, io, mem};
 
 use heed_traits::Comparator;
 use synchronoise::SignalEvent;
 
+use super::watchdog::{Watchdog, WatchedTxn};
 use super::{
-    custom_key_cmp_wrapper, get_file_fd, DefaultComparator, EnvClosingEvent, EnvInfo, FlagSetMode,
-    IntegerComparator, OPENED_ENV,
+    custom_key_cmp_wrapper, get_file_fd, retry, DefaultComparator, EnvClosingEvent, EnvInfo,
+    FlagSetMode, IntegerComparator, ReadTxnReport, ReadTxnWatchdog, RetryPolicy, OPENED_ENV,
 };
 use crate::cursor::{MoveOperation, RoCursor};
 use crate::envs::EnvStat;
 use crate::mdb::ffi::{self, MDB_env};
 use crate::mdb::lmdb_error::mdb_result;
 use crate::mdb::lmdb_flags::AllDatabaseFlags;
+use crate::sequence::SEQUENCES_DATABASE_NAME;
 #[allow(unused)] // for cargo auto doc links
 use crate::EnvOpenOptions;
 use crate::{
-    assert_eq_env_txn, CompactionOption, Database, DatabaseOpenOptions, EnvFlags, Error, Result,
-    RoTxn, RwTxn, Unspecified, WithTls, WithoutTls,
+    assert_eq_env_txn, CompactionOption, Database, DatabaseOpenOptions, EnvFlags, Error,
+    ErrorContext, Result, RoTxn, RwTxn, Sequence, Unspecified, WithTls, WithoutTls,
 };
 
 /// An environment handle constructed by using [`EnvOpenOptions::open`].
@@ -40,7 +44,18 @@ impl<T> Env<T> {
         path: PathBuf,
         signal_event: Arc<SignalEvent>,
     ) -> Self {
-        Env { inner: Arc::new(EnvInner { env_ptr, path, signal_event }), _tls_marker: PhantomData }
+        let read_txn_watchdog = RwLock::new(None);
+        let error_context = AtomicBool::new(false);
+        let database_names = RwLock::new(HashMap::new());
+        let inner = EnvInner {
+            env_ptr,
+            signal_event,
+            path,
+            read_txn_watchdog,
+            error_context,
+            database_names,
+        };
+        Env { inner: Arc::new(inner), _tls_marker: PhantomData }
     }
 
     pub(crate) fn env_mut_ptr(&self) -> NonNull<ffi::MDB_env> {
@@ -172,6 +187,74 @@ impl<T> Env<T> {
         }
     }
 
+    /// Enable the watchdog that tracks the read transactions opened from now on in this process.
+    ///
+    /// It replaces the previous watchdog, if any. The transactions tracked by a
+    /// previous watchdog are no longer reported.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use heed::{EnvOpenOptions, ReadTxnWatchdog};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// let env = unsafe { EnvOpenOptions::new().open(dir.path())? };
+    /// env.enable_read_txn_watchdog(ReadTxnWatchdog::new(Duration::ZERO));
+    ///
+    /// let rtxn = env.read_txn()?;
+    /// let reports = env.check_read_txns();
+    /// assert_eq!(reports.len(), 1);
+    /// assert_eq!(reports[0].txn_id, rtxn.id());
+    /// assert_eq!(reports[0].commits_behind, 0);
+    ///
+    /// drop(rtxn);
+    /// assert!(env.active_read_txns().is_empty());
+    /// # Ok(()) }
+    /// ```
+    pub fn enable_read_txn_watchdog(&self, watchdog: ReadTxnWatchdog) {
+        let watchdog = Arc::new(Watchdog::new(watchdog));
+        *self.inner.read_txn_watchdog.write().unwrap() = Some(watchdog);
+    }
+
+    /// Disable the read transactions watchdog.
+    pub fn disable_read_txn_watchdog(&self) {
+        *self.inner.read_txn_watchdog.write().unwrap() = None;
+    }
+
+    /// Returns the reports of all the read transactions tracked by the watchdog, oldest first.
+    ///
+    /// Returns an empty list if the watchdog is disabled.
+    pub fn active_read_txns(&self) -> Vec<ReadTxnReport> {
+        match self.inner.read_txn_watchdog() {
+            Some(watchdog) => watchdog.reports(self.info().last_txn_id),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the reports of the read transactions opened for longer than the watchdog
+    /// threshold, oldest first, and calls the watchdog callback for each of them.
+    ///
+    /// Returns an empty list if the watchdog is disabled.
+    pub fn check_read_txns(&self) -> Vec<ReadTxnReport> {
+        match self.inner.read_txn_watchdog() {
+            Some(watchdog) => watchdog.check(self.info().last_txn_id),
+            None => Vec::new(),
+        }
+    }
+
+    /// Record the database, the operation and the key involved in the
+    /// errors returned from now on, see [`Error::context`].
+    ///
+    /// The context is only built when an error occurs and costs nothing otherwise.
+    pub fn enable_error_context(&self) {
+        self.inner.error_context.store(true, Ordering::Relaxed);
+    }
+
+    /// Stop recording the context of the errors.
+    pub fn disable_error_context(&self) {
+        self.inner.error_context.store(false, Ordering::Relaxed);
+    }
+
     /// Returns some statistics about this environment.
     pub fn stat(&self) -> EnvStat {
         let mut raw_stat = mem::MaybeUninit::uninit();
@@ -307,6 +390,42 @@ impl<T> Env<T> {
         options.create(wtxn)
     }
 
+    /// Opens the durable ID generator with the given name, creating it if needed.
+    ///
+    /// The sequences are stored in a reserved database of this environment, which counts
+    /// towards the [maximum number of databases](EnvOpenOptions::max_dbs). Like databases,
+    /// a sequence first opened in a transaction that is aborted must be opened again.
+    ///
+    /// ```
+    /// # use heed::EnvOpenOptions;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// let mut wtxn = env.write_txn()?;
+    /// let orders = env.sequence(&mut wtxn, "orders")?;
+    /// let first = orders.next(&mut wtxn)?;
+    /// wtxn.commit()?;
+    ///
+    /// // The IDs handed out by an aborted transaction are handed out again.
+    /// let mut wtxn = env.write_txn()?;
+    /// let orders = env.sequence(&mut wtxn, "orders")?;
+    /// let second = orders.next(&mut wtxn)?;
+    /// wtxn.abort();
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// assert_eq!(orders.next(&mut wtxn)?, second);
+    /// assert_eq!(second, first + 1);
+    /// # Ok(()) }
+    /// ```
+    pub fn sequence(&self, wtxn: &mut RwTxn, name: &str) -> Result<Sequence> {
+        let db = self.create_database(wtxn, Some(SEQUENCES_DATABASE_NAME))?;
+        Ok(Sequence::new(db, name))
+    }
+
     pub(crate) fn raw_init_database<C: Comparator + 'static, CDUP: Comparator + 'static>(
         &self,
         mut raw_txn: NonNull<ffi::MDB_txn>,
@@ -322,6 +441,9 @@ impl<T> Env<T> {
         }
 
         let dbi = self.raw_open_dbi(raw_txn, name, flags.bits())?;
+        if let Some(name) = name {
+            self.inner.database_names.write().unwrap().insert(dbi, Arc::from(name));
+        }
 
         let cmp_type_id = TypeId::of::<C>();
         if cmp_type_id != TypeId::of::<DefaultComparator>()
@@ -457,6 +579,97 @@ impl<T> Env<T> {
         RoTxn::static_read_txn(self)
     }
 
+    /// Run the given closure in a new write transaction that is committed if the
+    /// closure returns `Ok` and aborted otherwise.
+    ///
+    /// ```
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// let db: Database<Str, Str> = env.write(|wtxn| env.create_database(wtxn, None))?;
+    /// env.write(|wtxn| db.put(wtxn, "hello", "world"))?;
+    ///
+    /// let value = env.read(|rtxn| Ok(db.get(rtxn, "hello")?.map(ToOwned::to_owned)))?;
+    /// assert_eq!(value.as_deref(), Some("world"));
+    /// # Ok(()) }
+    /// ```
+    pub fn write<R, F>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut RwTxn) -> Result<R>,
+    {
+        let mut wtxn = self.write_txn()?;
+        match f(&mut wtxn) {
+            Ok(value) => {
+                wtxn.commit()?;
+                Ok(value)
+            }
+            Err(e) => {
+                wtxn.abort();
+                Err(e)
+            }
+        }
+    }
+
+    /// Run the given closure in a new write transaction like [`Self::write`] and
+    /// run it again, in a brand new transaction, as long as the policy asks for it.
+    ///
+    /// ```
+    /// # use heed::EnvOpenOptions;
+    /// use heed::{Database, ExponentialBackoff};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// let policy = ExponentialBackoff::new();
+    /// let db: Database<Str, Str> = env.write_with_retry(&policy, |wtxn| {
+    ///     let db = env.create_database(wtxn, None)?;
+    ///     db.put(wtxn, "hello", "world")?;
+    ///     Ok(db)
+    /// })?;
+    /// # Ok(()) }
+    /// ```
+    pub fn write_with_retry<R, P, F>(&self, policy: &P, mut f: F) -> Result<R>
+    where
+        P: RetryPolicy + ?Sized,
+        F: FnMut(&mut RwTxn) -> Result<R>,
+    {
+        retry(policy, || self.write(&mut f))
+    }
+
+    /// Run the given closure in a new read transaction.
+    ///
+    /// See [`Self::write`] for an example.
+    pub fn read<R, F>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&RoTxn<T>) -> Result<R>,
+    {
+        let rtxn = self.read_txn()?;
+        f(&rtxn)
+    }
+
+    /// Run the given closure in a new read transaction like [`Self::read`] and
+    /// run it again, in a brand new transaction, as long as the policy asks for it.
+    pub fn read_with_retry<R, P, F>(&self, policy: &P, mut f: F) -> Result<R>
+    where
+        P: RetryPolicy + ?Sized,
+        F: FnMut(&RoTxn<T>) -> Result<R>,
+    {
+        retry(policy, || self.read(&mut f))
+    }
+
     /// Copy an LMDB environment to the specified path, with options.
     ///
     /// This function may be used to make a backup of an existing environment.
@@ -731,12 +944,45 @@ pub(crate) struct EnvInner {
     env_ptr: NonNull<MDB_env>,
     signal_event: Arc<SignalEvent>,
     pub(crate) path: PathBuf,
+    read_txn_watchdog: RwLock<Option<Arc<Watchdog>>>,
+    error_context: AtomicBool,
+    database_names: RwLock<HashMap<ffi::MDB_dbi, Arc<str>>>,
 }
 
 impl EnvInner {
     pub(crate) fn env_mut_ptr(&self) -> NonNull<ffi::MDB_env> {
         self.env_ptr
     }
+
+    /// Returns the read transactions watchdog, if enabled.
+    ///
+    /// The lock is released before returning, the watchdog callback can use the environment.
+    fn read_txn_watchdog(&self) -> Option<Arc<Watchdog>> {
+        self.read_txn_watchdog.read().unwrap().clone()
+    }
+
+    /// Registers the read transaction with the given ID if the watchdog is enabled.
+    pub(crate) fn watch_read_txn(&self, txn_id: usize) -> Option<WatchedTxn> {
+        self.read_txn_watchdog.read().unwrap().as_ref().map(|watchdog| watchdog.track(txn_id))
+    }
+
+    /// Attaches the database, the operation and the key to the error
+    /// if the context of the errors is recorded.
+    #[cold]
+    pub(crate) fn error_context(
+        &self,
+        error: Error,
+        dbi: ffi::MDB_dbi,
+        operation: &'static str,
+        key: Option<&[u8]>,
+    ) -> Error {
+        if !self.error_context.load(Ordering::Relaxed) {
+            return error;
+        }
+
+        let database_name = self.database_names.read().unwrap().get(&dbi).cloned();
+        error.with_context(ErrorContext::new(database_name, dbi, operation, key))
+    }
 }
 
 unsafe impl Send for EnvInner {}
@@ -1072,4 +1318,154 @@ mod tests {
             assert_eq!(maxkeysize, 511);
         }
     }
+
+    #[test]
+    fn write_with_retry_runs_again() {
+        use std::cell::Cell;
+
+        use crate::{ExponentialBackoff, MdbError};
+
+        let dir = tempfile::tempdir().unwrap();
+        let env =
+            unsafe { EnvOpenOptions::new().map_size(10 * 1024 * 1024).open(dir.path()) }.unwrap();
+        let db = env.write(|wtxn| env.create_database::<Str, Str>(wtxn, None)).unwrap();
+
+        let mut policy = ExponentialBackoff::new();
+        policy.max_attempts(3).backoff(Duration::ZERO, Duration::ZERO);
+
+        let attempts = Cell::new(0);
+        env.write_with_retry(&policy, |wtxn| {
+            attempts.set(attempts.get() + 1);
+            db.put(wtxn, "attempt", &attempts.get().to_string())?;
+            if attempts.get() < 2 {
+                Err(Error::Mdb(MdbError::MapFull))
+            } else {
+                Ok(())
+            }
+        })
+        .unwrap();
+        assert_eq!(attempts.get(), 2);
+
+        let value = env.read(|rtxn| Ok(db.get(rtxn, "attempt")?.map(ToOwned::to_owned))).unwrap();
+        assert_eq!(value.as_deref(), Some("2"));
+
+        attempts.set(0);
+        let result: crate::Result<()> = env.write_with_retry(&policy, |wtxn| {
+            attempts.set(attempts.get() + 1);
+            db.put(wtxn, "never", "committed")?;
+            Err(Error::Mdb(MdbError::MapFull))
+        });
+        assert!(matches!(result, Err(Error::Mdb(MdbError::MapFull))));
+        assert_eq!(attempts.get(), 3);
+
+        attempts.set(0);
+        let result: crate::Result<()> = env.read_with_retry(&policy, |_rtxn| {
+            attempts.set(attempts.get() + 1);
+            Err(Error::Mdb(MdbError::Corrupted))
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 1);
+
+        let rtxn = env.read_txn().unwrap();
+        assert_eq!(db.get(&rtxn, "never").unwrap(), None);
+    }
+
+    #[test]
+    fn read_txn_watchdog_reports_commits_behind() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        use crate::ReadTxnWatchdog;
+
+        let dir = tempfile::tempdir().unwrap();
+        let env =
+            unsafe { EnvOpenOptions::new().map_size(10 * 1024 * 1024).open(dir.path()) }.unwrap();
+        let db = env.write(|wtxn| env.create_database::<Str, Str>(wtxn, None)).unwrap();
+
+        let called = Arc::new(AtomicUsize::new(0));
+        let mut watchdog = ReadTxnWatchdog::new(Duration::ZERO);
+        let called_cloned = called.clone();
+        watchdog.capture_backtraces(true).on_long_lived(move |_report| {
+            called_cloned.fetch_add(1, Ordering::SeqCst);
+        });
+        env.enable_read_txn_watchdog(watchdog);
+
+        let rtxn = env.read_txn().unwrap();
+        env.write(|wtxn| db.put(wtxn, "hello", "world")).unwrap();
+        env.write(|wtxn| db.put(wtxn, "hello", "monde")).unwrap();
+
+        let reports = env.check_read_txns();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].txn_id, rtxn.id());
+        assert_eq!(reports[0].commits_behind, 2);
+        assert!(reports[0].backtrace.is_some());
+        assert_eq!(called.load(Ordering::SeqCst), 1);
+
+        drop(rtxn);
+        assert!(env.active_read_txns().is_empty());
+
+        env.disable_read_txn_watchdog();
+        let _rtxn = env.read_txn().unwrap();
+        assert!(env.active_read_txns().is_empty());
+    }
+
+    #[test]
+    fn read_txn_watchdog_callback_uses_env() {
+        use crate::ReadTxnWatchdog;
+
+        let dir = tempfile::tempdir().unwrap();
+        let env = unsafe { EnvOpenOptions::new().read_txn_without_tls().open(dir.path()) }.unwrap();
+
+        // The callback can open read transactions and disable the watchdog.
+        let mut watchdog = ReadTxnWatchdog::new(Duration::ZERO);
+        let env_cloned = env.clone();
+        watchdog.on_long_lived(move |_report| {
+            drop(env_cloned.read_txn().unwrap());
+            env_cloned.disable_read_txn_watchdog();
+        });
+        env.enable_read_txn_watchdog(watchdog);
+
+        let _rtxn = env.read_txn().unwrap();
+        assert_eq!(env.check_read_txns().len(), 1);
+        assert!(env.check_read_txns().is_empty());
+    }
+
+    #[test]
+    fn sequences_across_nested_txns() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = unsafe { EnvOpenOptions::new().max_dbs(1).open(dir.path()) }.unwrap();
+
+        let mut wtxn = env.write_txn().unwrap();
+        let ids = env.sequence(&mut wtxn, "ids").unwrap();
+        let others = env.sequence(&mut wtxn, "others").unwrap();
+        assert_eq!(ids.next(&mut wtxn).unwrap(), 0);
+        assert_eq!(ids.reserve(&mut wtxn, 1000).unwrap(), 1..1001);
+        assert_eq!(others.next(&mut wtxn).unwrap(), 0);
+
+        // An aborted nested transaction hands out the same IDs again.
+        let mut nested = env.nested_write_txn(&mut wtxn).unwrap();
+        assert_eq!(ids.next(&mut nested).unwrap(), 1001);
+        nested.abort();
+
+        let mut nested = env.nested_write_txn(&mut wtxn).unwrap();
+        assert_eq!(ids.next(&mut nested).unwrap(), 1001);
+        nested.commit().unwrap();
+        assert_eq!(ids.peek(&wtxn).unwrap(), 1002);
+        wtxn.commit().unwrap();
+
+        let mut wtxn = env.write_txn().unwrap();
+        assert_eq!(ids.reserve(&mut wtxn, 10).unwrap(), 1002..1012);
+        wtxn.abort();
+
+        let mut wtxn = env.write_txn().unwrap();
+        assert_eq!(ids.next(&mut wtxn).unwrap(), 1002);
+        assert_eq!(others.next(&mut wtxn).unwrap(), 1);
+
+        // An exhausted sequence returns an error and keeps its next ID.
+        assert!(matches!(ids.reserve(&mut wtxn, u64::MAX), Err(Error::Encoding(_))));
+        assert_eq!(ids.peek(&wtxn).unwrap(), 1003);
+        assert_eq!(ids.reserve(&mut wtxn, u64::MAX - 1003).unwrap(), 1003..u64::MAX);
+        assert!(matches!(ids.next(&mut wtxn), Err(Error::Encoding(_))));
+        assert_eq!(ids.peek(&wtxn).unwrap(), u64::MAX);
+    }
 }
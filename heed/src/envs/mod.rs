@@ -27,12 +27,18 @@ use crate::{Database, DatabaseFlags};
 mod encrypted_env;
 mod env;
 mod env_open_options;
+mod retry;
+mod watchdog;
 
 #[cfg(master3)]
 pub use encrypted_env::EncryptedEnv;
 pub use env::Env;
 pub(crate) use env::EnvInner;
 pub use env_open_options::EnvOpenOptions;
+pub(crate) use retry::retry;
+pub use retry::{ExponentialBackoff, RetryPolicy};
+pub(crate) use watchdog::WatchedTxn;
+pub use watchdog::{ReadTxnReport, ReadTxnWatchdog};
 
 /// Records the current list of opened environments for tracking purposes. The canonical
 /// path of an environment is removed when either an `Env` or `EncryptedEnv` is closed.
@@ -0,0 +1,134 @@
+use std::thread;
+use std::time::Duration;
+
+#[allow(unused)] // for cargo auto doc links
+use crate::{Env, RwTxn};
+use crate::{Error, MdbError, Result};
+
+/// Decides whether a transaction closure must be run again after a failure.
+///
+/// It is used by the [`Env::write_with_retry`] and [`Env::read_with_retry`] helpers.
+/// Implementors can, for example, resize the environment when it returned a
+/// [`MdbError::MapFull`] error and ask for an immediate retry.
+pub trait RetryPolicy {
+    /// Returns how long to wait before the next attempt or `None` to give up and
+    /// return the error to the caller.
+    ///
+    /// `attempt` is the number of the attempt that just failed, starting at one.
+    fn retry_after(&self, attempt: u32, error: &Error) -> Option<Duration>;
+}
+
+impl<P: RetryPolicy + ?Sized> RetryPolicy for &P {
+    fn retry_after(&self, attempt: u32, error: &Error) -> Option<Duration> {
+        (**self).retry_after(attempt, error)
+    }
+}
+
+impl<P: RetryPolicy + ?Sized> RetryPolicy for &mut P {
+    fn retry_after(&self, attempt: u32, error: &Error) -> Option<Duration> {
+        (**self).retry_after(attempt, error)
+    }
+}
+
+/// A [`RetryPolicy`] that waits twice as long after each failed attempt.
+///
+/// By default, it tries at most three times, starts by waiting 10ms, never waits more
+/// than a second and only retries on [`ExponentialBackoff::is_transient`] errors.
+///
+/// ```
+/// use std::time::Duration;
+/// use heed::{Error, ExponentialBackoff, MdbError};
+///
+/// let mut policy = ExponentialBackoff::new();
+/// policy
+///     .max_attempts(5)
+///     .backoff(Duration::from_millis(1), Duration::from_millis(100))
+///     .retryable(|e| matches!(e, Error::Mdb(MdbError::ReadersFull)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ExponentialBackoff {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    retryable: fn(&Error) -> bool,
+}
+
+impl ExponentialBackoff {
+    /// Creates a new policy with the default parameters.
+    pub fn new() -> ExponentialBackoff {
+        ExponentialBackoff {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+            retryable: ExponentialBackoff::is_transient,
+        }
+    }
+
+    /// Set the maximum number of times the closure is run, including the first attempt.
+    pub fn max_attempts(&mut self, attempts: u32) -> &mut Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// Set the duration to wait after the first failure and the maximum duration to wait.
+    pub fn backoff(&mut self, initial: Duration, max: Duration) -> &mut Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Set the function that decides which errors are worth a retry.
+    pub fn retryable(&mut self, retryable: fn(&Error) -> bool) -> &mut Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Returns `true` for the [`MdbError::MapFull`] and [`MdbError::ReadersFull`] errors.
+    ///
+    /// Note that retrying after a [`MdbError::MapFull`] error only makes sense if the
+    /// environment is resized in the meantime, by another thread for example.
+    pub fn is_transient(error: &Error) -> bool {
+        matches!(error, Error::Mdb(MdbError::MapFull | MdbError::ReadersFull))
+    }
+}
+
+impl Default for ExponentialBackoff {
+    fn default() -> Self {
+        ExponentialBackoff::new()
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn retry_after(&self, attempt: u32, error: &Error) -> Option<Duration> {
+        if attempt >= self.max_attempts || !(self.retryable)(error) {
+            return None;
+        }
+
+        let factor = 1u32.checked_shl(attempt - 1).unwrap_or(u32::MAX);
+        let backoff = self.initial_backoff.saturating_mul(factor);
+        Some(backoff.min(self.max_backoff))
+    }
+}
+
+/// Runs the closure until it succeeds or the policy gives up.
+pub(crate) fn retry<R, P, F>(policy: &P, mut f: F) -> Result<R>
+where
+    P: RetryPolicy + ?Sized,
+    F: FnMut() -> Result<R>,
+{
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(error) => match policy.retry_after(attempt, &error) {
+                Some(backoff) => {
+                    if !backoff.is_zero() {
+                        thread::sleep(backoff);
+                    }
+                    attempt += 1;
+                }
+                None => return Err(error),
+            },
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[allow(unused)] // for cargo auto doc links
+use crate::{Env, EnvInfo, RoTxn};
+
+/// Options of the watchdog that tracks the read transactions of an [`Env`].
+///
+/// Once enabled with [`Env::enable_read_txn_watchdog`], every [`RoTxn`] created in this
+/// process records its creation time and, optionally, its creation backtrace.
+/// The read transactions opened for longer than the threshold are reported by
+/// [`Env::check_read_txns`].
+///
+/// ```
+/// use std::time::Duration;
+/// use heed::ReadTxnWatchdog;
+///
+/// let mut watchdog = ReadTxnWatchdog::new(Duration::from_secs(60));
+/// watchdog.capture_backtraces(true).on_long_lived(|report| {
+///     eprintln!("read txn {} is {} commits behind", report.txn_id, report.commits_behind);
+/// });
+/// ```
+#[derive(Clone)]
+pub struct ReadTxnWatchdog {
+    threshold: Duration,
+    capture_backtraces: bool,
+    callback: Option<Arc<ReportCallback>>,
+}
+
+type ReportCallback = dyn Fn(&ReadTxnReport) + Send + Sync;
+
+impl ReadTxnWatchdog {
+    /// Creates a watchdog that considers read transactions opened
+    /// for longer than `threshold` as long-lived.
+    pub fn new(threshold: Duration) -> ReadTxnWatchdog {
+        ReadTxnWatchdog { threshold, capture_backtraces: false, callback: None }
+    }
+
+    /// Capture the backtrace of every read transaction when it is created.
+    ///
+    /// This is a debug mode, capturing backtraces is slow.
+    pub fn capture_backtraces(&mut self, enabled: bool) -> &mut Self {
+        self.capture_backtraces = enabled;
+        self
+    }
+
+    /// Set the function called for each long-lived read transaction
+    /// found by [`Env::check_read_txns`].
+    pub fn on_long_lived<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: Fn(&ReadTxnReport) + Send + Sync + 'static,
+    {
+        self.callback = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl fmt::Debug for ReadTxnWatchdog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadTxnWatchdog")
+            .field("threshold", &self.threshold)
+            .field("capture_backtraces", &self.capture_backtraces)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Describes a read transaction tracked by the [`ReadTxnWatchdog`].
+#[derive(Debug, Clone)]
+pub struct ReadTxnReport {
+    /// The ID of the transaction, see [`RoTxn::id`].
+    pub txn_id: usize,
+    /// For how long the transaction has been opened.
+    pub age: Duration,
+    /// The number of commits between the snapshot read by this
+    /// transaction and [`EnvInfo::last_txn_id`].
+    pub commits_behind: usize,
+    /// The backtrace of the transaction creation, if captured.
+    pub backtrace: Option<Arc<Backtrace>>,
+}
+
+/// The state of an enabled watchdog, shared by the environment and the transactions.
+pub(crate) struct Watchdog {
+    options: ReadTxnWatchdog,
+    next_key: AtomicU64,
+    txns: Mutex<HashMap<u64, TrackedTxn>>,
+}
+
+struct TrackedTxn {
+    txn_id: usize,
+    created_at: Instant,
+    backtrace: Option<Arc<Backtrace>>,
+}
+
+impl Watchdog {
+    pub(crate) fn new(options: ReadTxnWatchdog) -> Watchdog {
+        Watchdog { options, next_key: AtomicU64::new(0), txns: Mutex::default() }
+    }
+
+    /// Start tracking a read transaction, it is untracked when the returned value is dropped.
+    pub(crate) fn track(self: &Arc<Self>, txn_id: usize) -> WatchedTxn {
+        let backtrace =
+            self.options.capture_backtraces.then(|| Arc::new(Backtrace::force_capture()));
+        let tracked = TrackedTxn { txn_id, created_at: Instant::now(), backtrace };
+        let key = self.next_key.fetch_add(1, Ordering::Relaxed);
+        self.txns.lock().unwrap().insert(key, tracked);
+        WatchedTxn { watchdog: self.clone(), key }
+    }
+
+    /// Returns the reports of the tracked transactions, oldest first.
+    pub(crate) fn reports(&self, last_txn_id: usize) -> Vec<ReadTxnReport> {
+        let now = Instant::now();
+        let txns = self.txns.lock().unwrap();
+        let mut reports: Vec<_> = txns
+            .values()
+            .map(|tracked| ReadTxnReport {
+                txn_id: tracked.txn_id,
+                age: now.saturating_duration_since(tracked.created_at),
+                commits_behind: last_txn_id.saturating_sub(tracked.txn_id),
+                backtrace: tracked.backtrace.clone(),
+            })
+            .collect();
+        drop(txns);
+        reports.sort_unstable_by_key(|report| std::cmp::Reverse(report.age));
+        reports
+    }
+
+    /// Returns the long-lived transactions and calls the callback for each of them.
+    pub(crate) fn check(&self, last_txn_id: usize) -> Vec<ReadTxnReport> {
+        let mut reports = self.reports(last_txn_id);
+        reports.retain(|report| report.age >= self.options.threshold);
+        if let Some(callback) = &self.options.callback {
+            reports.iter().for_each(|report| (callback)(report));
+        }
+        reports
+    }
+}
+
+/// A read transaction registration, removed from the watchdog when dropped.
+pub(crate) struct WatchedTxn {
+    watchdog: Arc<Watchdog>,
+    key: u64,
+}
+
+impl Drop for WatchedTxn {
+    fn drop(&mut self) {
+        self.watchdog.txns.lock().unwrap().remove(&self.key);
+    }
+}
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+use std::{error, fmt};
+
+use crate::envs::EnvInner;
+use crate::mdb::ffi;
+use crate::{BoxedError, Error};
+
+/// The maximum number of key bytes kept in an [`ErrorContext`].
+const MAX_KEY_LEN: usize = 64;
+
+/// Describes where an [`Error`](crate::Error) happened: the database,
+/// the operation and the key involved.
+///
+/// The context is only recorded once enabled with
+/// [`Env::enable_error_context`](crate::Env::enable_error_context)
+/// and is retrieved with [`Error::context`](crate::Error::context).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorContext {
+    database_name: Option<Arc<str>>,
+    dbi: ffi::MDB_dbi,
+    operation: &'static str,
+    key: Option<Box<[u8]>>,
+    key_len: usize,
+}
+
+impl ErrorContext {
+    pub(crate) fn new(
+        database_name: Option<Arc<str>>,
+        dbi: ffi::MDB_dbi,
+        operation: &'static str,
+        key: Option<&[u8]>,
+    ) -> ErrorContext {
+        let key_len = key.map_or(0, <[u8]>::len);
+        let key = key.map(|key| Box::from(&key[..key.len().min(MAX_KEY_LEN)]));
+        ErrorContext { database_name, dbi, operation, key, key_len }
+    }
+
+    /// The name of the database, `None` for the unnamed database.
+    pub fn database_name(&self) -> Option<&str> {
+        self.database_name.as_deref()
+    }
+
+    /// The LMDB handle of the database.
+    pub fn dbi(&self) -> u32 {
+        self.dbi
+    }
+
+    /// The name of the method that failed, e.g. `get`, `put` or `iter`.
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// The raw bytes of the key, truncated to the first 64 bytes.
+    ///
+    /// Returns `None` when the key is unknown, e.g. when the key itself failed to encode.
+    pub fn key(&self) -> Option<&[u8]> {
+        self.key.as_deref()
+    }
+
+    /// Whether the key returned by [`Self::key`] has been truncated.
+    pub fn is_key_truncated(&self) -> bool {
+        self.key.as_ref().is_some_and(|key| key.len() < self.key_len)
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} on ", self.operation)?;
+        match &self.database_name {
+            Some(name) => write!(f, "database {name:?}")?,
+            None => write!(f, "database #{}", self.dbi)?,
+        }
+        if let Some(key) = &self.key {
+            f.write_str(" with key 0x")?;
+            for byte in key.iter() {
+                write!(f, "{byte:02x}")?;
+            }
+            if self.is_key_truncated() {
+                write!(f, "... ({} bytes)", self.key_len)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The database and the name of an operation, attached to the errors of the operation
+/// if the environment records the context of the errors.
+#[derive(Clone, Copy)]
+pub(crate) struct Operation<'a> {
+    env: &'a EnvInner,
+    dbi: ffi::MDB_dbi,
+    name: &'static str,
+}
+
+impl<'a> Operation<'a> {
+    pub(crate) fn new(env: &'a EnvInner, dbi: ffi::MDB_dbi, name: &'static str) -> Operation<'a> {
+        Operation { env, dbi, name }
+    }
+
+    /// Attaches the context of the operation and the key involved to the error.
+    pub(crate) fn error(self, error: impl Into<Error>, key: Option<&[u8]>) -> Error {
+        self.env.error_context(error.into(), self.dbi, self.name, key)
+    }
+
+    /// Returns a function that turns an encoding error into an [`Error`] with this context.
+    pub(crate) fn encoding(self, key: Option<&'a [u8]>) -> impl FnOnce(BoxedError) -> Error + 'a {
+        move |error| self.error(Error::Encoding(error), key)
+    }
+
+    /// Returns a function that turns a decoding error into an [`Error`] with this context.
+    pub(crate) fn decoding(self, key: Option<&'a [u8]>) -> impl FnOnce(BoxedError) -> Error + 'a {
+        move |error| self.error(Error::Decoding(error), key)
+    }
+}
+
+/// Wraps the source of an [`Error`](crate::Error) to attach its context to it.
+#[derive(Debug)]
+pub(crate) struct ContextError {
+    pub(crate) context: ErrorContext,
+    source: BoxedError,
+}
+
+impl ContextError {
+    pub(crate) fn new(context: ErrorContext, source: BoxedError) -> ContextError {
+        ContextError { context, source }
+    }
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.source, self.context)
+    }
+}
+
+impl error::Error for ContextError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.source)
+    }
+}
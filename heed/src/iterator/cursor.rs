@@ -0,0 +1,425 @@
+use std::borrow::Cow;
+use std::marker;
+
+use crate::cursor::MoveOperation;
+use crate::*;
+
+/// Decodes the entry the cursor returned and attaches the context to the decoding error.
+fn decode_entry<'c, KC, DC>(
+    cursor: &RoCursor,
+    operation: &'static str,
+    entry: Option<(&'c [u8], &'c [u8])>,
+) -> Result<Option<(KC::DItem, DC::DItem)>>
+where
+    KC: BytesDecode<'c>,
+    DC: BytesDecode<'c>,
+{
+    match entry {
+        Some((key, data)) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+            (Ok(key), Ok(data)) => Ok(Some((key, data))),
+            (Err(e), _) | (_, Err(e)) => {
+                Err(cursor.error_context(Error::Decoding(e), operation, Some(key)))
+            }
+        },
+        None => Ok(None),
+    }
+}
+
+/// Moves the cursor on the given key and returns the entry it points to.
+fn seek_key<'txn, 'a, KC>(
+    cursor: &mut RoCursor<'txn>,
+    key: &'a KC::EItem,
+) -> Result<Option<(&'txn [u8], &'txn [u8])>>
+where
+    KC: BytesEncode<'a>,
+{
+    let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+        .map_err(|e| cursor.error_context(Error::Encoding(e), "seek", None))?;
+    if cursor.move_on_key(&key_bytes)? {
+        cursor.current()
+    } else {
+        Ok(None)
+    }
+}
+
+/// Moves the cursor on the first key greater than or equal to the given one.
+fn seek_key_ge<'txn, 'a, KC>(
+    cursor: &mut RoCursor<'txn>,
+    key: &'a KC::EItem,
+) -> Result<Option<(&'txn [u8], &'txn [u8])>>
+where
+    KC: BytesEncode<'a>,
+{
+    let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+        .map_err(|e| cursor.error_context(Error::Encoding(e), "seek_ge", None))?;
+    cursor.move_on_key_greater_than_or_equal_to(&key_bytes)
+}
+
+/// A read-only cursor that can freely move in a database.
+///
+/// Unlike the iterators, a cursor can go back and forth and jump
+/// to any key without being reopened. It is created with [`Database::cursor`].
+///
+/// ```
+/// # use heed::EnvOpenOptions;
+/// use heed::Database;
+/// use heed::types::*;
+/// use heed::byteorder::BigEndian;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let dir = tempfile::tempdir()?;
+/// # let env = unsafe { EnvOpenOptions::new()
+/// #     .map_size(10 * 1024 * 1024) // 10MB
+/// #     .max_dbs(3000)
+/// #     .open(dir.path())?
+/// # };
+/// type BEU32 = U32<BigEndian>;
+///
+/// let mut wtxn = env.write_txn()?;
+/// let db: Database<BEU32, Str> = env.create_database(&mut wtxn, Some("cursor"))?;
+///
+/// # db.clear(&mut wtxn)?;
+/// db.put(&mut wtxn, &13, "i-am-thirteen")?;
+/// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+/// db.put(&mut wtxn, &42, "i-am-forty-two")?;
+///
+/// let mut cursor = db.cursor(&wtxn)?;
+/// assert_eq!(cursor.seek_ge(&20)?, Some((27, "i-am-twenty-seven")));
+/// assert_eq!(cursor.prev()?, Some((13, "i-am-thirteen")));
+/// assert_eq!(cursor.prev()?, None);
+/// assert_eq!(cursor.last()?, Some((42, "i-am-forty-two")));
+/// assert_eq!(cursor.seek(&27)?, Some((27, "i-am-twenty-seven")));
+/// assert_eq!(cursor.next()?, Some((42, "i-am-forty-two")));
+/// assert_eq!(cursor.next()?, None);
+///
+/// drop(cursor);
+/// wtxn.commit()?;
+/// # Ok(()) }
+/// ```
+pub struct DatabaseCursor<'txn, KC, DC, C = DefaultComparator> {
+    cursor: RoCursor<'txn>,
+    _phantom: marker::PhantomData<(KC, DC, C)>,
+}
+
+impl<'txn, KC, DC, C> DatabaseCursor<'txn, KC, DC, C> {
+    pub(crate) fn new(cursor: RoCursor<'txn>) -> DatabaseCursor<'txn, KC, DC, C> {
+        DatabaseCursor { cursor, _phantom: marker::PhantomData }
+    }
+
+    /// Moves the cursor on the given key and returns its first value.
+    ///
+    /// Returns `None` and leaves the cursor unpositioned if the key doesn't exist.
+    pub fn seek<'a>(&mut self, key: &'a KC::EItem) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesEncode<'a> + BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
+        let entry = seek_key::<KC>(&mut self.cursor, key)?;
+        decode_entry::<KC, DC>(&self.cursor, "seek", entry)
+    }
+
+    /// Moves the cursor on the first key greater than or equal to the given one.
+    pub fn seek_ge<'a>(&mut self, key: &'a KC::EItem) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesEncode<'a> + BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
+        let entry = seek_key_ge::<KC>(&mut self.cursor, key)?;
+        decode_entry::<KC, DC>(&self.cursor, "seek_ge", entry)
+    }
+
+    /// Moves the cursor on the first entry of the database.
+    pub fn first(&mut self) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
+        let entry = self.cursor.move_on_first(MoveOperation::Any)?;
+        decode_entry::<KC, DC>(&self.cursor, "first", entry)
+    }
+
+    /// Moves the cursor on the last entry of the database.
+    pub fn last(&mut self) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
+        let entry = self.cursor.move_on_last(MoveOperation::Any)?;
+        decode_entry::<KC, DC>(&self.cursor, "last", entry)
+    }
+
+    /// Moves the cursor on the next entry, including the duplicate values of the current key.
+    ///
+    /// Moves on the first entry if the cursor is not positioned yet.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
+        let entry = self.cursor.move_on_next(MoveOperation::Any)?;
+        decode_entry::<KC, DC>(&self.cursor, "next", entry)
+    }
+
+    /// Moves the cursor on the previous entry, including the duplicate values of the current key.
+    ///
+    /// Moves on the last entry if the cursor is not positioned yet.
+    pub fn prev(&mut self) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
+        let entry = self.cursor.move_on_prev(MoveOperation::Any)?;
+        decode_entry::<KC, DC>(&self.cursor, "prev", entry)
+    }
+
+    /// Moves the cursor on the next value of the current key.
+    ///
+    /// Returns `None` when the cursor is on the last value of the key,
+    /// or if the database is not [`DUP_SORT`](crate::DatabaseFlags::DUP_SORT).
+    pub fn next_dup(&mut self) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
+        let entry = self.cursor.move_on_next(MoveOperation::Dup)?;
+        decode_entry::<KC, DC>(&self.cursor, "next_dup", entry)
+    }
+
+    /// Returns the entry the cursor is pointing on, without moving it.
+    pub fn current(&mut self) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'txn>,
+        DC: BytesDecode<'txn>,
+    {
+        let entry = self.cursor.current()?;
+        decode_entry::<KC, DC>(&self.cursor, "current", entry)
+    }
+
+    /// Returns the number of values of the key the cursor is pointing on.
+    ///
+    /// The database must be [`DUP_SORT`](crate::DatabaseFlags::DUP_SORT),
+    /// an [`MdbError::Incompatible`] error is returned otherwise.
+    pub fn count_dups(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+
+    /// Change the codec types of this cursor, specifying the codecs.
+    pub fn remap_types<KC2, DC2>(self) -> DatabaseCursor<'txn, KC2, DC2, C> {
+        DatabaseCursor { cursor: self.cursor, _phantom: marker::PhantomData }
+    }
+
+    /// Change the key codec type of this cursor, specifying the new codec.
+    pub fn remap_key_type<KC2>(self) -> DatabaseCursor<'txn, KC2, DC, C> {
+        self.remap_types::<KC2, DC>()
+    }
+
+    /// Change the data codec type of this cursor, specifying the new codec.
+    pub fn remap_data_type<DC2>(self) -> DatabaseCursor<'txn, KC, DC2, C> {
+        self.remap_types::<KC, DC2>()
+    }
+}
+
+impl<KC, DC, C> fmt::Debug for DatabaseCursor<'_, KC, DC, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabaseCursor").finish()
+    }
+}
+
+/// A read-write cursor that can freely move in a database and modify it.
+///
+/// The entries it returns borrow the cursor, they must be dropped
+/// before modifying the database. This is what makes [`Self::put`]
+/// and [`Self::delete`] safe to call, unlike the `put_current` and
+/// `del_current` methods of the iterators. It is created with [`Database::cursor_mut`].
+///
+/// ```
+/// # use heed::EnvOpenOptions;
+/// use heed::Database;
+/// use heed::types::*;
+/// use heed::byteorder::BigEndian;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let dir = tempfile::tempdir()?;
+/// # let env = unsafe { EnvOpenOptions::new()
+/// #     .map_size(10 * 1024 * 1024) // 10MB
+/// #     .max_dbs(3000)
+/// #     .open(dir.path())?
+/// # };
+/// type BEU32 = U32<BigEndian>;
+///
+/// let mut wtxn = env.write_txn()?;
+/// let db: Database<BEU32, Str> = env.create_database(&mut wtxn, Some("cursor-mut"))?;
+///
+/// # db.clear(&mut wtxn)?;
+/// db.put(&mut wtxn, &13, "i-am-thirteen")?;
+/// db.put(&mut wtxn, &27, "i-am-twenty-seven")?;
+///
+/// let mut cursor = db.cursor_mut(&mut wtxn)?;
+/// while let Some((key, _)) = cursor.next()? {
+///     if key == 13 {
+///         assert!(cursor.delete()?);
+///     }
+/// }
+/// cursor.put(&42, "i-am-forty-two")?;
+/// assert_eq!(cursor.current()?, Some((42, "i-am-forty-two")));
+///
+/// drop(cursor);
+/// assert_eq!(db.get(&wtxn, &13)?, None);
+/// assert_eq!(db.len(&wtxn)?, 2);
+///
+/// wtxn.commit()?;
+/// # Ok(()) }
+/// ```
+pub struct DatabaseCursorMut<'txn, KC, DC, C = DefaultComparator> {
+    cursor: RwCursor<'txn>,
+    _phantom: marker::PhantomData<(KC, DC, C)>,
+}
+
+impl<'txn, KC, DC, C> DatabaseCursorMut<'txn, KC, DC, C> {
+    pub(crate) fn new(cursor: RwCursor<'txn>) -> DatabaseCursorMut<'txn, KC, DC, C> {
+        DatabaseCursorMut { cursor, _phantom: marker::PhantomData }
+    }
+
+    /// Moves the cursor on the given key and returns its first value.
+    ///
+    /// For more info, see [`DatabaseCursor::seek`].
+    pub fn seek<'c, 'a>(&'c mut self, key: &'a KC::EItem) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesEncode<'a> + BytesDecode<'c>,
+        DC: BytesDecode<'c>,
+    {
+        let entry = seek_key::<KC>(&mut self.cursor, key)?;
+        decode_entry::<KC, DC>(&self.cursor, "seek", entry)
+    }
+
+    /// Moves the cursor on the first key greater than or equal to the given one.
+    pub fn seek_ge<'c, 'a>(
+        &'c mut self,
+        key: &'a KC::EItem,
+    ) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesEncode<'a> + BytesDecode<'c>,
+        DC: BytesDecode<'c>,
+    {
+        let entry = seek_key_ge::<KC>(&mut self.cursor, key)?;
+        decode_entry::<KC, DC>(&self.cursor, "seek_ge", entry)
+    }
+
+    /// Moves the cursor on the first entry of the database.
+    pub fn first<'c>(&'c mut self) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'c>,
+        DC: BytesDecode<'c>,
+    {
+        let entry = self.cursor.move_on_first(MoveOperation::Any)?;
+        decode_entry::<KC, DC>(&self.cursor, "first", entry)
+    }
+
+    /// Moves the cursor on the last entry of the database.
+    pub fn last<'c>(&'c mut self) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'c>,
+        DC: BytesDecode<'c>,
+    {
+        let entry = self.cursor.move_on_last(MoveOperation::Any)?;
+        decode_entry::<KC, DC>(&self.cursor, "last", entry)
+    }
+
+    /// Moves the cursor on the next entry, including the duplicate values of the current key.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<'c>(&'c mut self) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'c>,
+        DC: BytesDecode<'c>,
+    {
+        let entry = self.cursor.move_on_next(MoveOperation::Any)?;
+        decode_entry::<KC, DC>(&self.cursor, "next", entry)
+    }
+
+    /// Moves the cursor on the previous entry, including the duplicate values of the current key.
+    pub fn prev<'c>(&'c mut self) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'c>,
+        DC: BytesDecode<'c>,
+    {
+        let entry = self.cursor.move_on_prev(MoveOperation::Any)?;
+        decode_entry::<KC, DC>(&self.cursor, "prev", entry)
+    }
+
+    /// Moves the cursor on the next value of the current key.
+    pub fn next_dup<'c>(&'c mut self) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'c>,
+        DC: BytesDecode<'c>,
+    {
+        let entry = self.cursor.move_on_next(MoveOperation::Dup)?;
+        decode_entry::<KC, DC>(&self.cursor, "next_dup", entry)
+    }
+
+    /// Returns the entry the cursor is pointing on, without moving it.
+    pub fn current<'c>(&'c mut self) -> Result<Option<(KC::DItem, DC::DItem)>>
+    where
+        KC: BytesDecode<'c>,
+        DC: BytesDecode<'c>,
+    {
+        let entry = self.cursor.current()?;
+        decode_entry::<KC, DC>(&self.cursor, "current", entry)
+    }
+
+    /// Returns the number of values of the key the cursor is pointing on.
+    ///
+    /// For more info, see [`DatabaseCursor::count_dups`].
+    pub fn count_dups(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+
+    /// Inserts a key-value pair in the database, replacing the value of an
+    /// existing key, and moves the cursor on it.
+    ///
+    /// In a [`DUP_SORT`](crate::DatabaseFlags::DUP_SORT) database the value
+    /// is added to the values of the key.
+    pub fn put<'a>(&mut self, key: &'a KC::EItem, data: &'a DC::EItem) -> Result<()>
+    where
+        KC: BytesEncode<'a>,
+        DC: BytesEncode<'a>,
+    {
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(|e| self.cursor.error_context(Error::Encoding(e), "put", None))?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data)
+            .map_err(|e| self.cursor.error_context(Error::Encoding(e), "put", Some(&key_bytes)))?;
+        // safety: the entries returned by this cursor borrow it and therefore
+        //         can't be alive, nor be used to feed this function.
+        unsafe { self.cursor.put_current_with_flags(PutFlags::empty(), &key_bytes, &data_bytes) }
+    }
+
+    /// Deletes the entry the cursor is pointing on.
+    ///
+    /// Returns `true` if the entry was successfully deleted. The next
+    /// call to [`Self::next`] returns the entry that followed it.
+    pub fn delete(&mut self) -> Result<bool> {
+        // safety: the entries returned by this cursor borrow it and therefore can't be alive.
+        unsafe { self.cursor.del_current() }
+    }
+
+    /// Change the codec types of this cursor, specifying the codecs.
+    pub fn remap_types<KC2, DC2>(self) -> DatabaseCursorMut<'txn, KC2, DC2, C> {
+        DatabaseCursorMut { cursor: self.cursor, _phantom: marker::PhantomData }
+    }
+
+    /// Change the key codec type of this cursor, specifying the new codec.
+    pub fn remap_key_type<KC2>(self) -> DatabaseCursorMut<'txn, KC2, DC, C> {
+        self.remap_types::<KC2, DC>()
+    }
+
+    /// Change the data codec type of this cursor, specifying the new codec.
+    pub fn remap_data_type<DC2>(self) -> DatabaseCursorMut<'txn, KC, DC2, C> {
+        self.remap_types::<KC, DC2>()
+    }
+}
+
+impl<KC, DC, C> fmt::Debug for DatabaseCursorMut<'_, KC, DC, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabaseCursorMut").finish()
+    }
+}
@@ -0,0 +1,324 @@
+use std::marker;
+use std::ops::Bound;
+
+use crate::cursor::MoveOperation;
+use crate::*;
+
+fn move_on_dup_range_start<'txn, CDUP: Comparator>(
+    cursor: &mut RoCursor<'txn>,
+    key: &[u8],
+    start_bound: &Bound<Vec<u8>>,
+) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
+    match start_bound {
+        Bound::Included(start) => cursor.move_on_key_data_greater_than_or_equal_to(key, start),
+        Bound::Excluded(start) => {
+            match cursor.move_on_key_data_greater_than_or_equal_to(key, start)? {
+                Some((_, data)) if CDUP::compare(data, start).is_eq() => {
+                    cursor.move_on_next(MoveOperation::Dup)
+                }
+                result => Ok(result),
+            }
+        }
+        Bound::Unbounded => {
+            if cursor.move_on_key(key)? {
+                cursor.current()
+            } else {
+                Ok(None)
+            }
+        }
+    }
+}
+
+fn move_on_dup_range_end<'txn, CDUP: Comparator>(
+    cursor: &mut RoCursor<'txn>,
+    key: &[u8],
+    end_bound: &Bound<Vec<u8>>,
+) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
+    let (end, included) = match end_bound {
+        Bound::Included(end) => (end, true),
+        Bound::Excluded(end) => (end, false),
+        Bound::Unbounded => {
+            return if cursor.move_on_key(key)? {
+                cursor.move_on_last(MoveOperation::Dup)
+            } else {
+                Ok(None)
+            };
+        }
+    };
+
+    match cursor.move_on_key_data_greater_than_or_equal_to(key, end)? {
+        Some((key, data)) if included && CDUP::compare(data, end).is_eq() => Ok(Some((key, data))),
+        Some(_) => cursor.move_on_prev(MoveOperation::Dup),
+        // Either the key doesn't exist or all its values are lower than the end bound.
+        None if cursor.move_on_key(key)? => cursor.move_on_last(MoveOperation::Dup),
+        None => Ok(None),
+    }
+}
+
+/// A read-only iterator over a range of the values of a single key.
+///
+/// It is created with [`Database::duplicates_range`].
+pub struct RoDupRange<'txn, KC, DC, CDUP = DefaultComparator> {
+    cursor: RoCursor<'txn>,
+    key: Vec<u8>,
+    move_on_start: bool,
+    start_bound: Bound<Vec<u8>>,
+    end_bound: Bound<Vec<u8>>,
+    _phantom: marker::PhantomData<(KC, DC, CDUP)>,
+}
+
+impl<'txn, KC, DC, CDUP> RoDupRange<'txn, KC, DC, CDUP> {
+    pub(crate) fn new(
+        cursor: RoCursor<'txn>,
+        key: Vec<u8>,
+        start_bound: Bound<Vec<u8>>,
+        end_bound: Bound<Vec<u8>>,
+    ) -> RoDupRange<'txn, KC, DC, CDUP> {
+        RoDupRange {
+            cursor,
+            key,
+            move_on_start: true,
+            start_bound,
+            end_bound,
+            _phantom: marker::PhantomData,
+        }
+    }
+
+    /// Change the codec types of this iterator, specifying the codecs.
+    pub fn remap_types<KC2, DC2>(self) -> RoDupRange<'txn, KC2, DC2, CDUP> {
+        RoDupRange {
+            cursor: self.cursor,
+            key: self.key,
+            move_on_start: self.move_on_start,
+            start_bound: self.start_bound,
+            end_bound: self.end_bound,
+            _phantom: marker::PhantomData,
+        }
+    }
+
+    /// Change the key codec type of this iterator, specifying the new codec.
+    pub fn remap_key_type<KC2>(self) -> RoDupRange<'txn, KC2, DC, CDUP> {
+        self.remap_types::<KC2, DC>()
+    }
+
+    /// Change the data codec type of this iterator, specifying the new codec.
+    pub fn remap_data_type<DC2>(self) -> RoDupRange<'txn, KC, DC2, CDUP> {
+        self.remap_types::<KC, DC2>()
+    }
+}
+
+impl<'txn, KC, DC, CDUP> Iterator for RoDupRange<'txn, KC, DC, CDUP>
+where
+    KC: BytesDecode<'txn>,
+    DC: BytesDecode<'txn>,
+    CDUP: Comparator,
+{
+    type Item = Result<(KC::DItem, DC::DItem)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = if self.move_on_start {
+            self.move_on_start = false;
+            move_on_dup_range_start::<CDUP>(&mut self.cursor, &self.key, &self.start_bound)
+        } else {
+            self.cursor.move_on_next(MoveOperation::Dup)
+        };
+
+        match result {
+            // The cursor may have left the key if it wasn't positioned on it.
+            Ok(Some((key, data))) if key == &self.key[..] => {
+                let must_be_returned = match &self.end_bound {
+                    Bound::Included(end) => CDUP::compare(data, end).is_le(),
+                    Bound::Excluded(end) => CDUP::compare(data, end).is_lt(),
+                    Bound::Unbounded => true,
+                };
+
+                if must_be_returned {
+                    match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                        (Ok(key), Ok(data)) => Some(Ok((key, data))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "duplicates_range",
+                            Some(key),
+                        ))),
+                    }
+                } else {
+                    None
+                }
+            }
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl<KC, DC, CDUP> fmt::Debug for RoDupRange<'_, KC, DC, CDUP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoDupRange").finish()
+    }
+}
+
+/// A reverse read-only iterator over a range of the values of a single key.
+///
+/// It is created with [`Database::rev_duplicates_range`].
+pub struct RoRevDupRange<'txn, KC, DC, CDUP = DefaultComparator> {
+    cursor: RoCursor<'txn>,
+    key: Vec<u8>,
+    move_on_end: bool,
+    start_bound: Bound<Vec<u8>>,
+    end_bound: Bound<Vec<u8>>,
+    _phantom: marker::PhantomData<(KC, DC, CDUP)>,
+}
+
+impl<'txn, KC, DC, CDUP> RoRevDupRange<'txn, KC, DC, CDUP> {
+    pub(crate) fn new(
+        cursor: RoCursor<'txn>,
+        key: Vec<u8>,
+        start_bound: Bound<Vec<u8>>,
+        end_bound: Bound<Vec<u8>>,
+    ) -> RoRevDupRange<'txn, KC, DC, CDUP> {
+        RoRevDupRange {
+            cursor,
+            key,
+            move_on_end: true,
+            start_bound,
+            end_bound,
+            _phantom: marker::PhantomData,
+        }
+    }
+
+    /// Change the codec types of this iterator, specifying the codecs.
+    pub fn remap_types<KC2, DC2>(self) -> RoRevDupRange<'txn, KC2, DC2, CDUP> {
+        RoRevDupRange {
+            cursor: self.cursor,
+            key: self.key,
+            move_on_end: self.move_on_end,
+            start_bound: self.start_bound,
+            end_bound: self.end_bound,
+            _phantom: marker::PhantomData,
+        }
+    }
+
+    /// Change the key codec type of this iterator, specifying the new codec.
+    pub fn remap_key_type<KC2>(self) -> RoRevDupRange<'txn, KC2, DC, CDUP> {
+        self.remap_types::<KC2, DC>()
+    }
+
+    /// Change the data codec type of this iterator, specifying the new codec.
+    pub fn remap_data_type<DC2>(self) -> RoRevDupRange<'txn, KC, DC2, CDUP> {
+        self.remap_types::<KC, DC2>()
+    }
+}
+
+impl<'txn, KC, DC, CDUP> Iterator for RoRevDupRange<'txn, KC, DC, CDUP>
+where
+    KC: BytesDecode<'txn>,
+    DC: BytesDecode<'txn>,
+    CDUP: Comparator,
+{
+    type Item = Result<(KC::DItem, DC::DItem)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = if self.move_on_end {
+            self.move_on_end = false;
+            move_on_dup_range_end::<CDUP>(&mut self.cursor, &self.key, &self.end_bound)
+        } else {
+            self.cursor.move_on_prev(MoveOperation::Dup)
+        };
+
+        match result {
+            // The cursor may have left the key if it wasn't positioned on it.
+            Ok(Some((key, data))) if key == &self.key[..] => {
+                let must_be_returned = match &self.start_bound {
+                    Bound::Included(start) => CDUP::compare(data, start).is_ge(),
+                    Bound::Excluded(start) => CDUP::compare(data, start).is_gt(),
+                    Bound::Unbounded => true,
+                };
+
+                if must_be_returned {
+                    match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                        (Ok(key), Ok(data)) => Some(Ok((key, data))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "rev_duplicates_range",
+                            Some(key),
+                        ))),
+                    }
+                } else {
+                    None
+                }
+            }
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl<KC, DC, CDUP> fmt::Debug for RoRevDupRange<'_, KC, DC, CDUP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoRevDupRange").finish()
+    }
+}
+
+/// A read-only iterator over the values of a single key of a
+/// [`DUP_FIXED`](crate::DatabaseFlags::DUP_FIXED) database,
+/// returned by pages of contiguous values.
+///
+/// Every item is the concatenation of up to a page of values, decoded with `DC`.
+/// It is created with [`Database::get_duplicates_fixed`].
+pub struct RoDupFixedChunks<'txn, DC> {
+    cursor: RoCursor<'txn>,
+    key: Vec<u8>,
+    move_on_key: bool,
+    _phantom: marker::PhantomData<DC>,
+}
+
+impl<'txn, DC> RoDupFixedChunks<'txn, DC> {
+    pub(crate) fn new(cursor: RoCursor<'txn>, key: Vec<u8>) -> RoDupFixedChunks<'txn, DC> {
+        RoDupFixedChunks { cursor, key, move_on_key: true, _phantom: marker::PhantomData }
+    }
+
+    /// Change the codec used to decode the pages of values.
+    pub fn remap_data_type<DC2>(self) -> RoDupFixedChunks<'txn, DC2> {
+        RoDupFixedChunks {
+            cursor: self.cursor,
+            key: self.key,
+            move_on_key: self.move_on_key,
+            _phantom: marker::PhantomData,
+        }
+    }
+}
+
+impl<'txn, DC> Iterator for RoDupFixedChunks<'txn, DC>
+where
+    DC: BytesDecode<'txn>,
+{
+    type Item = Result<DC::DItem>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = if self.move_on_key {
+            self.move_on_key = false;
+            self.cursor.move_on_key_multiple(&self.key)
+        } else {
+            self.cursor.move_on_next_multiple()
+        };
+
+        match result {
+            Ok(Some(data)) => match DC::bytes_decode(data) {
+                Ok(data) => Some(Ok(data)),
+                Err(e) => Some(Err(self.cursor.error_context(
+                    Error::Decoding(e),
+                    "get_duplicates_fixed",
+                    Some(&self.key),
+                ))),
+            },
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl<DC> fmt::Debug for RoDupFixedChunks<'_, DC> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoDupFixedChunks").finish()
+    }
+}
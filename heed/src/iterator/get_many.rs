@@ -0,0 +1,126 @@
+use std::marker;
+
+use crate::cursor::MoveOperation;
+use crate::databases::encode_into;
+use crate::*;
+
+/// Looks up many keys with a single cursor that only moves forward
+/// as long as the keys are given in the order of the comparator `C`.
+pub(crate) struct SortedLookup<'txn, C> {
+    cursor: RoCursor<'txn>,
+    /// The previous key looked up, `None` before the first lookup.
+    last_key: Option<Vec<u8>>,
+    /// The smallest entry greater than or equal to the previous key, `None` at the end.
+    entry: Option<(&'txn [u8], &'txn [u8])>,
+    _phantom: marker::PhantomData<C>,
+}
+
+impl<'txn, C: Comparator> SortedLookup<'txn, C> {
+    pub(crate) fn new(cursor: RoCursor<'txn>) -> SortedLookup<'txn, C> {
+        SortedLookup { cursor, last_key: None, entry: None, _phantom: marker::PhantomData }
+    }
+
+    /// Returns the first value of the given key.
+    pub(crate) fn get(&mut self, key: &[u8]) -> Result<Option<&'txn [u8]>> {
+        let in_order = self.last_key.as_ref().is_some_and(|last| C::compare(key, last).is_ge());
+
+        // The cursor points on the smallest key greater than or equal to the previous one,
+        // when the keys are in order there is no key between them and the given one.
+        let entry = match self.entry {
+            Some((current, _)) if in_order && C::compare(current, key).is_lt() => {
+                // Close keys are often on the same page, try a short hop first.
+                match self.cursor.move_on_next(MoveOperation::NoDup)? {
+                    Some((next, _)) if C::compare(next, key).is_lt() => {
+                        self.cursor.move_on_key_greater_than_or_equal_to(key)?
+                    }
+                    entry => entry,
+                }
+            }
+            entry if in_order => entry,
+            _ => self.cursor.move_on_key_greater_than_or_equal_to(key)?,
+        };
+
+        let last_key = self.last_key.get_or_insert_with(Vec::new);
+        last_key.clear();
+        last_key.extend_from_slice(key);
+        self.entry = entry;
+
+        Ok(entry.filter(|(current, _)| C::compare(current, key).is_eq()).map(|(_, data)| data))
+    }
+
+    pub(crate) fn error_context(&self, error: Error, key: Option<&[u8]>) -> Error {
+        self.cursor.error_context(error, "get_many", key)
+    }
+}
+
+/// A read-only iterator over the values of a sequence of keys.
+///
+/// It yields the first value of every key, in the order the keys are given, or `None`
+/// when a key doesn't exist. It is created with [`Database::get_many_sorted`].
+pub struct RoGetMany<'txn, I, KC, DC, C = DefaultComparator> {
+    lookup: SortedLookup<'txn, C>,
+    keys: I,
+    key_buffer: Vec<u8>,
+    _phantom: marker::PhantomData<(KC, DC)>,
+}
+
+impl<'txn, I, KC, DC, C: Comparator> RoGetMany<'txn, I, KC, DC, C> {
+    pub(crate) fn new(cursor: RoCursor<'txn>, keys: I) -> RoGetMany<'txn, I, KC, DC, C> {
+        RoGetMany {
+            lookup: SortedLookup::new(cursor),
+            keys,
+            key_buffer: Vec::new(),
+            _phantom: marker::PhantomData,
+        }
+    }
+
+    /// Change the data codec type of this iterator, specifying the new codec.
+    pub fn remap_data_type<DC2>(self) -> RoGetMany<'txn, I, KC, DC2, C> {
+        RoGetMany {
+            lookup: self.lookup,
+            keys: self.keys,
+            key_buffer: self.key_buffer,
+            _phantom: marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, 'txn, I, KC, DC, C> Iterator for RoGetMany<'txn, I, KC, DC, C>
+where
+    I: Iterator<Item = &'a KC::EItem>,
+    KC: BytesEncode<'a> + 'a,
+    DC: BytesDecode<'txn>,
+    C: Comparator,
+{
+    type Item = Result<Option<DC::DItem>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.keys.next()?;
+
+        self.key_buffer.clear();
+        if let Err(e) = encode_into::<KC>(key, &mut self.key_buffer) {
+            return Some(Err(self.lookup.error_context(Error::Encoding(e), None)));
+        }
+
+        match self.lookup.get(&self.key_buffer) {
+            Ok(Some(data)) => match DC::bytes_decode(data) {
+                Ok(data) => Some(Ok(Some(data))),
+                Err(e) => {
+                    Some(Err(self.lookup.error_context(Error::Decoding(e), Some(&self.key_buffer))))
+                }
+            },
+            Ok(None) => Some(Ok(None)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<I, KC, DC, C> fmt::Debug for RoGetMany<'_, I, KC, DC, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoGetMany").finish()
+    }
+}
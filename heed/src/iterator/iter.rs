@@ -152,6 +152,56 @@ impl<'txn, KC, DC, IM> RoIter<'txn, KC, DC, IM> {
     }
 }
 
+impl<'txn, KC, DC> RoIter<'txn, KC, DC, MoveBetweenKeys> {
+    /// Returns the number of values of the key of the last entry returned by this iterator.
+    ///
+    /// The count is read from LMDB without walking the duplicate values.
+    /// The database must be [`DUP_SORT`](crate::DatabaseFlags::DUP_SORT).
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::{DatabaseFlags, EnvOpenOptions};
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI64 = I64<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db = env.database_options()
+    ///     .types::<BEI64, BEI64>()
+    ///     .flags(DatabaseFlags::DUP_SORT)
+    ///     .name("dup-sort")
+    ///     .create(&mut wtxn)?;
+    ///
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &68, &120)?;
+    /// db.put(&mut wtxn, &68, &121)?;
+    /// db.put(&mut wtxn, &68, &122)?;
+    /// db.put(&mut wtxn, &35, &120)?;
+    ///
+    /// let mut iter = db.iter(&wtxn)?.move_between_keys();
+    /// assert_eq!(iter.next().transpose()?, Some((35, 120)));
+    /// assert_eq!(iter.dup_count()?, 1);
+    /// assert_eq!(iter.next().transpose()?, Some((68, 120)));
+    /// assert_eq!(iter.dup_count()?, 3);
+    ///
+    /// drop(iter);
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn dup_count(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+}
+
 impl<'txn, KC, DC, IM> Iterator for RoIter<'txn, KC, DC, IM>
 where
     KC: BytesDecode<'txn>,
@@ -171,7 +221,9 @@ where
         match result {
             Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                 (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                (Err(e), _) | (_, Err(e)) => {
+                    Some(Err(self.cursor.error_context(Error::Decoding(e), "iter", Some(key))))
+                }
             },
             Ok(None) => None,
             Err(e) => Some(Err(e)),
@@ -194,7 +246,9 @@ where
         match result {
             Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                 (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                (Err(e), _) | (_, Err(e)) => {
+                    Some(Err(self.cursor.error_context(Error::Decoding(e), "iter", Some(key))))
+                }
             },
             Ok(None) => None,
             Err(e) => Some(Err(e)),
@@ -269,8 +323,11 @@ impl<'txn, KC, DC, IM> RwIter<'txn, KC, DC, IM> {
         KC: BytesEncode<'a>,
         DC: BytesEncode<'a>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(|e| self.cursor.error_context(Error::Encoding(e), "put_current", None))?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current", Some(&key_bytes))
+        })?;
         self.cursor.put_current(&key_bytes, &data_bytes)
     }
 
@@ -298,7 +355,9 @@ impl<'txn, KC, DC, IM> RwIter<'txn, KC, DC, IM> {
         KC: BytesEncode<'a>,
         F: FnOnce(&mut ReservedSpace) -> io::Result<()>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current_reserved_with_flags", None)
+        })?;
         self.cursor.put_current_reserved_with_flags(flags, &key_bytes, data_size, write_func)
     }
 
@@ -329,8 +388,16 @@ impl<'txn, KC, DC, IM> RwIter<'txn, KC, DC, IM> {
         KC: BytesEncode<'a>,
         NDC: BytesEncode<'a>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = NDC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current_with_options", None)
+        })?;
+        let data_bytes: Cow<[u8]> = NDC::bytes_encode(data).map_err(|e| {
+            self.cursor.error_context(
+                Error::Encoding(e),
+                "put_current_with_options",
+                Some(&key_bytes),
+            )
+        })?;
         self.cursor.put_current_with_flags(flags, &key_bytes, &data_bytes)
     }
 
@@ -381,6 +448,15 @@ impl<'txn, KC, DC, IM> RwIter<'txn, KC, DC, IM> {
     }
 }
 
+impl<'txn, KC, DC> RwIter<'txn, KC, DC, MoveBetweenKeys> {
+    /// Returns the number of values of the key of the last entry returned by this iterator.
+    ///
+    /// For more info, see [`RoIter::dup_count`].
+    pub fn dup_count(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+}
+
 impl<'txn, KC, DC, IM> Iterator for RwIter<'txn, KC, DC, IM>
 where
     KC: BytesDecode<'txn>,
@@ -400,7 +476,9 @@ where
         match result {
             Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                 (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                (Err(e), _) | (_, Err(e)) => {
+                    Some(Err(self.cursor.error_context(Error::Decoding(e), "iter_mut", Some(key))))
+                }
             },
             Ok(None) => None,
             Err(e) => Some(Err(e)),
@@ -423,7 +501,9 @@ where
         match result {
             Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                 (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                (Err(e), _) | (_, Err(e)) => {
+                    Some(Err(self.cursor.error_context(Error::Decoding(e), "iter_mut", Some(key))))
+                }
             },
             Ok(None) => None,
             Err(e) => Some(Err(e)),
@@ -498,6 +578,15 @@ impl<'txn, KC, DC, IM> RoRevIter<'txn, KC, DC, IM> {
     }
 }
 
+impl<'txn, KC, DC> RoRevIter<'txn, KC, DC, MoveBetweenKeys> {
+    /// Returns the number of values of the key of the last entry returned by this iterator.
+    ///
+    /// For more info, see [`RoIter::dup_count`].
+    pub fn dup_count(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+}
+
 impl<'txn, KC, DC, IM> Iterator for RoRevIter<'txn, KC, DC, IM>
 where
     KC: BytesDecode<'txn>,
@@ -517,7 +606,9 @@ where
         match result {
             Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                 (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                (Err(e), _) | (_, Err(e)) => {
+                    Some(Err(self.cursor.error_context(Error::Decoding(e), "rev_iter", Some(key))))
+                }
             },
             Ok(None) => None,
             Err(e) => Some(Err(e)),
@@ -540,7 +631,9 @@ where
         match result {
             Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                 (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                (Err(e), _) | (_, Err(e)) => {
+                    Some(Err(self.cursor.error_context(Error::Decoding(e), "rev_iter", Some(key))))
+                }
             },
             Ok(None) => None,
             Err(e) => Some(Err(e)),
@@ -615,8 +708,11 @@ impl<'txn, KC, DC, IM> RwRevIter<'txn, KC, DC, IM> {
         KC: BytesEncode<'a>,
         DC: BytesEncode<'a>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(|e| self.cursor.error_context(Error::Encoding(e), "put_current", None))?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current", Some(&key_bytes))
+        })?;
         self.cursor.put_current(&key_bytes, &data_bytes)
     }
 
@@ -644,7 +740,9 @@ impl<'txn, KC, DC, IM> RwRevIter<'txn, KC, DC, IM> {
         KC: BytesEncode<'a>,
         F: FnOnce(&mut ReservedSpace) -> io::Result<()>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current_reserved_with_flags", None)
+        })?;
         self.cursor.put_current_reserved_with_flags(flags, &key_bytes, data_size, write_func)
     }
 
@@ -675,8 +773,16 @@ impl<'txn, KC, DC, IM> RwRevIter<'txn, KC, DC, IM> {
         KC: BytesEncode<'a>,
         NDC: BytesEncode<'a>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = NDC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current_with_options", None)
+        })?;
+        let data_bytes: Cow<[u8]> = NDC::bytes_encode(data).map_err(|e| {
+            self.cursor.error_context(
+                Error::Encoding(e),
+                "put_current_with_options",
+                Some(&key_bytes),
+            )
+        })?;
         self.cursor.put_current_with_flags(flags, &key_bytes, &data_bytes)
     }
 
@@ -729,6 +835,15 @@ impl<'txn, KC, DC, IM> RwRevIter<'txn, KC, DC, IM> {
     }
 }
 
+impl<'txn, KC, DC> RwRevIter<'txn, KC, DC, MoveBetweenKeys> {
+    /// Returns the number of values of the key of the last entry returned by this iterator.
+    ///
+    /// For more info, see [`RoIter::dup_count`].
+    pub fn dup_count(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+}
+
 impl<'txn, KC, DC, IM> Iterator for RwRevIter<'txn, KC, DC, IM>
 where
     KC: BytesDecode<'txn>,
@@ -748,7 +863,11 @@ where
         match result {
             Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                 (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                    Error::Decoding(e),
+                    "rev_iter_mut",
+                    Some(key),
+                ))),
             },
             Ok(None) => None,
             Err(e) => Some(Err(e)),
@@ -771,7 +890,11 @@ where
         match result {
             Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                 (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                    Error::Decoding(e),
+                    "rev_iter_mut",
+                    Some(key),
+                ))),
             },
             Ok(None) => None,
             Err(e) => Some(Err(e)),
@@ -1,7 +1,14 @@
+mod cursor;
+mod duplicates;
+mod get_many;
 mod iter;
 mod prefix;
 mod range;
 
+pub use self::cursor::{DatabaseCursor, DatabaseCursorMut};
+pub use self::duplicates::{RoDupFixedChunks, RoDupRange, RoRevDupRange};
+pub use self::get_many::RoGetMany;
+pub(crate) use self::get_many::SortedLookup;
 pub use self::iter::{RoIter, RoRevIter, RwIter, RwRevIter};
 pub use self::prefix::{RoPrefix, RoRevPrefix, RwPrefix, RwRevPrefix};
 pub use self::range::{RoRange, RoRevRange, RwRange, RwRevRange};
@@ -727,4 +734,61 @@ mod tests {
 
         wtxn.abort();
     }
+
+    #[test]
+    fn cursor_moves_through_duplicates() {
+        use crate::types::*;
+        use crate::{DatabaseFlags, EnvOpenOptions};
+
+        let dir = tempfile::tempdir().unwrap();
+        let env = unsafe {
+            EnvOpenOptions::new()
+                .map_size(10 * 1024 * 1024) // 10MB
+                .max_dbs(3000)
+                .open(dir.path())
+                .unwrap()
+        };
+
+        let mut wtxn = env.write_txn().unwrap();
+        let db = env
+            .database_options()
+            .types::<Str, Str>()
+            .flags(DatabaseFlags::DUP_SORT)
+            .create(&mut wtxn)
+            .unwrap();
+
+        for (key, data) in [("a", "1"), ("b", "1"), ("b", "2"), ("b", "3"), ("c", "1")] {
+            db.put(&mut wtxn, key, data).unwrap();
+        }
+
+        let mut cursor = db.cursor(&wtxn).unwrap();
+        assert_eq!(cursor.seek("b").unwrap(), Some(("b", "1")));
+        assert_eq!(cursor.count_dups().unwrap(), 3);
+        assert_eq!(cursor.next_dup().unwrap(), Some(("b", "2")));
+        assert_eq!(cursor.next_dup().unwrap(), Some(("b", "3")));
+        assert_eq!(cursor.next_dup().unwrap(), None);
+        assert_eq!(cursor.current().unwrap(), Some(("b", "3")));
+        assert_eq!(cursor.next().unwrap(), Some(("c", "1")));
+        assert_eq!(cursor.seek("bb").unwrap(), None);
+        assert_eq!(cursor.seek_ge("bb").unwrap(), Some(("c", "1")));
+        assert_eq!(cursor.prev().unwrap(), Some(("b", "3")));
+        drop(cursor);
+
+        let mut cursor = db.cursor_mut(&mut wtxn).unwrap();
+        while let Some((key, _)) = cursor.next().unwrap() {
+            if key == "b" {
+                assert!(cursor.delete().unwrap());
+            }
+        }
+        cursor.put("d", "1").unwrap();
+        assert_eq!(cursor.current().unwrap(), Some(("d", "1")));
+        assert_eq!(cursor.first().unwrap(), Some(("a", "1")));
+        assert_eq!(cursor.last().unwrap(), Some(("d", "1")));
+        drop(cursor);
+
+        let entries: Vec<_> = db.iter(&wtxn).unwrap().collect::<Result<_, _>>().unwrap();
+        assert_eq!(entries, [("a", "1"), ("c", "1"), ("d", "1")]);
+
+        wtxn.abort();
+    }
 }
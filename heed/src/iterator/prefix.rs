@@ -128,6 +128,15 @@ impl<'txn, KC, DC, C, IM> RoPrefix<'txn, KC, DC, C, IM> {
     }
 }
 
+impl<'txn, KC, DC, C> RoPrefix<'txn, KC, DC, C, MoveBetweenKeys> {
+    /// Returns the number of values of the key of the last entry returned by this iterator.
+    ///
+    /// For more info, see [`RoIter::dup_count`].
+    pub fn dup_count(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+}
+
 impl<'txn, KC, DC, C, IM> Iterator for RoPrefix<'txn, KC, DC, C, IM>
 where
     KC: BytesDecode<'txn>,
@@ -150,7 +159,11 @@ where
                 if key.starts_with(&self.prefix) {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "prefix_iter",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -182,7 +195,11 @@ where
                 if key.starts_with(&self.prefix) {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "prefix_iter",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -262,8 +279,11 @@ impl<'txn, KC, DC, C, IM> RwPrefix<'txn, KC, DC, C, IM> {
         KC: BytesEncode<'a>,
         DC: BytesEncode<'a>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(|e| self.cursor.error_context(Error::Encoding(e), "put_current", None))?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current", Some(&key_bytes))
+        })?;
         self.cursor.put_current(&key_bytes, &data_bytes)
     }
 
@@ -291,7 +311,9 @@ impl<'txn, KC, DC, C, IM> RwPrefix<'txn, KC, DC, C, IM> {
         KC: BytesEncode<'a>,
         F: FnOnce(&mut ReservedSpace) -> io::Result<()>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current_reserved_with_flags", None)
+        })?;
         self.cursor.put_current_reserved_with_flags(flags, &key_bytes, data_size, write_func)
     }
 
@@ -322,8 +344,16 @@ impl<'txn, KC, DC, C, IM> RwPrefix<'txn, KC, DC, C, IM> {
         KC: BytesEncode<'a>,
         NDC: BytesEncode<'a>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = NDC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current_with_options", None)
+        })?;
+        let data_bytes: Cow<[u8]> = NDC::bytes_encode(data).map_err(|e| {
+            self.cursor.error_context(
+                Error::Encoding(e),
+                "put_current_with_options",
+                Some(&key_bytes),
+            )
+        })?;
         self.cursor.put_current_with_flags(flags, &key_bytes, &data_bytes)
     }
 
@@ -379,6 +409,15 @@ impl<'txn, KC, DC, C, IM> RwPrefix<'txn, KC, DC, C, IM> {
     }
 }
 
+impl<'txn, KC, DC, C> RwPrefix<'txn, KC, DC, C, MoveBetweenKeys> {
+    /// Returns the number of values of the key of the last entry returned by this iterator.
+    ///
+    /// For more info, see [`RoIter::dup_count`].
+    pub fn dup_count(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+}
+
 impl<'txn, KC, DC, C, IM> Iterator for RwPrefix<'txn, KC, DC, C, IM>
 where
     KC: BytesDecode<'txn>,
@@ -401,7 +440,11 @@ where
                 if key.starts_with(&self.prefix) {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "prefix_iter_mut",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -433,7 +476,11 @@ where
                 if key.starts_with(&self.prefix) {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "prefix_iter_mut",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -516,6 +563,15 @@ impl<'txn, KC, DC, C, IM> RoRevPrefix<'txn, KC, DC, C, IM> {
     }
 }
 
+impl<'txn, KC, DC, C> RoRevPrefix<'txn, KC, DC, C, MoveBetweenKeys> {
+    /// Returns the number of values of the key of the last entry returned by this iterator.
+    ///
+    /// For more info, see [`RoIter::dup_count`].
+    pub fn dup_count(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+}
+
 impl<'txn, KC, DC, C, IM> Iterator for RoRevPrefix<'txn, KC, DC, C, IM>
 where
     KC: BytesDecode<'txn>,
@@ -538,7 +594,11 @@ where
                 if key.starts_with(&self.prefix) {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "rev_prefix_iter",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -569,7 +629,11 @@ where
                 if key.starts_with(&self.prefix) {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "rev_prefix_iter",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -649,8 +713,11 @@ impl<'txn, KC, DC, C, IM> RwRevPrefix<'txn, KC, DC, C, IM> {
         KC: BytesEncode<'a>,
         DC: BytesEncode<'a>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(|e| self.cursor.error_context(Error::Encoding(e), "put_current", None))?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current", Some(&key_bytes))
+        })?;
         self.cursor.put_current(&key_bytes, &data_bytes)
     }
 
@@ -678,7 +745,9 @@ impl<'txn, KC, DC, C, IM> RwRevPrefix<'txn, KC, DC, C, IM> {
         KC: BytesEncode<'a>,
         F: FnOnce(&mut ReservedSpace) -> io::Result<()>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current_reserved_with_flags", None)
+        })?;
         self.cursor.put_current_reserved_with_flags(flags, &key_bytes, data_size, write_func)
     }
 
@@ -709,8 +778,16 @@ impl<'txn, KC, DC, C, IM> RwRevPrefix<'txn, KC, DC, C, IM> {
         KC: BytesEncode<'a>,
         NDC: BytesEncode<'a>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = NDC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current_with_options", None)
+        })?;
+        let data_bytes: Cow<[u8]> = NDC::bytes_encode(data).map_err(|e| {
+            self.cursor.error_context(
+                Error::Encoding(e),
+                "put_current_with_options",
+                Some(&key_bytes),
+            )
+        })?;
         self.cursor.put_current_with_flags(flags, &key_bytes, &data_bytes)
     }
 
@@ -766,6 +843,15 @@ impl<'txn, KC, DC, C, IM> RwRevPrefix<'txn, KC, DC, C, IM> {
     }
 }
 
+impl<'txn, KC, DC, C> RwRevPrefix<'txn, KC, DC, C, MoveBetweenKeys> {
+    /// Returns the number of values of the key of the last entry returned by this iterator.
+    ///
+    /// For more info, see [`RoIter::dup_count`].
+    pub fn dup_count(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+}
+
 impl<'txn, KC, DC, C, IM> Iterator for RwRevPrefix<'txn, KC, DC, C, IM>
 where
     KC: BytesDecode<'txn>,
@@ -788,7 +874,11 @@ where
                 if key.starts_with(&self.prefix) {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "rev_prefix_iter_mut",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -819,7 +909,11 @@ where
                 if key.starts_with(&self.prefix) {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "rev_prefix_iter_mut",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
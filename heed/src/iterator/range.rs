@@ -118,6 +118,15 @@ impl<'txn, KC, DC, C, IM> RoRange<'txn, KC, DC, C, IM> {
     }
 }
 
+impl<'txn, KC, DC, C> RoRange<'txn, KC, DC, C, MoveBetweenKeys> {
+    /// Returns the number of values of the key of the last entry returned by this iterator.
+    ///
+    /// For more info, see [`RoIter::dup_count`].
+    pub fn dup_count(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+}
+
 impl<'txn, KC, DC, C, IM> Iterator for RoRange<'txn, KC, DC, C, IM>
 where
     KC: BytesDecode<'txn>,
@@ -146,7 +155,11 @@ where
                 if must_be_returned {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "range",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -181,7 +194,11 @@ where
                 if must_be_returned {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "range",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -272,8 +289,11 @@ impl<'txn, KC, DC, C, IM> RwRange<'txn, KC, DC, C, IM> {
         KC: BytesEncode<'a>,
         DC: BytesEncode<'a>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(|e| self.cursor.error_context(Error::Encoding(e), "put_current", None))?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current", Some(&key_bytes))
+        })?;
         self.cursor.put_current(&key_bytes, &data_bytes)
     }
 
@@ -301,7 +321,9 @@ impl<'txn, KC, DC, C, IM> RwRange<'txn, KC, DC, C, IM> {
         KC: BytesEncode<'a>,
         F: FnOnce(&mut ReservedSpace) -> io::Result<()>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current_reserved_with_flags", None)
+        })?;
         self.cursor.put_current_reserved_with_flags(flags, &key_bytes, data_size, write_func)
     }
 
@@ -332,8 +354,16 @@ impl<'txn, KC, DC, C, IM> RwRange<'txn, KC, DC, C, IM> {
         KC: BytesEncode<'a>,
         NDC: BytesEncode<'a>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = NDC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current_with_options", None)
+        })?;
+        let data_bytes: Cow<[u8]> = NDC::bytes_encode(data).map_err(|e| {
+            self.cursor.error_context(
+                Error::Encoding(e),
+                "put_current_with_options",
+                Some(&key_bytes),
+            )
+        })?;
         self.cursor.put_current_with_flags(flags, &key_bytes, &data_bytes)
     }
 
@@ -392,6 +422,15 @@ impl<'txn, KC, DC, C, IM> RwRange<'txn, KC, DC, C, IM> {
     }
 }
 
+impl<'txn, KC, DC, C> RwRange<'txn, KC, DC, C, MoveBetweenKeys> {
+    /// Returns the number of values of the key of the last entry returned by this iterator.
+    ///
+    /// For more info, see [`RoIter::dup_count`].
+    pub fn dup_count(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+}
+
 impl<'txn, KC, DC, C, IM> Iterator for RwRange<'txn, KC, DC, C, IM>
 where
     KC: BytesDecode<'txn>,
@@ -420,7 +459,11 @@ where
                 if must_be_returned {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "range_mut",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -455,7 +498,11 @@ where
                 if must_be_returned {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "range_mut",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -552,6 +599,15 @@ impl<'txn, KC, DC, C, IM> RoRevRange<'txn, KC, DC, C, IM> {
     }
 }
 
+impl<'txn, KC, DC, C> RoRevRange<'txn, KC, DC, C, MoveBetweenKeys> {
+    /// Returns the number of values of the key of the last entry returned by this iterator.
+    ///
+    /// For more info, see [`RoIter::dup_count`].
+    pub fn dup_count(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+}
+
 impl<'txn, KC, DC, C, IM> Iterator for RoRevRange<'txn, KC, DC, C, IM>
 where
     KC: BytesDecode<'txn>,
@@ -580,7 +636,11 @@ where
                 if must_be_returned {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "rev_range",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -617,7 +677,11 @@ where
                 if must_be_returned {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "rev_range",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -708,8 +772,11 @@ impl<'txn, KC, DC, C, IM> RwRevRange<'txn, KC, DC, C, IM> {
         KC: BytesEncode<'a>,
         DC: BytesEncode<'a>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key)
+            .map_err(|e| self.cursor.error_context(Error::Encoding(e), "put_current", None))?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current", Some(&key_bytes))
+        })?;
         self.cursor.put_current(&key_bytes, &data_bytes)
     }
 
@@ -737,7 +804,9 @@ impl<'txn, KC, DC, C, IM> RwRevRange<'txn, KC, DC, C, IM> {
         KC: BytesEncode<'a>,
         F: FnOnce(&mut ReservedSpace) -> io::Result<()>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current_reserved_with_flags", None)
+        })?;
         self.cursor.put_current_reserved_with_flags(flags, &key_bytes, data_size, write_func)
     }
 
@@ -768,8 +837,16 @@ impl<'txn, KC, DC, C, IM> RwRevRange<'txn, KC, DC, C, IM> {
         KC: BytesEncode<'a>,
         NDC: BytesEncode<'a>,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = NDC::bytes_encode(data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(|e| {
+            self.cursor.error_context(Error::Encoding(e), "put_current_with_options", None)
+        })?;
+        let data_bytes: Cow<[u8]> = NDC::bytes_encode(data).map_err(|e| {
+            self.cursor.error_context(
+                Error::Encoding(e),
+                "put_current_with_options",
+                Some(&key_bytes),
+            )
+        })?;
         self.cursor.put_current_with_flags(flags, &key_bytes, &data_bytes)
     }
 
@@ -828,6 +905,15 @@ impl<'txn, KC, DC, C, IM> RwRevRange<'txn, KC, DC, C, IM> {
     }
 }
 
+impl<'txn, KC, DC, C> RwRevRange<'txn, KC, DC, C, MoveBetweenKeys> {
+    /// Returns the number of values of the key of the last entry returned by this iterator.
+    ///
+    /// For more info, see [`RoIter::dup_count`].
+    pub fn dup_count(&self) -> Result<usize> {
+        self.cursor.count_duplicates()
+    }
+}
+
 impl<'txn, KC, DC, C, IM> Iterator for RwRevRange<'txn, KC, DC, C, IM>
 where
     KC: BytesDecode<'txn>,
@@ -856,7 +942,11 @@ where
                 if must_be_returned {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "rev_range_mut",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
@@ -893,7 +983,11 @@ where
                 if must_be_returned {
                     match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                         (Ok(key), Ok(data)) => Some(Ok((key, data))),
-                        (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+                        (Err(e), _) | (_, Err(e)) => Some(Err(self.cursor.error_context(
+                            Error::Decoding(e),
+                            "rev_range_mut",
+                            Some(key),
+                        ))),
                     }
                 } else {
                     None
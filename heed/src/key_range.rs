@@ -0,0 +1,79 @@
+use std::ops::{Bound, RangeBounds};
+
+/// An owned range of raw keys, returned by [`Database::split_ranges`].
+///
+/// It can be given to [`Database::range`] once the database key type is
+/// remapped to [`Bytes`](crate::types::Bytes).
+///
+/// [`Database::split_ranges`]: crate::Database::split_ranges
+/// [`Database::range`]: crate::Database::range
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyRange {
+    /// The start bound of the range.
+    pub start: Bound<Vec<u8>>,
+    /// The end bound of the range.
+    pub end: Bound<Vec<u8>>,
+}
+
+impl KeyRange {
+    /// The range covering every key.
+    pub fn full() -> KeyRange {
+        KeyRange { start: Bound::Unbounded, end: Bound::Unbounded }
+    }
+}
+
+impl RangeBounds<[u8]> for KeyRange {
+    fn start_bound(&self) -> Bound<&[u8]> {
+        self.start.as_ref().map(Vec::as_slice)
+    }
+
+    fn end_bound(&self) -> Bound<&[u8]> {
+        self.end.as_ref().map(Vec::as_slice)
+    }
+}
+
+/// Returns `n - 1` keys evenly spaced between `first` and `last`, in lexicographic order.
+///
+/// The keys share the common prefix of `first` and `last` and are interpolated
+/// on the eight bytes following it.
+pub(crate) fn interpolate_keys(first: &[u8], last: &[u8], n: usize) -> Vec<Vec<u8>> {
+    let prefix_len = first.iter().zip(last).take_while(|(a, b)| a == b).count();
+    let read_u64 = |key: &[u8]| {
+        let rest = &key[prefix_len..];
+        let mut bytes = [0; 8];
+        let len = rest.len().min(bytes.len());
+        bytes[..len].copy_from_slice(&rest[..len]);
+        u64::from_be_bytes(bytes)
+    };
+
+    let low = read_u64(first);
+    let width = u128::from(read_u64(last).saturating_sub(low));
+    (1..n)
+        .map(|i| {
+            let offset = width * i as u128 / n as u128;
+            let mut key = first[..prefix_len].to_vec();
+            key.extend_from_slice(&(low + offset as u64).to_be_bytes());
+            key
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolate_between_keys() {
+        let keys = interpolate_keys(b"user:\x00", b"user:\xff", 4);
+        assert_eq!(keys.len(), 3);
+        assert!(keys.iter().all(|key| key.starts_with(b"user:")));
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(keys[1][5], 0x7f);
+
+        assert!(interpolate_keys(b"a", b"b", 1).is_empty());
+        assert!(interpolate_keys(b"a", b"b", 0).is_empty());
+        assert!(interpolate_keys(b"same", b"same", 3)
+            .iter()
+            .all(|key| key == b"same\0\0\0\0\0\0\0\0"));
+    }
+}
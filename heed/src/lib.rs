@@ -69,43 +69,62 @@
 //! ```
 #![warn(missing_docs)]
 
+mod bulk_load;
 pub mod cookbook;
 mod cursor;
 mod databases;
 mod envs;
+mod error_context;
 pub mod iteration_method;
 mod iterator;
+mod key_range;
 mod mdb;
 mod reserved_space;
+mod sequence;
 mod txn;
 
 use std::ffi::CStr;
 use std::{error, fmt, io, mem, result};
 
 pub use byteorder;
+#[cfg(feature = "derive")]
+pub use heed_derive::{Databases, HeedCodec};
 use heed_traits as traits;
 pub use heed_types as types;
 
+pub use self::bulk_load::BulkLoadOptions;
 use self::cursor::{RoCursor, RwCursor};
-pub use self::databases::{Database, DatabaseOpenOptions, DatabaseStat};
+pub use self::databases::{
+    Clock, Database, DatabaseOpenOptions, DatabaseStat, Databases, IndexedDatabase, ManualClock,
+    MultimapDatabase, RoValues, SecondaryIndex, SystemClock, TtlDatabase, TtlIter, TtlSweeper,
+};
 #[cfg(master3)]
 pub use self::databases::{EncryptedDatabase, EncryptedDatabaseOpenOptions};
 #[cfg(master3)]
 pub use self::envs::EncryptedEnv;
 pub use self::envs::{
     env_closing_event, CompactionOption, DefaultComparator, Env, EnvClosingEvent, EnvInfo,
-    EnvOpenOptions, FlagSetMode, IntegerComparator,
+    EnvOpenOptions, ExponentialBackoff, FlagSetMode, IntegerComparator, ReadTxnReport,
+    ReadTxnWatchdog, RetryPolicy,
 };
+use self::error_context::ContextError;
+pub use self::error_context::ErrorContext;
 pub use self::iterator::{
-    RoIter, RoPrefix, RoRange, RoRevIter, RoRevPrefix, RoRevRange, RwIter, RwPrefix, RwRange,
+    DatabaseCursor, DatabaseCursorMut, RoDupFixedChunks, RoDupRange, RoGetMany, RoIter, RoPrefix,
+    RoRange, RoRevDupRange, RoRevIter, RoRevPrefix, RoRevRange, RwIter, RwPrefix, RwRange,
     RwRevIter, RwRevPrefix, RwRevRange,
 };
+pub use self::key_range::KeyRange;
 pub use self::mdb::error::Error as MdbError;
 use self::mdb::ffi::{from_val, into_val};
 pub use self::mdb::flags::{DatabaseFlags, EnvFlags, PutFlags};
 pub use self::reserved_space::ReservedSpace;
-pub use self::traits::{BoxedError, BytesDecode, BytesEncode, Comparator, LexicographicComparator};
-pub use self::txn::{AnyTls, RoTxn, RwTxn, TlsUsage, WithTls, WithoutTls};
+pub use self::sequence::Sequence;
+pub use self::traits::{
+    BoxedError, BytesDecode, BytesEncode, BytesEncodeInto, Comparator, LexicographicComparator,
+    MergeFunction,
+};
+pub use self::txn::{AnyTls, RoTxn, RwTxn, Savepoint, TlsUsage, WithTls, WithoutTls};
 
 /// The underlying LMDB library version information.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -160,6 +179,10 @@ pub fn lmdb_version() -> LmdbVersion {
 }
 
 /// An error that encapsulates all possible errors in this crate.
+///
+/// When the environment [records the error contexts](Env::enable_error_context),
+/// the encoding, decoding and I/O errors returned by the database operations
+/// also carry the database, the operation and the key involved, see [`Error::context`].
 #[derive(Debug)]
 pub enum Error {
     /// I/O error: can come from the standard library or be a rewrapped [`MdbError`].
@@ -192,6 +215,71 @@ impl fmt::Display for Error {
 
 impl error::Error for Error {}
 
+impl Error {
+    /// Returns the database, the operation and the key involved in this error.
+    ///
+    /// It is only available once the environment
+    /// [records the error contexts](Env::enable_error_context). The context is attached to
+    /// the source of the error, the variant stays the same: the [`Error::Io`] errors keep
+    /// their [`io::ErrorKind`] and their original error is the source of the context.
+    /// The [`Error::Mdb`] variant only holds the LMDB error code and carries no context.
+    ///
+    /// ```
+    /// use heed::types::*;
+    /// use heed::{Database, EnvOpenOptions, Error};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// env.enable_error_context();
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<Str, Bytes> = env.create_database(&mut wtxn, Some("users"))?;
+    /// db.put(&mut wtxn, "kero", &[0xff, 0xfe])?;
+    ///
+    /// let error = db.remap_data_type::<Str>().get(&wtxn, "kero").unwrap_err();
+    /// assert!(matches!(error, Error::Decoding(_)));
+    ///
+    /// let context = error.context().unwrap();
+    /// assert_eq!(context.database_name(), Some("users"));
+    /// assert_eq!(context.operation(), "get");
+    /// assert_eq!(context.key(), Some(&b"kero"[..]));
+    /// # Ok(()) }
+    /// ```
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Error::Encoding(error) | Error::Decoding(error) => {
+                error.downcast_ref::<ContextError>().map(|error| &error.context)
+            }
+            Error::Io(error) => {
+                error.get_ref()?.downcast_ref::<ContextError>().map(|error| &error.context)
+            }
+            Error::Mdb(_) | Error::EnvAlreadyOpened => None,
+        }
+    }
+
+    /// Attaches the given context to this error, if it doesn't already have one.
+    pub(crate) fn with_context(self, context: ErrorContext) -> Error {
+        if self.context().is_some() {
+            return self;
+        }
+
+        match self {
+            Error::Encoding(error) => Error::Encoding(Box::new(ContextError::new(context, error))),
+            Error::Decoding(error) => Error::Decoding(Box::new(ContextError::new(context, error))),
+            Error::Io(error) => {
+                let kind = error.kind();
+                Error::Io(io::Error::new(kind, ContextError::new(context, Box::new(error))))
+            }
+            error => error,
+        }
+    }
+}
+
 impl From<MdbError> for Error {
     fn from(error: MdbError) -> Error {
         match error {
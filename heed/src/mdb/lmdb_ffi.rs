@@ -1,14 +1,15 @@
 use std::ptr;
 
 pub use ffi::{
-    mdb_cursor_close, mdb_cursor_del, mdb_cursor_get, mdb_cursor_open, mdb_cursor_put,
-    mdb_dbi_open, mdb_del, mdb_drop, mdb_env_close, mdb_env_copyfd2, mdb_env_create,
-    mdb_env_get_fd, mdb_env_get_flags, mdb_env_get_maxkeysize, mdb_env_get_maxreaders,
-    mdb_env_info, mdb_env_open, mdb_env_set_flags, mdb_env_set_mapsize, mdb_env_set_maxdbs,
-    mdb_env_set_maxreaders, mdb_env_stat, mdb_env_sync, mdb_filehandle_t, mdb_get, mdb_put,
-    mdb_reader_check, mdb_set_compare, mdb_set_dupsort, mdb_stat, mdb_txn_abort, mdb_txn_begin,
-    mdb_txn_commit, mdb_txn_id, mdb_version, MDB_cursor, MDB_dbi, MDB_env, MDB_envinfo, MDB_stat,
-    MDB_txn, MDB_val, MDB_CP_COMPACT, MDB_CURRENT, MDB_RDONLY, MDB_RESERVE,
+    mdb_cursor_close, mdb_cursor_count, mdb_cursor_del, mdb_cursor_get, mdb_cursor_open,
+    mdb_cursor_put, mdb_dbi_flags, mdb_dbi_open, mdb_del, mdb_drop, mdb_env_close, mdb_env_copyfd2,
+    mdb_env_create, mdb_env_get_fd, mdb_env_get_flags, mdb_env_get_maxkeysize,
+    mdb_env_get_maxreaders, mdb_env_info, mdb_env_open, mdb_env_set_flags, mdb_env_set_mapsize,
+    mdb_env_set_maxdbs, mdb_env_set_maxreaders, mdb_env_stat, mdb_env_sync, mdb_filehandle_t,
+    mdb_get, mdb_put, mdb_reader_check, mdb_set_compare, mdb_set_dupsort, mdb_stat, mdb_txn_abort,
+    mdb_txn_begin, mdb_txn_commit, mdb_txn_id, mdb_version, MDB_cursor, MDB_dbi, MDB_env,
+    MDB_envinfo, MDB_stat, MDB_txn, MDB_val, MDB_CP_COMPACT, MDB_CURRENT, MDB_MULTIPLE, MDB_RDONLY,
+    MDB_RESERVE,
 };
 #[cfg(master3)]
 pub use ffi::{mdb_env_set_encrypt, MDB_enc_func};
@@ -26,6 +27,8 @@ pub mod cursor_op {
     pub const MDB_LAST_DUP: MDB_cursor_op = ffi::MDB_LAST_DUP;
     pub const MDB_SET_RANGE: MDB_cursor_op = ffi::MDB_SET_RANGE;
     pub const MDB_SET: MDB_cursor_op = ffi::MDB_SET;
+    pub const MDB_GET_BOTH: MDB_cursor_op = ffi::MDB_GET_BOTH;
+    pub const MDB_GET_BOTH_RANGE: MDB_cursor_op = ffi::MDB_GET_BOTH_RANGE;
     pub const MDB_PREV: MDB_cursor_op = ffi::MDB_PREV;
     pub const MDB_PREV_NODUP: MDB_cursor_op = ffi::MDB_PREV_NODUP;
     pub const MDB_PREV_DUP: MDB_cursor_op = ffi::MDB_PREV_DUP;
@@ -33,6 +36,8 @@ pub mod cursor_op {
     pub const MDB_NEXT_NODUP: MDB_cursor_op = ffi::MDB_NEXT_NODUP;
     pub const MDB_NEXT_DUP: MDB_cursor_op = ffi::MDB_NEXT_DUP;
     pub const MDB_GET_CURRENT: MDB_cursor_op = ffi::MDB_GET_CURRENT;
+    pub const MDB_GET_MULTIPLE: MDB_cursor_op = ffi::MDB_GET_MULTIPLE;
+    pub const MDB_NEXT_MULTIPLE: MDB_cursor_op = ffi::MDB_NEXT_MULTIPLE;
 }
 
 pub fn reserve_size_val(size: usize) -> ffi::MDB_val {
@@ -0,0 +1,86 @@
+use std::ops::Range;
+
+use byteorder::BigEndian;
+
+use crate::types::{Str, U64};
+use crate::{Database, Error, Result, RoTxn, RwTxn};
+
+/// The name of the database storing the next ID of every sequence of an environment.
+pub(crate) const SEQUENCES_DATABASE_NAME: &str = "__heed_sequences";
+
+/// A durable generator of monotonically increasing `u64` IDs, created with [`Env::sequence`].
+///
+/// The next ID of the sequence is stored in the environment and updated in the write
+/// transaction that hands out the IDs. IDs handed out by a transaction that is aborted,
+/// nested ones included, are therefore handed out again by the next transaction.
+///
+/// The first ID of a sequence is zero.
+///
+/// ```
+/// # use heed::EnvOpenOptions;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let dir = tempfile::tempdir()?;
+/// # let env = unsafe { EnvOpenOptions::new()
+/// #     .map_size(10 * 1024 * 1024) // 10MB
+/// #     .max_dbs(3000)
+/// #     .open(dir.path())?
+/// # };
+/// let mut wtxn = env.write_txn()?;
+/// let users = env.sequence(&mut wtxn, "users")?;
+///
+/// assert_eq!(users.next(&mut wtxn)?, 0);
+/// assert_eq!(users.next(&mut wtxn)?, 1);
+///
+/// // Reserve a block of IDs to hand them out without touching the database.
+/// assert_eq!(users.reserve(&mut wtxn, 1000)?, 2..1002);
+/// assert_eq!(users.peek(&wtxn)?, 1002);
+///
+/// wtxn.commit()?;
+/// # Ok(()) }
+/// ```
+///
+/// [`Env::sequence`]: crate::Env::sequence
+#[derive(Debug, Clone)]
+pub struct Sequence {
+    db: Database<Str, U64<BigEndian>>,
+    name: String,
+}
+
+impl Sequence {
+    pub(crate) fn new(db: Database<Str, U64<BigEndian>>, name: &str) -> Sequence {
+        Sequence { db, name: name.to_owned() }
+    }
+
+    /// The name of this sequence.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Hands out the next ID of this sequence.
+    ///
+    /// Returns an [`Error::Encoding`] error if every `u64` ID has already been handed out.
+    pub fn next(&self, wtxn: &mut RwTxn) -> Result<u64> {
+        self.reserve(wtxn, 1).map(|ids| ids.start)
+    }
+
+    /// Hands out a block of `count` consecutive IDs of this sequence at once.
+    ///
+    /// Returns an [`Error::Encoding`] error, leaving the sequence untouched, if there
+    /// aren't `count` IDs left in the sequence: the next ID can't be encoded.
+    pub fn reserve(&self, wtxn: &mut RwTxn, count: u64) -> Result<Range<u64>> {
+        let mut ids = None;
+        self.db.update(wtxn, &self.name, |next: Option<u64>| {
+            let start = next.unwrap_or(0);
+            // Returning the value unchanged leaves the entry untouched.
+            let Some(end) = start.checked_add(count) else { return next };
+            ids = Some(start..end);
+            Some(end)
+        })?;
+        ids.ok_or_else(|| Error::Encoding("the sequence ran out of IDs".into()))
+    }
+
+    /// Returns the ID this sequence will hand out next, without handing it out.
+    pub fn peek(&self, txn: &RoTxn) -> Result<u64> {
+        Ok(self.db.get(txn, &self.name)?.unwrap_or(0))
+    }
+}
@@ -1,13 +1,14 @@
 use std::borrow::Cow;
 use std::marker::PhantomData;
-use std::ops::Deref;
+use std::mem;
+use std::ops::{Deref, DerefMut};
 use std::ptr::{self, NonNull};
 use std::sync::Arc;
 
-use crate::envs::{Env, EnvInner};
+use crate::envs::{Env, EnvInner, WatchedTxn};
 use crate::mdb::error::mdb_result;
 use crate::mdb::ffi;
-use crate::Result;
+use crate::{Error, Result};
 
 /// A read-only transaction.
 ///
@@ -57,6 +58,17 @@ struct RoTxnInner<'e> {
     /// Makes the struct covariant and !Sync
     pub(crate) txn: Option<NonNull<ffi::MDB_txn>>,
     env: Cow<'e, Arc<EnvInner>>,
+    /// The registration of this read transaction in the watchdog, if enabled.
+    _watched: Option<WatchedTxn>,
+}
+
+impl<'e> RoTxnInner<'e> {
+    /// Wraps a read-only transaction and registers it in the watchdog of the environment.
+    fn read_only(txn: NonNull<ffi::MDB_txn>, env: Cow<'e, Arc<EnvInner>>) -> RoTxnInner<'e> {
+        let txn_id = unsafe { ffi::mdb_txn_id(txn.as_ptr()) };
+        let watched = env.watch_read_txn(txn_id);
+        RoTxnInner { txn: Some(txn), env, _watched: watched }
+    }
 }
 
 impl<'e, T> RoTxn<'e, T> {
@@ -73,7 +85,7 @@ impl<'e, T> RoTxn<'e, T> {
         };
 
         Ok(RoTxn {
-            inner: RoTxnInner { txn: NonNull::new(txn), env: Cow::Borrowed(&env.inner) },
+            inner: RoTxnInner::read_only(NonNull::new(txn).unwrap(), Cow::Borrowed(&env.inner)),
             _tls_marker: PhantomData,
         })
     }
@@ -91,7 +103,7 @@ impl<'e, T> RoTxn<'e, T> {
         };
 
         Ok(RoTxn {
-            inner: RoTxnInner { txn: NonNull::new(txn), env: Cow::Owned(env.inner) },
+            inner: RoTxnInner::read_only(NonNull::new(txn).unwrap(), Cow::Owned(env.inner)),
             _tls_marker: PhantomData,
         })
     }
@@ -100,10 +112,33 @@ impl<'e, T> RoTxn<'e, T> {
         self.inner.txn.unwrap()
     }
 
+    /// Returns this transaction whatever its TLS usage, like the `Deref` implementations.
+    pub(crate) fn as_any_tls(&self) -> &RoTxn<'e, AnyTls> {
+        // SAFETY: OK because repr(transparent) means RoTxn<T> always has the same layout
+        // as RoTxnInner.
+        unsafe { std::mem::transmute(self) }
+    }
+
     pub(crate) fn env_mut_ptr(&self) -> NonNull<ffi::MDB_env> {
         self.inner.env.env_mut_ptr()
     }
 
+    pub(crate) fn env_inner(&self) -> &EnvInner {
+        &self.inner.env
+    }
+
+    /// Attaches the database, the operation and the key to the error
+    /// if the environment records the context of the errors.
+    pub(crate) fn error_context(
+        &self,
+        error: Error,
+        dbi: ffi::MDB_dbi,
+        operation: &'static str,
+        key: Option<&[u8]>,
+    ) -> Error {
+        self.inner.env.error_context(error, dbi, operation, key)
+    }
+
     /// Return the transaction's ID.
     ///
     /// This returns the identifier associated with this transaction. For a
@@ -275,6 +310,11 @@ unsafe impl Send for RoTxn<'_, WithoutTls> {}
 /// ```
 pub struct RwTxn<'p> {
     pub(crate) txn: RoTxn<'p, WithoutTls>,
+    hooks: TxnHooks,
+    /// The hooks of the parent transaction, if this one is nested.
+    parent_hooks: Option<&'p mut TxnHooks>,
+    /// The buffers in which the keys and values are encoded before being written.
+    pub(crate) scratch: ScratchBuffers,
 }
 
 impl<'p> RwTxn<'p> {
@@ -292,9 +332,16 @@ impl<'p> RwTxn<'p> {
 
         Ok(RwTxn {
             txn: RoTxn {
-                inner: RoTxnInner { txn: NonNull::new(txn), env: Cow::Borrowed(&env.inner) },
+                inner: RoTxnInner {
+                    txn: NonNull::new(txn),
+                    env: Cow::Borrowed(&env.inner),
+                    _watched: None,
+                },
                 _tls_marker: PhantomData,
             },
+            hooks: TxnHooks::default(),
+            parent_hooks: None,
+            scratch: ScratchBuffers::default(),
         })
     }
 
@@ -308,9 +355,16 @@ impl<'p> RwTxn<'p> {
 
         Ok(RwTxn {
             txn: RoTxn {
-                inner: RoTxnInner { txn: NonNull::new(txn), env: Cow::Borrowed(&env.inner) },
+                inner: RoTxnInner {
+                    txn: NonNull::new(txn),
+                    env: Cow::Borrowed(&env.inner),
+                    _watched: None,
+                },
                 _tls_marker: PhantomData,
             },
+            hooks: TxnHooks::default(),
+            parent_hooks: Some(&mut parent.hooks),
+            scratch: ScratchBuffers::default(),
         })
     }
 
@@ -380,11 +434,198 @@ impl<'p> RwTxn<'p> {
         };
 
         Ok(RoTxn {
-            inner: RoTxnInner { txn: NonNull::new(txn), env: self.inner.env.clone() },
+            inner: RoTxnInner::read_only(NonNull::new(txn).unwrap(), self.inner.env.clone()),
             _tls_marker: PhantomData,
         })
     }
 
+    /// Create a savepoint, a nested write transaction that is aborted when dropped
+    /// unless [`Savepoint::release`] is called.
+    ///
+    /// The returned guard dereferences to a [`RwTxn`] and can therefore be used
+    /// everywhere a write transaction is expected, including to create other savepoints.
+    /// While the savepoint is alive, the parent transaction cannot be used.
+    ///
+    /// ## LMDB Limitations
+    ///
+    /// Nested write transactions are not supported when the environment
+    /// has been opened with the `WRITE_MAP` flag.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("savepoint"))?;
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &1, "i-am-one")?;
+    ///
+    /// {
+    ///     let mut sp = wtxn.savepoint()?;
+    ///     db.put(&mut sp, &2, "i-am-two")?;
+    ///     // the savepoint is dropped, its changes are discarded
+    /// }
+    ///
+    /// let mut sp = wtxn.savepoint()?;
+    /// db.put(&mut sp, &3, "i-am-three")?;
+    /// sp.release()?;
+    ///
+    /// assert_eq!(db.get(&wtxn, &1)?, Some("i-am-one"));
+    /// assert_eq!(db.get(&wtxn, &2)?, None);
+    /// assert_eq!(db.get(&wtxn, &3)?, Some("i-am-three"));
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn savepoint(&mut self) -> Result<Savepoint<'_>> {
+        let mut txn: *mut ffi::MDB_txn = ptr::null_mut();
+        let parent_ptr: *mut ffi::MDB_txn = unsafe { self.txn.inner.txn.unwrap().as_mut() };
+
+        unsafe {
+            mdb_result(ffi::mdb_txn_begin(
+                self.txn.inner.env.env_mut_ptr().as_mut(),
+                parent_ptr,
+                0,
+                &mut txn,
+            ))?
+        };
+
+        Ok(Savepoint {
+            txn: RwTxn {
+                txn: RoTxn {
+                    inner: RoTxnInner {
+                        txn: NonNull::new(txn),
+                        env: Cow::Borrowed(&*self.txn.inner.env),
+                        _watched: None,
+                    },
+                    _tls_marker: PhantomData,
+                },
+                hooks: TxnHooks::default(),
+                parent_hooks: Some(&mut self.hooks),
+                scratch: ScratchBuffers::default(),
+            },
+        })
+    }
+
+    /// Run the given closure in a [`Savepoint`] that is released if the closure
+    /// returns `Ok` and aborted if it returns `Err`.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let db: Database<BEI32, Str> = env.create_database(&mut wtxn, Some("atomically"))?;
+    /// # db.clear(&mut wtxn)?;
+    ///
+    /// let result: heed::Result<()> = wtxn.atomically(|sp| {
+    ///     db.put(sp, &1, "i-am-one")?;
+    ///     Err(heed::Error::Mdb(heed::MdbError::Panic))
+    /// });
+    /// assert!(result.is_err());
+    ///
+    /// wtxn.atomically(|sp| db.put(sp, &2, "i-am-two"))?;
+    ///
+    /// assert_eq!(db.get(&wtxn, &1)?, None);
+    /// assert_eq!(db.get(&wtxn, &2)?, Some("i-am-two"));
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn atomically<T, E, F>(&mut self, f: F) -> std::result::Result<T, E>
+    where
+        F: FnOnce(&mut RwTxn) -> std::result::Result<T, E>,
+        E: From<Error>,
+    {
+        let mut savepoint = self.savepoint()?;
+        match f(&mut savepoint) {
+            Ok(value) => {
+                savepoint.release()?;
+                Ok(value)
+            }
+            Err(e) => {
+                savepoint.abort();
+                Err(e)
+            }
+        }
+    }
+
+    /// Register a function to call once this transaction is successfully committed.
+    ///
+    /// The hooks of a nested transaction are moved to its parent when it is committed and
+    /// are therefore only called when the top-level transaction is committed. They are
+    /// dropped without being called if the transaction is aborted.
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// # use heed::EnvOpenOptions;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let env = unsafe { EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(dir.path())?
+    /// # };
+    /// let committed = Arc::new(AtomicBool::new(false));
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// let mut sp = wtxn.savepoint()?;
+    /// let committed_cloned = committed.clone();
+    /// sp.on_commit(move || committed_cloned.store(true, Ordering::SeqCst));
+    /// sp.release()?;
+    /// assert!(!committed.load(Ordering::SeqCst));
+    ///
+    /// wtxn.commit()?;
+    /// assert!(committed.load(Ordering::SeqCst));
+    /// # Ok(()) }
+    /// ```
+    pub fn on_commit<F>(&mut self, hook: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.hooks.on_commit.push(Box::new(hook));
+    }
+
+    /// Register a function to call once this transaction is aborted, either explicitly,
+    /// by being dropped or because the commit failed.
+    ///
+    /// The hooks of a nested transaction are moved to its parent when it is committed
+    /// and are therefore also called when the parent transaction is aborted.
+    pub fn on_abort<F>(&mut self, hook: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.hooks.on_abort.push(Box::new(hook));
+    }
+
     /// Commit all the operations of a transaction into the database.
     /// The transaction is reset.
     pub fn commit(mut self) -> Result<()> {
@@ -392,6 +633,13 @@ impl<'p> RwTxn<'p> {
         // committed/aborter and ensure we cannot use it two times.
         let mut txn = self.txn.inner.txn.take().unwrap();
         let result = unsafe { mdb_result(ffi::mdb_txn_commit(txn.as_mut())) };
+        let hooks = mem::take(&mut self.hooks);
+        match (&result, self.parent_hooks.take()) {
+            (Ok(()), Some(parent_hooks)) => parent_hooks.append(hooks),
+            (Ok(()), None) => hooks.run_commit(),
+            // A failed commit frees the transaction like an abort would do.
+            (Err(_), _) => hooks.run_abort(),
+        }
         result.map_err(Into::into)
     }
 
@@ -402,6 +650,67 @@ impl<'p> RwTxn<'p> {
         // committed/aborter and ensure we cannot use it twice.
         let mut txn = self.txn.inner.txn.take().unwrap();
         unsafe { ffi::mdb_txn_abort(txn.as_mut()) }
+        mem::take(&mut self.hooks).run_abort();
+    }
+}
+
+impl Drop for RwTxn<'_> {
+    fn drop(&mut self) {
+        if let Some(mut txn) = self.txn.inner.txn.take() {
+            // The transaction hasn't been committed nor aborted yet,
+            // we abort it before running the abort hooks.
+            unsafe { ffi::mdb_txn_abort(txn.as_mut()) }
+            mem::take(&mut self.hooks).run_abort();
+        }
+    }
+}
+
+type Hook = Box<dyn FnOnce() + Send>;
+
+/// The functions to call when a [`RwTxn`] is committed or aborted.
+#[derive(Default)]
+struct TxnHooks {
+    on_commit: Vec<Hook>,
+    on_abort: Vec<Hook>,
+}
+
+impl TxnHooks {
+    /// Moves the hooks of a committed nested transaction into its parent ones.
+    fn append(&mut self, mut child: TxnHooks) {
+        self.on_commit.append(&mut child.on_commit);
+        self.on_abort.append(&mut child.on_abort);
+    }
+
+    fn run_commit(self) {
+        self.on_commit.into_iter().for_each(|hook| hook());
+    }
+
+    fn run_abort(self) {
+        self.on_abort.into_iter().for_each(|hook| hook());
+    }
+}
+
+/// The buffers larger than this are released instead of being kept for the next writes.
+const MAX_SCRATCH_CAPACITY: usize = 1024 * 1024; // 1MiB
+
+/// The buffers reused by a [`RwTxn`] to encode the keys and values it writes,
+/// which avoids an allocation per write.
+#[derive(Default)]
+pub(crate) struct ScratchBuffers {
+    pub key: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+impl ScratchBuffers {
+    /// Clears the buffers and releases the ones that grew too large.
+    pub fn recycle(&mut self) {
+        for buffer in [&mut self.key, &mut self.data] {
+            if buffer.capacity() > MAX_SCRATCH_CAPACITY {
+                *buffer = Vec::new();
+            } else {
+                buffer.clear();
+            }
+        }
     }
 }
 
@@ -421,6 +730,41 @@ impl std::ops::DerefMut for RwTxn<'_> {
     }
 }
 
+/// A nested write transaction that is aborted when dropped unless it is released.
+///
+/// Created by [`RwTxn::savepoint`], it dereferences to a [`RwTxn`].
+pub struct Savepoint<'p> {
+    txn: RwTxn<'p>,
+}
+
+impl Savepoint<'_> {
+    /// Commit the operations of this savepoint into its parent transaction.
+    pub fn release(self) -> Result<()> {
+        self.txn.commit()
+    }
+
+    /// Abandon the operations of this savepoint.
+    ///
+    /// This is equivalent to dropping the savepoint.
+    pub fn abort(self) {
+        self.txn.abort()
+    }
+}
+
+impl<'p> Deref for Savepoint<'p> {
+    type Target = RwTxn<'p>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.txn
+    }
+}
+
+impl DerefMut for Savepoint<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.txn
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -440,4 +784,87 @@ mod tests {
 
         is_send::<RwTxn>();
     }
+
+    #[test]
+    fn nested_savepoints() {
+        use crate::types::*;
+        use crate::{Database, EnvOpenOptions};
+
+        let dir = tempfile::tempdir().unwrap();
+        let env =
+            unsafe { EnvOpenOptions::new().map_size(16 * 1024 * 1024).open(dir.path()) }.unwrap();
+
+        let mut wtxn = env.write_txn().unwrap();
+        let db: Database<Str, Str> = env.create_database(&mut wtxn, None).unwrap();
+
+        let mut outer = wtxn.savepoint().unwrap();
+        db.put(&mut outer, "outer", "kept").unwrap();
+        {
+            let mut inner = outer.savepoint().unwrap();
+            db.put(&mut inner, "inner", "dropped").unwrap();
+        }
+        let mut inner = outer.savepoint().unwrap();
+        db.put(&mut inner, "inner", "released").unwrap();
+        inner.release().unwrap();
+        outer.release().unwrap();
+
+        assert_eq!(db.get(&wtxn, "outer").unwrap(), Some("kept"));
+        assert_eq!(db.get(&wtxn, "inner").unwrap(), Some("released"));
+
+        let result: crate::Result<()> = wtxn.atomically(|sp| {
+            db.put(sp, "closure", "aborted")?;
+            Err(crate::Error::Mdb(crate::MdbError::Panic))
+        });
+        assert!(result.is_err());
+        assert_eq!(db.get(&wtxn, "closure").unwrap(), None);
+
+        wtxn.commit().unwrap();
+    }
+
+    #[test]
+    fn nested_hooks_are_propagated() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::EnvOpenOptions;
+
+        let dir = tempfile::tempdir().unwrap();
+        let env =
+            unsafe { EnvOpenOptions::new().map_size(16 * 1024 * 1024).open(dir.path()) }.unwrap();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let push = |event: &'static str| {
+            let events = events.clone();
+            move || events.lock().unwrap().push(event)
+        };
+
+        let mut wtxn = env.write_txn().unwrap();
+        wtxn.on_commit(push("parent commit"));
+        {
+            let mut child = env.nested_write_txn(&mut wtxn).unwrap();
+            child.on_commit(push("aborted child commit"));
+            child.on_abort(push("aborted child abort"));
+        }
+        let mut child = env.nested_write_txn(&mut wtxn).unwrap();
+        child.on_commit(push("child commit"));
+        child.on_abort(push("child abort"));
+        child.commit().unwrap();
+        assert_eq!(*events.lock().unwrap(), ["aborted child abort"]);
+
+        wtxn.commit().unwrap();
+        assert_eq!(
+            *events.lock().unwrap(),
+            ["aborted child abort", "parent commit", "child commit"]
+        );
+
+        events.lock().unwrap().clear();
+        let mut wtxn = env.write_txn().unwrap();
+        wtxn.atomically(|sp| {
+            sp.on_commit(push("savepoint commit"));
+            sp.on_abort(push("savepoint abort"));
+            crate::Result::Ok(())
+        })
+        .unwrap();
+        wtxn.abort();
+        assert_eq!(*events.lock().unwrap(), ["savepoint abort"]);
+    }
 }